@@ -0,0 +1,35 @@
+#![no_main]
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use subnet_calculator::address_math::HostBitsPolicy;
+use subnet_calculator::free_space::FitStrategy;
+use subnet_calculator::ipv4::calculator::{calculate, find_free_subnets, LIMIT};
+use subnet_calculator::ipv4::types::PlatformProfile;
+
+#[derive(Debug, Arbitrary)]
+struct Input<'a> {
+    ip: &'a str,
+    mask: &'a str,
+    hosts: Option<u32>,
+    subnets: Option<u32>,
+    platform: u8,
+    used: &'a str,
+    size: &'a str,
+    count: &'a str,
+    strategy: u8,
+    strict: bool,
+}
+
+fuzz_target!(|input: Input| {
+    let platform = PlatformProfile::ALL[input.platform as usize % PlatformProfile::ALL.len()];
+    let policy = if input.strict { HostBitsPolicy::Reject } else { HostBitsPolicy::Normalize };
+    if let Ok(result) = calculate(input.ip, input.mask, input.hosts, input.subnets, platform, policy) {
+        assert!(result.subnets.len() <= LIMIT);
+        assert!(policy == HostBitsPolicy::Normalize || result.host_bits.is_none());
+        assert!(result.subnets.iter().all(|s| result.base_network.contains(&s.network)));
+    }
+    let strategy = FitStrategy::ALL[input.strategy as usize % FitStrategy::ALL.len()];
+    if let Ok(result) = find_free_subnets(input.ip, input.mask, input.used, input.size, input.count, strategy, platform, policy) {
+        assert!(result.subnets.iter().all(|s| result.base_network.contains(&s.network)));
+    }
+});
@@ -0,0 +1,49 @@
+#![no_main]
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use subnet_calculator::address_math::HostBitsPolicy;
+use subnet_calculator::free_space::FitStrategy;
+use subnet_calculator::ipv6::calculator::{calculate, find_free_subnets, LIMIT};
+use subnet_calculator::ipv6::types::{HierarchyPlacement, SubnetMode};
+
+#[derive(Debug, Arbitrary)]
+struct Input<'a> {
+    addr: &'a str,
+    prefix: &'a str,
+    mode: u8,
+    needed: Option<u32>,
+    child_prefix: Option<u8>,
+    pd_pools: Option<u32>,
+    used: &'a str,
+    size: &'a str,
+    count: &'a str,
+    strategy: u8,
+    strict: bool,
+}
+
+const MODES: [SubnetMode; 4] = [SubnetMode::Inspect, SubnetMode::BySubnets, SubnetMode::ByPrefix, SubnetMode::PrefixDelegation];
+
+fuzz_target!(|input: Input| {
+    let mode = MODES[input.mode as usize % MODES.len()];
+    let policy = if input.strict { HostBitsPolicy::Reject } else { HostBitsPolicy::Normalize };
+    let result = calculate(
+        input.addr,
+        input.prefix,
+        mode,
+        input.needed,
+        input.child_prefix,
+        vec![],
+        HierarchyPlacement::Contiguous,
+        input.pd_pools,
+        policy,
+    );
+    if let Ok(result) = result {
+        assert!(result.subnets.len() <= LIMIT);
+        assert!(policy == HostBitsPolicy::Normalize || result.host_bits.is_none());
+        assert!(result.subnets.iter().all(|s| result.base_network.contains(&s.network)));
+    }
+    let strategy = FitStrategy::ALL[input.strategy as usize % FitStrategy::ALL.len()];
+    if let Ok(result) = find_free_subnets(input.addr, input.prefix, input.used, input.size, input.count, strategy, policy) {
+        assert!(result.subnets.iter().all(|s| result.base_network.contains(&s.network)));
+    }
+});
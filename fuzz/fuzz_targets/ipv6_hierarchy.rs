@@ -0,0 +1,37 @@
+#![no_main]
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use subnet_calculator::address_math::HostBitsPolicy;
+use subnet_calculator::ipv6::calculator::{calculate, flatten_hierarchy};
+use subnet_calculator::ipv6::types::{HierarchyLevel, HierarchyNode, HierarchyPlacement, SubnetMode};
+
+#[derive(Debug, Arbitrary)]
+struct Input<'a> {
+    addr: &'a str,
+    prefix: u8,
+    levels: Vec<(u32, u8)>,
+    sparse: bool,
+}
+
+fn check(node: &HierarchyNode) {
+    for child in &node.children {
+        assert!(node.prefix.contains(&child.prefix));
+        check(child);
+    }
+}
+
+fuzz_target!(|input: Input| {
+    let levels: Vec<HierarchyLevel> = input
+        .levels
+        .iter()
+        .take(8)
+        .enumerate()
+        .map(|(i, &(num, bits))| HierarchyLevel { name: format!("L{}", i), num, bits })
+        .collect();
+    let placement = if input.sparse { HierarchyPlacement::Sparse } else { HierarchyPlacement::Contiguous };
+    let result = calculate(input.addr, &input.prefix.to_string(), SubnetMode::ByHierarchy, None, None, levels, placement, None, HostBitsPolicy::Normalize);
+    if let Ok(Some(hierarchy)) = result.map(|r| r.hierarchy) {
+        hierarchy.tree.iter().for_each(check);
+        let _ = flatten_hierarchy(&hierarchy);
+    }
+});
@@ -0,0 +1,11 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+use subnet_calculator::ipv4::calculator::{calculate_wildcard, parse_network};
+
+fuzz_target!(|input: (&str, &str, &str)| {
+    let (ip, mask, test) = input;
+    if let Ok(net) = parse_network(ip, mask) {
+        assert!(net.prefix_len() <= 32);
+    }
+    let _ = calculate_wildcard(ip, mask, test);
+});
@@ -0,0 +1,382 @@
+// src/address_math.rs
+use ipnet::IpNet;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ArithmeticError {
+    Parse(String),
+    FamilyMismatch,
+    Overflow,                          // Past 255.255.255.255 / ffff:…:ffff
+    Underflow,                         // Before 0.0.0.0 / ::
+    HostOutOfRange { max: u128 },      // Host numbers are 1-based
+    SubnetOutOfRange { max: u128 },    // Subnet numbers are 1-based
+    InvalidPrefix { min: u8, max: u8 },
+}
+
+impl std::fmt::Display for ArithmeticError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArithmeticError::Parse(msg) => write!(f, "{}", msg),
+            ArithmeticError::FamilyMismatch => write!(f, "Both addresses must be IPv4 or both IPv6"),
+            ArithmeticError::Overflow => write!(f, "Result is past the end of the address space"),
+            ArithmeticError::Underflow => write!(f, "Result is before the start of the address space"),
+            ArithmeticError::HostOutOfRange { max } => write!(f, "Host number must be between 1 and {}", max),
+            ArithmeticError::SubnetOutOfRange { max } => write!(f, "Subnet number must be between 1 and {}", max),
+            ArithmeticError::InvalidPrefix { min, max } => write!(f, "Subnet size must be between /{} and /{}", min, max),
+        }
+    }
+}
+
+impl std::error::Error for ArithmeticError {}
+
+fn bits(addr: &IpAddr) -> u32 {
+    if addr.is_ipv4() { 32 } else { 128 }
+}
+
+/// Same u32/u128 conversion `calculate` uses for its last-N subnets
+pub fn to_u128(addr: IpAddr) -> u128 {
+    match addr {
+        IpAddr::V4(a) => u32::from(a) as u128,
+        IpAddr::V6(a) => u128::from(a),
+    }
+}
+
+/// Inverse of `to_u128` in the family of `like`; IPv4 values above u32::MAX overflow
+pub fn from_u128(value: u128, like: &IpAddr) -> Result<IpAddr, ArithmeticError> {
+    match like {
+        IpAddr::V4(_) => u32::try_from(value).map(|v| IpAddr::V4(Ipv4Addr::from(v))).map_err(|_| ArithmeticError::Overflow),
+        IpAddr::V6(_) => Ok(IpAddr::V6(Ipv6Addr::from(value))),
+    }
+}
+
+/// 2^(bits - len), or None for the 2^128 addresses of ::/0
+fn block_size(addr_bits: u32, len: u8) -> Option<u128> {
+    1u128.checked_shl(addr_bits - len as u32)
+}
+
+/// Offset such as "10", "+10" or "-10"; magnitudes up to 2^128 - 1 for IPv6
+pub fn parse_offset(text: &str) -> Result<(bool, u128), ArithmeticError> {
+    let text = text.trim();
+    let (negative, digits) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text.strip_prefix('+').unwrap_or(text)),
+    };
+    digits
+        .trim()
+        .parse::<u128>()
+        .map(|n| (negative, n))
+        .map_err(|_| ArithmeticError::Parse(format!("'{}' is not a whole number", text)))
+}
+
+/// `addr` moved `amount` addresses forward (or backward when `negative`)
+pub fn offset(addr: IpAddr, negative: bool, amount: u128) -> Result<IpAddr, ArithmeticError> {
+    let value = to_u128(addr);
+    let moved = if negative {
+        value.checked_sub(amount).ok_or(ArithmeticError::Underflow)?
+    } else {
+        value.checked_add(amount).ok_or(ArithmeticError::Overflow)?
+    };
+    from_u128(moved, &addr)
+}
+
+/// Addresses from `from` to `to`, and whether `to` comes before `from`
+pub fn distance(from: IpAddr, to: IpAddr) -> Result<(u128, bool), ArithmeticError> {
+    if from.is_ipv4() != to.is_ipv4() {
+        return Err(ArithmeticError::FamilyMismatch);
+    }
+    let (a, b) = (to_u128(from), to_u128(to));
+    Ok(if b >= a { (b - a, false) } else { (a - b, true) })
+}
+
+/// What to do with input like 192.168.1.77/24, whose address isn't the network address
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HostBitsPolicy {
+    /// Use the network address and report where the typed address sits in it
+    #[default]
+    Normalize,
+    /// Strict mode: a host address where a network was expected is an error
+    Reject,
+}
+
+/// A prefix typed with host bits set, and where that address falls in its network
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HostBits {
+    pub input: IpNet,
+    pub network: IpNet,           // Normalized
+    pub offset: u128,             // Addresses past the network address
+    pub host_index: Option<u128>, // Numbered like `nth_host`; None for an IPv4 broadcast address
+}
+
+impl std::fmt::Display for HostBits {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} has host bits set; the network is {} (", self.input, self.network)?;
+        match self.host_index {
+            Some(n) => write!(f, "host {}, offset +{})", n, self.offset),
+            None => write!(f, "broadcast address, offset +{})", self.offset),
+        }
+    }
+}
+
+/// Host-bit details for `net`, or None when its address is already the network address
+pub fn host_bits(net: IpNet) -> Option<HostBits> {
+    let offset = to_u128(net.addr()) - to_u128(net.network());
+    if offset == 0 {
+        return None;
+    }
+    // Inverse of `nth_host`
+    let host_index = match net {
+        IpNet::V4(v4) if v4.prefix_len() < 31 => Some(offset).filter(|_| net.addr() != net.broadcast()),
+        _ => Some(offset + 1),
+    };
+    Some(HostBits { input: net, network: net.trunc(), offset, host_index })
+}
+
+/// Apply `policy` to a parsed prefix: Ok with the host-bit details to warn about, or Err in strict mode
+pub fn check_host_bits(net: IpNet, policy: HostBitsPolicy) -> Result<Option<HostBits>, HostBits> {
+    match (host_bits(net), policy) {
+        (Some(found), HostBitsPolicy::Reject) => Err(found),
+        (found, _) => Ok(found),
+    }
+}
+
+/// The n-th usable host (1-based). IPv4 skips the network and broadcast addresses except on
+/// /31 and /32; IPv6 counts from the network address, like the summary's first host.
+pub fn nth_host(net: IpNet, n: u128) -> Result<IpAddr, ArithmeticError> {
+    let network = net.network();
+    let (first, count) = match net {
+        IpNet::V4(v4) if v4.prefix_len() >= 31 => (0, block_size(32, v4.prefix_len()).unwrap_or(1)),
+        IpNet::V4(v4) => (1, block_size(32, v4.prefix_len()).unwrap_or(0) - 2),
+        IpNet::V6(v6) => (0, block_size(128, v6.prefix_len()).unwrap_or(u128::MAX)),
+    };
+    if n == 0 || n > count {
+        return Err(ArithmeticError::HostOutOfRange { max: count });
+    }
+    offset(network, false, first + (n - 1))
+}
+
+/// The n-th (1-based) /len subnet inside `parent`
+pub fn nth_subnet(parent: IpNet, len: u8, n: u128) -> Result<IpNet, ArithmeticError> {
+    let addr_bits = bits(&parent.network());
+    if len < parent.prefix_len() || len as u32 > addr_bits {
+        return Err(ArithmeticError::InvalidPrefix { min: parent.prefix_len(), max: addr_bits as u8 });
+    }
+    let count = 1u128.checked_shl((len - parent.prefix_len()) as u32).unwrap_or(u128::MAX);
+    if n == 0 || n > count {
+        return Err(ArithmeticError::SubnetOutOfRange { max: count });
+    }
+    let step = block_size(addr_bits, len).unwrap_or(0);
+    let start = (n - 1).checked_mul(step).ok_or(ArithmeticError::Overflow)?;
+    let addr = offset(parent.network(), false, start)?;
+    IpNet::new(addr, len).map_err(|_| ArithmeticError::InvalidPrefix { min: parent.prefix_len(), max: addr_bits as u8 })
+}
+
+/// The adjacent subnet of the same size, after (`next`) or before `net`
+pub fn neighbor(net: IpNet, next: bool) -> Result<IpNet, ArithmeticError> {
+    let network = net.network();
+    let size = block_size(bits(&network), net.prefix_len()).ok_or(if next { ArithmeticError::Overflow } else { ArithmeticError::Underflow })?;
+    let addr = offset(network, !next, size)?;
+    IpNet::new(addr, net.prefix_len()).map_err(|_| ArithmeticError::Overflow)
+}
+
+pub fn parse_address(text: &str) -> Result<IpAddr, ArithmeticError> {
+    text.trim().parse::<IpAddr>().map_err(|_| ArithmeticError::Parse(format!("'{}' is not an IP address", text.trim())))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArithmeticOp {
+    Offset,
+    Distance,
+    NthHost,
+    NthSubnet,
+    Neighbors,
+}
+
+impl ArithmeticOp {
+    pub const ALL: [ArithmeticOp; 5] = [ArithmeticOp::Offset, ArithmeticOp::Distance, ArithmeticOp::NthHost, ArithmeticOp::NthSubnet, ArithmeticOp::Neighbors];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ArithmeticOp::Offset => "Address ± offset",
+            ArithmeticOp::Distance => "Distance between addresses",
+            ArithmeticOp::NthHost => "Nth host",
+            ArithmeticOp::NthSubnet => "Nth subnet",
+            ArithmeticOp::Neighbors => "Previous / next subnet",
+        }
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            ArithmeticOp::Offset => "offset",
+            ArithmeticOp::Distance => "distance",
+            ArithmeticOp::NthHost => "host",
+            ArithmeticOp::NthSubnet => "subnet",
+            ArithmeticOp::Neighbors => "neighbors",
+        }
+    }
+
+    pub fn from_key(key: &str) -> ArithmeticOp {
+        ArithmeticOp::ALL.into_iter().find(|op| op.key() == key).unwrap_or(ArithmeticOp::Offset)
+    }
+}
+
+fn parse_number(text: &str, what: &str) -> Result<u128, ArithmeticError> {
+    text.trim().trim_start_matches('#').parse::<u128>().map_err(|_| ArithmeticError::Parse(format!("{} must be a whole number", what)))
+}
+
+/// Run `op` against `network` with the panel's two free-form inputs; addresses default to the
+/// network address when left empty. Returns the lines to display.
+pub fn evaluate(op: ArithmeticOp, network: IpNet, first: &str, second: &str) -> Result<Vec<String>, ArithmeticError> {
+    let address = |text: &str| if text.trim().is_empty() { Ok(network.network()) } else { parse_address(text) };
+    // Nothing to show until the required inputs are filled in
+    let missing = match op {
+        ArithmeticOp::Offset | ArithmeticOp::Distance => second.trim().is_empty(),
+        ArithmeticOp::NthHost => first.trim().is_empty(),
+        ArithmeticOp::NthSubnet => first.trim().is_empty() || second.trim().is_empty(),
+        ArithmeticOp::Neighbors => false,
+    };
+    if missing {
+        return Ok(vec![]);
+    }
+    match op {
+        ArithmeticOp::Offset => {
+            let addr = address(first)?;
+            let (negative, amount) = parse_offset(second)?;
+            let result = offset(addr, negative, amount)?;
+            Ok(vec![format!("{} {} {} = {}", addr, if negative { "-" } else { "+" }, amount, result)])
+        }
+        ArithmeticOp::Distance => {
+            let (from, to) = (address(first)?, parse_address(second)?);
+            let (count, backwards) = distance(from, to)?;
+            Ok(vec![
+                format!("{} → {}: {}{} addresses", from, to, if backwards { "-" } else { "+" }, count),
+                format!("{} addresses inclusive", count.saturating_add(1)),
+            ])
+        }
+        ArithmeticOp::NthHost => {
+            let n = parse_number(first, "Host number")?;
+            Ok(vec![format!("Host #{} of {} = {}", n, network, nth_host(network, n)?)])
+        }
+        ArithmeticOp::NthSubnet => {
+            let len = second.trim().trim_start_matches('/').parse::<u8>().map_err(|_| ArithmeticError::Parse("Subnet size must be a prefix length like /26".into()))?;
+            let n = parse_number(first, "Subnet number")?;
+            Ok(vec![format!("Subnet #{} (/{}) of {} = {}", n, len, network, nth_subnet(network, len, n)?)])
+        }
+        ArithmeticOp::Neighbors => {
+            let show = |result: Result<IpNet, ArithmeticError>| result.map(|n| n.to_string()).unwrap_or_else(|e| e.to_string());
+            Ok(vec![
+                format!("Previous: {}", show(neighbor(network, false))),
+                format!("Next: {}", show(neighbor(network, true))),
+            ])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    fn net(s: &str) -> IpNet {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn offsets_and_distances() {
+        assert_eq!(offset(ip("10.0.0.250"), false, 10), Ok(ip("10.0.1.4")));
+        assert_eq!(offset(ip("10.0.0.5"), true, 10), Ok(ip("9.255.255.251")));
+        assert_eq!(offset(ip("255.255.255.255"), false, 1), Err(ArithmeticError::Overflow));
+        assert_eq!(offset(ip("::"), true, 1), Err(ArithmeticError::Underflow));
+        assert_eq!(offset(ip("::"), false, u128::MAX), Ok(ip("ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff")));
+        assert_eq!(distance(ip("10.0.0.10"), ip("10.0.0.1")), Ok((9, true)));
+        assert_eq!(distance(ip("10.0.0.1"), ip("::1")), Err(ArithmeticError::FamilyMismatch));
+        assert_eq!(parse_offset(" -42"), Ok((true, 42)));
+        assert_eq!(parse_offset("+7"), Ok((false, 7)));
+        assert!(parse_offset("ten").is_err());
+    }
+
+    #[test]
+    fn nth_host_and_subnet() {
+        assert_eq!(nth_host(net("10.0.0.0/24"), 1), Ok(ip("10.0.0.1")));
+        assert_eq!(nth_host(net("10.0.0.0/24"), 254), Ok(ip("10.0.0.254")));
+        assert_eq!(nth_host(net("10.0.0.0/24"), 255), Err(ArithmeticError::HostOutOfRange { max: 254 }));
+        assert_eq!(nth_host(net("10.0.0.0/31"), 2), Ok(ip("10.0.0.1")));
+        assert_eq!(nth_host(net("2001:db8::/64"), 1), Ok(ip("2001:db8::")));
+        assert_eq!(nth_subnet(net("10.0.0.0/16"), 24, 3), Ok(net("10.0.2.0/24")));
+        assert_eq!(nth_subnet(net("10.0.0.0/16"), 24, 257), Err(ArithmeticError::SubnetOutOfRange { max: 256 }));
+        assert_eq!(nth_subnet(net("10.0.0.0/16"), 8, 1), Err(ArithmeticError::InvalidPrefix { min: 16, max: 32 }));
+        assert_eq!(nth_subnet(net("::/0"), 128, u128::MAX), Ok(net("ffff:ffff:ffff:ffff:ffff:ffff:ffff:fffe/128")));
+    }
+
+    #[test]
+    fn neighbors_stop_at_the_edges() {
+        assert_eq!(neighbor(net("10.0.1.0/24"), true), Ok(net("10.0.2.0/24")));
+        assert_eq!(neighbor(net("10.0.1.0/24"), false), Ok(net("10.0.0.0/24")));
+        assert_eq!(neighbor(net("0.0.0.0/8"), false), Err(ArithmeticError::Underflow));
+        assert_eq!(neighbor(net("255.0.0.0/8"), true), Err(ArithmeticError::Overflow));
+        assert_eq!(neighbor(net("::/0"), true), Err(ArithmeticError::Overflow));
+    }
+
+    #[test]
+    fn evaluate_waits_for_required_inputs() {
+        let network = net("192.168.0.0/24");
+        assert_eq!(evaluate(ArithmeticOp::Offset, network, "", ""), Ok(vec![]));
+        assert_eq!(evaluate(ArithmeticOp::Offset, network, "", "5"), Ok(vec!["192.168.0.0 + 5 = 192.168.0.5".to_string()]));
+        assert_eq!(evaluate(ArithmeticOp::NthHost, network, "#3", ""), Ok(vec!["Host #3 of 192.168.0.0/24 = 192.168.0.3".to_string()]));
+        assert_eq!(evaluate(ArithmeticOp::NthSubnet, network, "2", "/26"), Ok(vec!["Subnet #2 (/26) of 192.168.0.0/24 = 192.168.0.64/26".to_string()]));
+        assert!(evaluate(ArithmeticOp::Distance, network, "", "::1").is_err());
+        for op in ArithmeticOp::ALL {
+            assert_eq!(ArithmeticOp::from_key(op.key()), op);
+        }
+    }
+
+    #[test]
+    fn locates_host_bits() {
+        let found = host_bits(net("192.168.1.77/24")).unwrap();
+        assert_eq!(found.network, net("192.168.1.0/24"));
+        assert_eq!((found.offset, found.host_index), (77, Some(77)));
+        assert_eq!(found.to_string(), "192.168.1.77/24 has host bits set; the network is 192.168.1.0/24 (host 77, offset +77)");
+        assert_eq!(host_bits(net("192.168.1.255/24")).unwrap().host_index, None);
+        assert_eq!(host_bits(net("10.0.0.1/31")).unwrap().host_index, Some(2));
+        assert_eq!(host_bits(net("2001:db8::1/64")).unwrap().host_index, Some(2));
+        assert_eq!(host_bits(net("192.168.1.0/24")), None);
+
+        assert_eq!(check_host_bits(net("10.0.0.0/8"), HostBitsPolicy::Reject), Ok(None));
+        assert!(check_host_bits(net("10.0.0.5/8"), HostBitsPolicy::Normalize).unwrap().is_some());
+        assert!(check_host_bits(net("10.0.0.5/8"), HostBitsPolicy::Reject).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn host_index_matches_nth_host(addr in any::<u32>(), len in 0u8..=32) {
+            let typed = IpNet::new(IpAddr::V4(addr.into()), len).unwrap();
+            if let Some(found) = host_bits(typed) {
+                prop_assert_eq!(found.network.network(), typed.network());
+                if let Some(n) = found.host_index {
+                    prop_assert_eq!(nth_host(found.network, n), Ok(typed.addr()));
+                }
+            }
+        }
+
+        #[test]
+        fn offset_and_distance_round_trip(a in any::<u128>(), b in any::<u128>()) {
+            let (from, to) = (IpAddr::V6(a.into()), IpAddr::V6(b.into()));
+            let (count, backwards) = distance(from, to).unwrap();
+            prop_assert_eq!(offset(from, backwards, count), Ok(to));
+        }
+
+        #[test]
+        fn nth_subnet_lies_inside_its_parent(addr in any::<u32>(), len in 0u8..=32, extra in 0u8..=8, n in any::<u32>()) {
+            let parent = IpNet::new(IpAddr::V4(addr.into()), len).unwrap().trunc();
+            let sub_len = (len + extra).min(32);
+            let count = 1u128 << (sub_len - len);
+            let n = n as u128 % count + 1;
+            let subnet = nth_subnet(parent, sub_len, n).unwrap();
+            prop_assert!(parent.contains(&subnet));
+            prop_assert_eq!(subnet.prefix_len(), sub_len);
+        }
+    }
+}
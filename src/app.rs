@@ -1,6 +1,6 @@
 // src/app.rs
 use dioxus::prelude::*;
-use crate::{components::{ActiveTab, Footer, Header, Tabs}, ipv4::Ipv4Tab, ipv6::Ipv6Tab};
+use crate::{components::{ActiveTab, Footer, Header, Tabs}, dual_stack::DualStackTab, ipv4::Ipv4Tab, ipv6::{Ipv6Tab, SharedIpv6Network}, tools::ToolsTab, workspace::WorkspaceTab};
 
 #[derive(Props, Clone, PartialEq)]
 struct PlaceholderProps {
@@ -9,6 +9,7 @@ struct PlaceholderProps {
 
 pub fn App() -> Element {
     let mut active_tab = use_signal(|| ActiveTab::Ipv4);
+    use_context_provider(|| SharedIpv6Network(Signal::new(None)));
 
     rsx! {
         document::Stylesheet {
@@ -37,6 +38,30 @@ pub fn App() -> Element {
                     }
                 }
 
+                // Dual-Stack Tab Content
+                div { class: if *active_tab.read() == ActiveTab::DualStack { "" } else { "hidden" },
+                    div { class: "text-center py-1",
+                        h2 { class: "text-2xl font-bold mb-4", "Dual-Stack Planner" }
+                        DualStackTab {}
+                    }
+                }
+
+                // Workspace Tab Content
+                div { class: if *active_tab.read() == ActiveTab::Workspace { "" } else { "hidden" },
+                    div { class: "text-center py-1",
+                        h2 { class: "text-2xl font-bold mb-4", "IPAM Workspace" }
+                        WorkspaceTab {}
+                    }
+                }
+
+                // Tools Tab Content
+                div { class: if *active_tab.read() == ActiveTab::Tools { "" } else { "hidden" },
+                    div { class: "text-center py-1",
+                        h2 { class: "text-2xl font-bold mb-4", "Network Tools" }
+                        ToolsTab {}
+                    }
+                }
+
                 // Converter Tab Content
                 div { class: if *active_tab.read() == ActiveTab::Converter { "" } else { "hidden" },
                     div { class: "text-center py-16",
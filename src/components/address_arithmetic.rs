@@ -0,0 +1,69 @@
+// src/components/address_arithmetic.rs
+use dioxus::prelude::*;
+use ipnet::IpNet;
+use crate::address_math::{evaluate, ArithmeticOp};
+
+/// Offset, distance, Nth host/subnet and neighbor lookups relative to the calculated network
+#[component]
+pub fn AddressArithmetic(network: IpNet) -> Element {
+    let mut op = use_signal(|| ArithmeticOp::Offset);
+    let mut first = use_signal(String::new);
+    let mut second = use_signal(String::new);
+
+    let (first_label, first_placeholder, second_label, second_placeholder) = match op() {
+        ArithmeticOp::Offset => ("Address", network.network().to_string(), "Offset", "e.g. +10 or -10".to_string()),
+        ArithmeticOp::Distance => ("From", network.network().to_string(), "To", network.broadcast().to_string()),
+        ArithmeticOp::NthHost => ("Host #", "e.g. 1000".to_string(), "", String::new()),
+        ArithmeticOp::NthSubnet => ("Subnet #", "e.g. 5".to_string(), "Size", format!("e.g. /{}", network.prefix_len().saturating_add(2).min(network.max_prefix_len()))),
+        ArithmeticOp::Neighbors => ("", String::new(), "", String::new()),
+    };
+    let output = evaluate(op(), network, &first(), &second());
+    let field = "w-full px-3 py-2 text-sm border border-gray-600 rounded-lg bg-gray-700";
+
+    rsx! {
+        div { class: "mt-6 p-4 bg-gray-700/40 rounded-lg text-left",
+            h3 { class: "font-bold mb-3", "Address Arithmetic" }
+            div { class: "grid grid-cols-3 gap-2 mb-3",
+                select {
+                    class: "{field}",
+                    onchange: move |e| {
+                        op.set(ArithmeticOp::from_key(&e.value()));
+                        first.set(String::new());
+                        second.set(String::new());
+                    },
+                    for o in ArithmeticOp::ALL {
+                        option { value: o.key(), selected: op() == o, "{o.label()}" }
+                    }
+                }
+                if !first_label.is_empty() {
+                    input {
+                        class: "{field}",
+                        title: first_label,
+                        placeholder: "{first_label}: {first_placeholder}",
+                        value: "{first}",
+                        oninput: move |e| first.set(e.value())
+                    }
+                }
+                if !second_label.is_empty() {
+                    input {
+                        class: "{field}",
+                        title: second_label,
+                        placeholder: "{second_label}: {second_placeholder}",
+                        value: "{second}",
+                        oninput: move |e| second.set(e.value())
+                    }
+                }
+            }
+            match output {
+                Ok(lines) => rsx! {
+                    for line in lines {
+                        p { class: "text-sm font-roboto", "{line}" }
+                    }
+                },
+                Err(err) => rsx! {
+                    p { class: "text-sm text-red-300", "{err}" }
+                },
+            }
+        }
+    }
+}
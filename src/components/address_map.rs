@@ -0,0 +1,358 @@
+// src/components/address_map.rs
+use dioxus::prelude::*;
+use std::f64::consts::PI;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use crate::components::ExportButtons;
+use crate::ipv6::types::HierarchyNode;
+
+const BAR_WIDTH: f64 = 800.0;
+const BAR_HEIGHT: f64 = 60.0;
+const MAX_BAR_SEGMENTS: u128 = 64;
+const SUNBURST_SIZE: f64 = 500.0;
+const SUNBURST_CENTER: f64 = 60.0;
+const MAX_RINGS: usize = 4;
+const MAX_ARCS_PER_RING: usize = 512;
+
+const UNUSED_FILL: &str = "#4b5563";
+const PARTIAL_FILL: &str = "#f59e0b";
+const PALETTE: &[&str] = &["#2563eb", "#16a34a", "#9333ea", "#db2777", "#0891b2", "#ca8a04"];
+
+/// Where a click on a shape drills down to
+#[derive(Clone, Debug, PartialEq)]
+pub enum Drill {
+    Range { start: u128, end: u128 },
+    Node(Vec<usize>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Shape {
+    pub d: String,
+    pub fill: &'static str,
+    pub title: String,
+    pub drill: Option<Drill>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Label {
+    pub x: f64,
+    pub y: f64,
+    pub text: String,
+}
+
+/// Geometry of one rendered map, shared by the interactive view and the SVG export
+#[derive(Clone, Debug, PartialEq)]
+pub struct MapScene {
+    pub width: f64,
+    pub height: f64,
+    pub shapes: Vec<Shape>,
+    pub labels: Vec<Label>,
+}
+
+impl MapScene {
+    pub fn to_svg(&self) -> String {
+        let mut out = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\" font-family=\"sans-serif\">\n",
+            w = self.width,
+            h = self.height
+        );
+        for shape in &self.shapes {
+            out.push_str(&format!(
+                "  <path d=\"{}\" fill=\"{}\" stroke=\"#111827\" stroke-width=\"0.5\"><title>{}</title></path>\n",
+                shape.d,
+                shape.fill,
+                escape_xml(&shape.title)
+            ));
+        }
+        for label in &self.labels {
+            out.push_str(&format!(
+                "  <text x=\"{:.1}\" y=\"{:.1}\" font-size=\"11\" fill=\"#f9fafb\" text-anchor=\"middle\">{}</text>\n",
+                label.x,
+                label.y,
+                escape_xml(&label.text)
+            ));
+        }
+        out.push_str("</svg>\n");
+        out
+    }
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn addr_string(value: u128, is_v4: bool) -> String {
+    if is_v4 {
+        Ipv4Addr::from(value as u32).to_string()
+    } else {
+        Ipv6Addr::from(value).to_string()
+    }
+}
+
+fn rect_path(x: f64, y: f64, w: f64, h: f64) -> String {
+    format!("M{x:.2} {y:.2}h{w:.2}v{h:.2}h{:.2}Z", -w)
+}
+
+fn sector_path(c: f64, r0: f64, r1: f64, a0: f64, a1: f64) -> String {
+    // A closed ring cannot be drawn as a single arc, so stop just short of it
+    let a1 = if a1 - a0 >= 2.0 * PI { a0 + 2.0 * PI - 1e-4 } else { a1 };
+    let large = if a1 - a0 > PI { 1 } else { 0 };
+    let point = |r: f64, a: f64| (c + r * a.sin(), c - r * a.cos());
+    let (x0, y0) = point(r1, a0);
+    let (x1, y1) = point(r1, a1);
+    let (x2, y2) = point(r0, a1);
+    let (x3, y3) = point(r0, a0);
+    format!(
+        "M{x0:.2} {y0:.2}A{r1:.2} {r1:.2} 0 {large} 1 {x1:.2} {y1:.2}L{x2:.2} {y2:.2}A{r0:.2} {r0:.2} 0 {large} 0 {x3:.2} {y3:.2}Z"
+    )
+}
+
+/// Proportional bar of equal-size subnets `[start, end)`, the first `used` of which are allocated
+pub fn split_bar(base: u128, subnet_size: u128, new_prefix: u8, used: u128, start: u128, end: u128, is_v4: bool) -> MapScene {
+    let len = end - start;
+    let per_segment = len.div_ceil(MAX_BAR_SEGMENTS).max(1);
+    let segments = len.div_ceil(per_segment);
+    let mut shapes = vec![];
+    let mut labels = vec![];
+
+    for i in 0..segments {
+        let first = start + i * per_segment;
+        let last = (first + per_segment).min(end);
+        let x = (first - start) as f64 / len as f64 * BAR_WIDTH;
+        let w = (last - first) as f64 / len as f64 * BAR_WIDTH;
+        let fill = if last <= used {
+            PALETTE[0]
+        } else if first >= used {
+            UNUSED_FILL
+        } else {
+            PARTIAL_FILL
+        };
+        let first_net = base + first * subnet_size;
+        let (title, drill) = if last - first == 1 {
+            (format!("#{} {}/{}", first + 1, addr_string(first_net, is_v4), new_prefix), None)
+        } else {
+            let last_net = base + (last - 1) * subnet_size;
+            (
+                format!(
+                    "#{}–#{} {}/{} … {}/{}",
+                    first + 1,
+                    last,
+                    addr_string(first_net, is_v4),
+                    new_prefix,
+                    addr_string(last_net, is_v4),
+                    new_prefix
+                ),
+                Some(Drill::Range { start: first, end: last }),
+            )
+        };
+        if segments <= 8 {
+            labels.push(Label { x: x + w / 2.0, y: BAR_HEIGHT / 2.0 + 24.0, text: format!("{}/{}", addr_string(first_net, is_v4), new_prefix) });
+        }
+        shapes.push(Shape { d: rect_path(x, 20.0, w, BAR_HEIGHT), fill, title, drill });
+    }
+
+    MapScene { width: BAR_WIDTH, height: BAR_HEIGHT + 40.0, shapes, labels }
+}
+
+/// Sunburst of the hierarchy below `path`; grey ring background is space no child occupies
+pub fn hierarchy_sunburst(roots: &[HierarchyNode], path: &[usize]) -> MapScene {
+    let c = SUNBURST_SIZE / 2.0;
+    let mut shapes = vec![];
+    let mut labels = vec![];
+
+    let Some(focus) = node_at(roots, path) else {
+        return MapScene { width: SUNBURST_SIZE, height: SUNBURST_SIZE, shapes, labels };
+    };
+    let focus_start = u128::from(focus.prefix.network());
+    let focus_size = 2f64.powi(128 - focus.prefix.prefix_len() as i32);
+
+    // Collect descendants ring by ring, keeping their path for drill-down
+    let mut rings: Vec<Vec<(Vec<usize>, &HierarchyNode)>> = vec![];
+    let mut current = vec![(path.to_vec(), focus)];
+    while rings.len() < MAX_RINGS {
+        let next: Vec<_> = current
+            .iter()
+            .flat_map(|(p, n)| {
+                n.children.iter().enumerate().map(move |(i, child)| {
+                    let mut child_path = p.clone();
+                    child_path.push(i);
+                    (child_path, child)
+                })
+            })
+            .collect();
+        if next.is_empty() {
+            break;
+        }
+        rings.push(next.clone());
+        current = next;
+    }
+
+    shapes.push(Shape {
+        d: sector_path(c, 0.0, SUNBURST_CENTER, 0.0, 2.0 * PI),
+        fill: PALETTE[0],
+        title: format!("{} {}", focus.label, focus.prefix),
+        drill: (!path.is_empty()).then(|| Drill::Node(path[..path.len() - 1].to_vec())),
+    });
+    labels.push(Label { x: c, y: c + 4.0, text: focus.prefix.to_string() });
+
+    let ring_width = if rings.is_empty() { 0.0 } else { (c - 10.0 - SUNBURST_CENTER) / rings.len() as f64 };
+    for (depth, ring) in rings.iter().enumerate() {
+        let r0 = SUNBURST_CENTER + depth as f64 * ring_width;
+        let r1 = r0 + ring_width;
+        let fill = PALETTE[(depth + 1) % PALETTE.len()];
+        shapes.push(Shape { d: sector_path(c, r0, r1, 0.0, 2.0 * PI), fill: UNUSED_FILL, title: "Unused".to_string(), drill: None });
+
+        let angle = |node: &HierarchyNode| {
+            let offset = (u128::from(node.prefix.network()) - focus_start) as f64;
+            let size = 2f64.powi(128 - node.prefix.prefix_len() as i32);
+            (offset / focus_size * 2.0 * PI, (offset + size) / focus_size * 2.0 * PI)
+        };
+
+        if ring.len() <= MAX_ARCS_PER_RING {
+            for (child_path, node) in ring {
+                let (a0, a1) = angle(node);
+                shapes.push(Shape {
+                    d: sector_path(c, r0, r1, a0, a1),
+                    fill,
+                    title: format!("{} {}", node.label, node.prefix),
+                    drill: (!node.children.is_empty()).then(|| Drill::Node(child_path.clone())),
+                });
+            }
+        } else {
+            // Too many nodes to draw one by one: merge touching neighbours into runs
+            let mut runs: Vec<(f64, f64, usize)> = vec![];
+            for (_, node) in ring {
+                let (a0, a1) = angle(node);
+                match runs.last_mut() {
+                    Some(run) if (run.1 - a0).abs() < 1e-9 => { run.1 = a1; run.2 += 1; }
+                    _ => runs.push((a0, a1, 1)),
+                }
+            }
+            for (a0, a1, count) in runs {
+                shapes.push(Shape { d: sector_path(c, r0, r1, a0, a1), fill, title: format!("{count} blocks"), drill: None });
+            }
+        }
+    }
+
+    MapScene { width: SUNBURST_SIZE, height: SUNBURST_SIZE, shapes, labels }
+}
+
+fn node_at<'a>(roots: &'a [HierarchyNode], path: &[usize]) -> Option<&'a HierarchyNode> {
+    let mut node = roots.first()?;
+    for &i in path {
+        node = node.children.get(i)?;
+    }
+    Some(node)
+}
+
+#[component]
+fn SceneView(scene: MapScene, on_drill: EventHandler<Drill>) -> Element {
+    let mut hovered = use_signal(String::new);
+
+    rsx! {
+        svg {
+            class: "w-full",
+            view_box: "0 0 {scene.width} {scene.height}",
+            {scene.shapes.into_iter().map(|shape| {
+                let title = shape.title.clone();
+                let drill = shape.drill.clone();
+                let cursor = if drill.is_some() { "cursor-pointer hover:opacity-80" } else { "" };
+                rsx! {
+                    path {
+                        class: "{cursor}",
+                        d: "{shape.d}",
+                        fill: "{shape.fill}",
+                        stroke: "#111827",
+                        stroke_width: "0.5",
+                        onmouseenter: move |_| hovered.set(title.clone()),
+                        onclick: move |_| if let Some(d) = drill.clone() { on_drill.call(d) },
+                    }
+                }
+            })}
+            for label in scene.labels {
+                text {
+                    x: "{label.x}",
+                    y: "{label.y}",
+                    font_size: "11",
+                    fill: "#f9fafb",
+                    text_anchor: "middle",
+                    "{label.text}"
+                }
+            }
+        }
+        p { class: "mt-2 text-sm text-gray-400 text-center min-h-5", "{hovered}" }
+    }
+}
+
+#[component]
+fn Legend() -> Element {
+    rsx! {
+        div { class: "flex justify-center space-x-6 text-xs text-gray-400 mt-2",
+            span { span { class: "inline-block w-3 h-3 mr-1 align-middle", style: "background:{PALETTE[0]}" } "Allocated" }
+            span { span { class: "inline-block w-3 h-3 mr-1 align-middle", style: "background:{PARTIAL_FILL}" } "Partly allocated" }
+            span { span { class: "inline-block w-3 h-3 mr-1 align-middle", style: "background:{UNUSED_FILL}" } "Unused" }
+        }
+    }
+}
+
+/// Bar map of an equal split; buckets of many subnets can be clicked to zoom in
+#[component]
+pub fn SplitMap(base: u128, new_prefix: u8, total: u128, used: u128, is_v4: bool) -> Element {
+    let bits = if is_v4 { 32 } else { 128 };
+    let subnet_size = 1u128.checked_shl((bits - new_prefix) as u32).unwrap_or(0);
+    let mut range = use_signal(|| (0u128, total));
+    let (start, end) = range();
+    let scene = split_bar(base, subnet_size, new_prefix, used, start, end, is_v4);
+    let svg = scene.to_svg();
+
+    rsx! {
+        div { class: "text-left",
+            div { class: "flex items-center justify-between mb-2",
+                span { class: "text-sm text-gray-400", "Subnets #{start + 1} – #{end} of {total}" }
+                div { class: "flex space-x-2",
+                    if start != 0 || end != total {
+                        button {
+                            class: "text-sm bg-gray-600 hover:bg-gray-500 px-3 py-1 rounded",
+                            onclick: move |_| range.set((0, total)),
+                            "Reset zoom"
+                        }
+                    }
+                    ExportButtons { content: svg, filename: "address-map.svg", mime: "image/svg+xml" }
+                }
+            }
+            SceneView {
+                scene,
+                on_drill: move |d| if let Drill::Range { start, end } = d { range.set((start, end)) }
+            }
+            Legend {}
+        }
+    }
+}
+
+/// Sunburst map of a hierarchy; click a ring segment to drill down, the centre to go back up
+#[component]
+pub fn HierarchyMap(nodes: Vec<HierarchyNode>) -> Element {
+    let mut path = use_signal(Vec::<usize>::new);
+
+    // A stale path (the hierarchy changed under it) falls back to the root without writing during render
+    let current = if node_at(&nodes, &path()).is_some() { path() } else { vec![] };
+    let scene = hierarchy_sunburst(&nodes, &current);
+    let svg = scene.to_svg();
+
+    rsx! {
+        div { class: "text-left",
+            div { class: "flex items-center justify-between mb-2",
+                span { class: "text-sm text-gray-400",
+                    if current.is_empty() { "Click a segment to drill down" } else { "Click the centre to go back up" }
+                }
+                ExportButtons { content: svg, filename: "hierarchy-map.svg", mime: "image/svg+xml" }
+            }
+            div { class: "max-w-lg mx-auto",
+                SceneView {
+                    scene,
+                    on_drill: move |d| if let Drill::Node(p) = d { path.set(p) }
+                }
+            }
+            Legend {}
+        }
+    }
+}
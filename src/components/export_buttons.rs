@@ -0,0 +1,22 @@
+use dioxus::prelude::*;
+use crate::util::{copy_to_clipboard, download_file};
+
+#[component]
+pub fn ExportButtons(content: String, filename: String, mime: &'static str) -> Element {
+    let copy_content = content.clone();
+
+    rsx! {
+        div { class: "flex space-x-2",
+            button {
+                class: "text-sm bg-blue-600 hover:bg-blue-700 px-3 py-1 rounded",
+                onclick: move |_| copy_to_clipboard(&copy_content),
+                "Copy"
+            }
+            button {
+                class: "text-sm bg-green-600 hover:bg-green-700 px-3 py-1 rounded",
+                onclick: move |_| download_file(&filename, mime, &content),
+                "Download"
+            }
+        }
+    }
+}
@@ -0,0 +1,86 @@
+// src/components/free_search.rs
+use dioxus::prelude::*;
+use crate::free_space::{FitStrategy, FreeSearch};
+
+/// Inputs shared by the IPv4 and IPv6 next-free modes
+#[component]
+pub fn FreeSearchFields(
+    used_input: Signal<String>,
+    size_input: Signal<String>,
+    count_input: Signal<String>,
+    strategy: Signal<FitStrategy>,
+    used_placeholder: &'static str,
+    size_placeholder: &'static str,
+) -> Element {
+    rsx! {
+        div { class: "mb-4",
+            label { class: "block text-left text-sm font-medium mb-2", "Used Prefixes (one per line)" }
+            textarea {
+                class: "w-full h-28 px-4 py-2 font-mono text-sm border border-gray-600 rounded-lg bg-gray-700",
+                placeholder: used_placeholder,
+                value: "{used_input}",
+                oninput: move |e| used_input.set(e.value())
+            }
+        }
+        div { class: "grid grid-cols-2 gap-2 mb-4",
+            div {
+                label { class: "block text-left text-sm font-medium mb-2", "Block Size" }
+                input {
+                    class: "w-full px-4 py-3 border border-gray-600 rounded-lg bg-gray-700",
+                    r#type: "text",
+                    placeholder: size_placeholder,
+                    value: "{size_input}",
+                    oninput: move |e| size_input.set(e.value())
+                }
+            }
+            div {
+                label { class: "block text-left text-sm font-medium mb-2", "Blocks Wanted" }
+                input {
+                    class: "w-full px-4 py-3 border border-gray-600 rounded-lg bg-gray-700 hide-number-spinner",
+                    r#type: "number",
+                    min: "1",
+                    placeholder: "1",
+                    value: "{count_input}",
+                    oninput: move |e| count_input.set(e.value())
+                }
+            }
+        }
+        div { class: "mb-4",
+            label { class: "block text-left text-sm font-medium mb-2", "Strategy" }
+            select {
+                class: "block w-60 mx-0 px-4 py-3 border border-gray-600 rounded-lg bg-gray-700",
+                onchange: move |e| strategy.set(FitStrategy::from_key(&e.value())),
+                for s in FitStrategy::ALL {
+                    option { value: s.key(), selected: strategy() == s, "{s.label()}" }
+                }
+            }
+        }
+    }
+}
+
+/// Search outcome shown above the found blocks
+#[component]
+pub fn FreeSearchSummary(search: FreeSearch, found: usize) -> Element {
+    let used = search.used.iter().map(|n| n.to_string()).collect::<Vec<_>>().join(", ");
+    let ignored = search.ignored.iter().map(|n| n.to_string()).collect::<Vec<_>>().join(", ");
+    rsx! {
+        div { class: "mb-4 p-4 bg-gray-700 rounded-lg text-sm text-left",
+            p { "Strategy: " strong { "{search.strategy.label()}" } }
+            p { "Block size: " strong { "/{search.prefix_len}" } }
+            p { "Found: " strong { "{found} of {search.requested}" } }
+            p { "Free addresses in parent: " strong { "{search.free_addresses}" } }
+            if !used.is_empty() {
+                p { class: "font-mono break-all", "Used: {used}" }
+            }
+            if !ignored.is_empty() {
+                p { class: "text-yellow-300 break-all", "Outside the parent (ignored): {ignored}" }
+            }
+            for found in search.normalized.iter() {
+                p { class: "text-yellow-300 break-all", "Normalized: {found}" }
+            }
+            if found < search.requested {
+                p { class: "text-red-300", "Not enough free space for {search.requested} blocks of /{search.prefix_len}" }
+            }
+        }
+    }
+}
@@ -0,0 +1,29 @@
+// src/components/host_bits.rs
+use dioxus::prelude::*;
+use crate::address_math::{HostBits, HostBitsPolicy};
+
+/// Strict-mode checkbox: reject prefixes typed with host bits set instead of normalizing them
+#[component]
+pub fn HostBitsToggle(policy: Signal<HostBitsPolicy>) -> Element {
+    rsx! {
+        label { class: "flex items-center mb-4 text-sm text-left",
+            input {
+                r#type: "checkbox",
+                class: "mr-2",
+                checked: policy() == HostBitsPolicy::Reject,
+                onchange: move |e| policy.set(if e.checked() { HostBitsPolicy::Reject } else { HostBitsPolicy::Normalize })
+            }
+            "Strict: reject host addresses used as networks"
+        }
+    }
+}
+
+/// Notice that an input prefix was normalized to its network
+#[component]
+pub fn HostBitsWarning(found: HostBits) -> Element {
+    rsx! {
+        div { class: "mb-4 p-3 bg-yellow-900/40 border border-yellow-600 rounded-lg text-sm text-yellow-200 text-left",
+            strong { "Normalized: " } "{found}"
+        }
+    }
+}
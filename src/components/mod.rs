@@ -1,7 +1,17 @@
 pub mod header;
 pub mod footer;
 pub mod tabs;
+pub mod export_buttons;
+pub mod address_map;
+pub mod free_search;
+pub mod address_arithmetic;
+pub mod host_bits;
 
 pub use header::Header;
 pub use footer::Footer;
-pub use tabs::{Tabs, ActiveTab};
\ No newline at end of file
+pub use tabs::{Tabs, ActiveTab};
+pub use export_buttons::ExportButtons;
+pub use address_map::{HierarchyMap, SplitMap};
+pub use free_search::{FreeSearchFields, FreeSearchSummary};
+pub use address_arithmetic::AddressArithmetic;
+pub use host_bits::{HostBitsToggle, HostBitsWarning};
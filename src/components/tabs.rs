@@ -4,6 +4,9 @@ use dioxus::prelude::*;
 pub enum ActiveTab {
     Ipv4,
     Ipv6,
+    DualStack,
+    Workspace,
+    Tools,
     Converter,
 }
 
@@ -22,6 +25,21 @@ pub fn Tabs(active_tab: ActiveTab, on_tab_change: EventHandler<ActiveTab>) -> El
                     active: active_tab == ActiveTab::Ipv6,
                     onclick: move |_| on_tab_change.call(ActiveTab::Ipv6)
                 }
+                TabButton {
+                    label: "Dual-Stack",
+                    active: active_tab == ActiveTab::DualStack,
+                    onclick: move |_| on_tab_change.call(ActiveTab::DualStack)
+                }
+                TabButton {
+                    label: "Workspace",
+                    active: active_tab == ActiveTab::Workspace,
+                    onclick: move |_| on_tab_change.call(ActiveTab::Workspace)
+                }
+                TabButton {
+                    label: "Tools",
+                    active: active_tab == ActiveTab::Tools,
+                    onclick: move |_| on_tab_change.call(ActiveTab::Tools)
+                }
                 TabButton {
                     label: "Converter",
                     active: active_tab == ActiveTab::Converter,
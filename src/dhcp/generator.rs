@@ -0,0 +1,340 @@
+// src/dhcp/generator.rs
+use ipnet::IpNet;
+use serde_json::{json, Value};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use crate::dhcp::types::{DhcpOptions, DhcpScope, DhcpServer, GatewayPosition};
+
+pub const MAX_DHCP_SCOPES: usize = 256;
+
+fn to_addr(net: &IpNet, value: u128) -> IpAddr {
+    match net {
+        IpNet::V4(_) => IpAddr::V4(Ipv4Addr::from(value as u32)),
+        IpNet::V6(_) => IpAddr::V6(Ipv6Addr::from(value)),
+    }
+}
+
+fn to_u128(addr: IpAddr) -> u128 {
+    match addr {
+        IpAddr::V4(a) => u32::from(a) as u128,
+        IpAddr::V6(a) => u128::from(a),
+    }
+}
+
+/// Lay out the gateway and pool for one subnet; None when nothing is left for the pool
+pub fn plan_scope(network: IpNet, options: &DhcpOptions) -> Option<DhcpScope> {
+    let start = to_u128(network.network());
+    let end = to_u128(network.broadcast());
+    let (first, last) = match network {
+        // Network and broadcast addresses are never leased; /31 and /32 have no pool
+        IpNet::V4(net) if net.prefix_len() >= 31 => return None,
+        IpNet::V4(_) => (start + 1, end - 1),
+        // Skip the Subnet-Router anycast address (the all-zeros host)
+        IpNet::V6(net) if net.prefix_len() == 128 => return None,
+        IpNet::V6(_) => (start + 1, end),
+    };
+
+    let (gateway, low, high) = match options.gateway {
+        GatewayPosition::First => (Some(first), first + 1, last),
+        GatewayPosition::Last => (Some(last), first, last.checked_sub(1)?),
+        GatewayPosition::None => (None, first, last),
+    };
+    let pool_start = low.checked_add(options.exclude_start as u128)?;
+    let pool_end = high.checked_sub(options.exclude_end as u128)?;
+    if pool_start > pool_end {
+        return None;
+    }
+
+    Some(DhcpScope {
+        network,
+        gateway: gateway.map(|g| to_addr(&network, g)),
+        pool_start: to_addr(&network, pool_start),
+        pool_end: to_addr(&network, pool_end),
+    })
+}
+
+/// Scopes for every subnet that still has room for a pool, and how many were skipped
+pub fn plan_scopes(subnets: &[IpNet], options: &DhcpOptions) -> (Vec<DhcpScope>, usize) {
+    let subnets = &subnets[..subnets.len().min(MAX_DHCP_SCOPES)];
+    let scopes: Vec<DhcpScope> = subnets.iter().filter_map(|&net| plan_scope(net, options)).collect();
+    let skipped = subnets.len() - scopes.len();
+    (scopes, skipped)
+}
+
+pub fn generate_dhcp(subnets: &[IpNet], options: &DhcpOptions) -> String {
+    let (scopes, _) = plan_scopes(subnets, options);
+    match options.server {
+        DhcpServer::IscDhcpd => isc_dhcpd(&scopes, options),
+        DhcpServer::Kea4 => kea4(&scopes, options),
+        DhcpServer::Dnsmasq => dnsmasq(&scopes, options),
+        DhcpServer::WindowsNetsh => netsh(&scopes, options),
+        DhcpServer::WindowsPowerShell => powershell(&scopes, options),
+        DhcpServer::Kea6 => kea6(&scopes, options),
+        DhcpServer::Radvd => radvd(&scopes, options),
+    }
+}
+
+fn dns_list(options: &DhcpOptions) -> Vec<String> {
+    options
+        .dns_servers
+        .iter()
+        .flat_map(|s| s.split(','))
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+fn netmask(scope: &DhcpScope) -> String {
+    scope.network.netmask().to_string()
+}
+
+fn isc_dhcpd(scopes: &[DhcpScope], o: &DhcpOptions) -> String {
+    let dns = dns_list(o);
+    let mut out = format!("default-lease-time {};\nmax-lease-time {};\n\n", o.lease_time, o.lease_time.saturating_mul(2));
+
+    for (i, scope) in scopes.iter().enumerate() {
+        out.push_str(&format!("# Subnet {} - {}\n", i + 1, scope.network));
+        out.push_str(&format!("subnet {} netmask {} {{\n", scope.network.network(), netmask(scope)));
+        out.push_str(&format!("  range {} {};\n", scope.pool_start, scope.pool_end));
+        if let Some(gw) = scope.gateway {
+            out.push_str(&format!("  option routers {};\n", gw));
+        }
+        out.push_str(&format!("  option broadcast-address {};\n", scope.network.broadcast()));
+        if !dns.is_empty() {
+            out.push_str(&format!("  option domain-name-servers {};\n", dns.join(", ")));
+        }
+        if let Some(domain) = &o.domain {
+            out.push_str(&format!("  option domain-name \"{}\";\n", domain));
+        }
+        out.push_str("}\n\n");
+    }
+    out
+}
+
+fn kea_options(scope: &DhcpScope, o: &DhcpOptions, dns_option: &str) -> Vec<Value> {
+    let mut data = vec![];
+    if let (Some(gw), IpNet::V4(_)) = (scope.gateway, scope.network) {
+        data.push(json!({ "name": "routers", "data": gw.to_string() }));
+    }
+    let dns = dns_list(o);
+    if !dns.is_empty() {
+        data.push(json!({ "name": dns_option, "data": dns.join(", ") }));
+    }
+    if let Some(domain) = &o.domain {
+        let name = if dns_option == "dns-servers" { "domain-search" } else { "domain-name" };
+        data.push(json!({ "name": name, "data": domain }));
+    }
+    data
+}
+
+fn kea4(scopes: &[DhcpScope], o: &DhcpOptions) -> String {
+    let subnets: Vec<Value> = scopes
+        .iter()
+        .enumerate()
+        .map(|(i, scope)| {
+            json!({
+                "id": i + 1,
+                "subnet": scope.network.to_string(),
+                "pools": [{ "pool": format!("{} - {}", scope.pool_start, scope.pool_end) }],
+                "option-data": kea_options(scope, o, "domain-name-servers"),
+            })
+        })
+        .collect();
+    let config = json!({
+        "Dhcp4": {
+            "interfaces-config": { "interfaces": [o.interface] },
+            "valid-lifetime": o.lease_time,
+            "renew-timer": o.lease_time / 2,
+            "rebind-timer": o.lease_time / 8 * 7,
+            "subnet4": subnets,
+        }
+    });
+    serde_json::to_string_pretty(&config).unwrap_or_default()
+}
+
+fn kea6(scopes: &[DhcpScope], o: &DhcpOptions) -> String {
+    let subnets: Vec<Value> = scopes
+        .iter()
+        .enumerate()
+        .map(|(i, scope)| {
+            json!({
+                "id": i + 1,
+                "subnet": scope.network.to_string(),
+                "interface": o.interface,
+                "pools": [{ "pool": format!("{} - {}", scope.pool_start, scope.pool_end) }],
+                "option-data": kea_options(scope, o, "dns-servers"),
+            })
+        })
+        .collect();
+    let config = json!({
+        "Dhcp6": {
+            "interfaces-config": { "interfaces": [o.interface] },
+            "valid-lifetime": o.lease_time,
+            "preferred-lifetime": o.lease_time / 2,
+            "subnet6": subnets,
+        }
+    });
+    serde_json::to_string_pretty(&config).unwrap_or_default()
+}
+
+fn dnsmasq(scopes: &[DhcpScope], o: &DhcpOptions) -> String {
+    let dns = dns_list(o);
+    let mut out = format!("interface={}\n", o.interface);
+    if let Some(domain) = &o.domain {
+        out.push_str(&format!("domain={}\n", domain));
+    }
+    out.push('\n');
+
+    for (i, scope) in scopes.iter().enumerate() {
+        let tag = format!("net{}", i + 1);
+        out.push_str(&format!("# Subnet {} - {}\n", i + 1, scope.network));
+        out.push_str(&format!(
+            "dhcp-range=set:{},{},{},{},{}s\n",
+            tag, scope.pool_start, scope.pool_end, netmask(scope), o.lease_time
+        ));
+        if let Some(gw) = scope.gateway {
+            out.push_str(&format!("dhcp-option=tag:{},option:router,{}\n", tag, gw));
+        }
+        if !dns.is_empty() {
+            out.push_str(&format!("dhcp-option=tag:{},option:dns-server,{}\n", tag, dns.join(",")));
+        }
+    }
+    out
+}
+
+fn netsh(scopes: &[DhcpScope], o: &DhcpOptions) -> String {
+    let dns = dns_list(o);
+    let mut out = String::new();
+
+    for (i, scope) in scopes.iter().enumerate() {
+        let id = scope.network.network();
+        out.push_str(&format!("REM Subnet {} - {}\n", i + 1, scope.network));
+        out.push_str(&format!("netsh dhcp server add scope {} {} \"Subnet {}\"\n", id, netmask(scope), i + 1));
+        out.push_str(&format!("netsh dhcp server scope {} add iprange {} {}\n", id, scope.pool_start, scope.pool_end));
+        if let Some(gw) = scope.gateway {
+            out.push_str(&format!("netsh dhcp server scope {} set optionvalue 003 IPADDRESS {}\n", id, gw));
+        }
+        if !dns.is_empty() {
+            out.push_str(&format!("netsh dhcp server scope {} set optionvalue 006 IPADDRESS {}\n", id, dns.join(" ")));
+        }
+        if let Some(domain) = &o.domain {
+            out.push_str(&format!("netsh dhcp server scope {} set optionvalue 015 STRING \"{}\"\n", id, domain));
+        }
+        out.push_str(&format!("netsh dhcp server scope {} set optionvalue 051 DWORD {}\n\n", id, o.lease_time));
+    }
+    out
+}
+
+fn powershell(scopes: &[DhcpScope], o: &DhcpOptions) -> String {
+    let dns = dns_list(o);
+    let mut out = String::new();
+
+    for (i, scope) in scopes.iter().enumerate() {
+        let id = scope.network.network();
+        out.push_str(&format!("# Subnet {} - {}\n", i + 1, scope.network));
+        out.push_str(&format!(
+            "Add-DhcpServerv4Scope -Name \"Subnet {}\" -StartRange {} -EndRange {} -SubnetMask {} -LeaseDuration (New-TimeSpan -Seconds {}) -State Active\n",
+            i + 1, scope.pool_start, scope.pool_end, netmask(scope), o.lease_time
+        ));
+        let mut option_args = String::new();
+        if let Some(gw) = scope.gateway {
+            option_args.push_str(&format!(" -Router {}", gw));
+        }
+        if !dns.is_empty() {
+            option_args.push_str(&format!(" -DnsServer {}", dns.join(",")));
+        }
+        if let Some(domain) = &o.domain {
+            option_args.push_str(&format!(" -DnsDomain \"{}\"", domain));
+        }
+        if !option_args.is_empty() {
+            out.push_str(&format!("Set-DhcpServerv4OptionValue -ScopeId {}{}\n", id, option_args));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn radvd(scopes: &[DhcpScope], o: &DhcpOptions) -> String {
+    let dns = dns_list(o);
+    let mut out = format!("interface {}\n{{\n    AdvSendAdvert on;\n", o.interface);
+
+    for scope in scopes {
+        // SLAAC needs a /64; other lengths are advertised on-link only
+        let autonomous = if scope.network.prefix_len() == 64 { "on" } else { "off" };
+        out.push_str(&format!("\n    prefix {}\n    {{\n", scope.network));
+        out.push_str("        AdvOnLink on;\n");
+        out.push_str(&format!("        AdvAutonomous {};\n", autonomous));
+        out.push_str(&format!("        AdvValidLifetime {};\n", o.lease_time));
+        out.push_str(&format!("        AdvPreferredLifetime {};\n", o.lease_time / 2));
+        out.push_str("    };\n");
+    }
+    if !dns.is_empty() {
+        out.push_str(&format!("\n    RDNSS {}\n    {{\n    }};\n", dns.join(" ")));
+    }
+    if let Some(domain) = &o.domain {
+        out.push_str(&format!("\n    DNSSL {}\n    {{\n    }};\n", domain));
+    }
+    out.push_str("};\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::assert_golden;
+
+    fn net(s: &str) -> IpNet {
+        s.parse().unwrap()
+    }
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn lays_out_gateway_and_pool() {
+        let mut options = DhcpOptions::new(DhcpServer::IscDhcpd);
+        let scope = plan_scope(net("10.0.0.0/24"), &options).unwrap();
+        assert_eq!((scope.gateway, scope.pool_start, scope.pool_end), (Some(ip("10.0.0.1")), ip("10.0.0.2"), ip("10.0.0.254")));
+
+        options.gateway = GatewayPosition::Last;
+        options.exclude_start = 10;
+        options.exclude_end = 5;
+        let scope = plan_scope(net("10.0.0.0/24"), &options).unwrap();
+        assert_eq!((scope.gateway, scope.pool_start, scope.pool_end), (Some(ip("10.0.0.254")), ip("10.0.0.11"), ip("10.0.0.248")));
+
+        let v6 = plan_scope(net("2001:db8::/64"), &DhcpOptions::new(DhcpServer::Kea6)).unwrap();
+        assert_eq!(v6.gateway, Some(ip("2001:db8::1")));
+        assert_eq!(v6.pool_end, ip("2001:db8::ffff:ffff:ffff:ffff"));
+    }
+
+    #[test]
+    fn skips_subnets_without_room() {
+        let mut options = DhcpOptions::new(DhcpServer::Dnsmasq);
+        options.exclude_start = 2;
+        let (scopes, skipped) = plan_scopes(&[net("10.0.0.0/30"), net("10.0.0.4/31"), net("10.0.1.0/24")], &options);
+        assert_eq!(scopes.len(), 1);
+        assert_eq!(skipped, 2);
+    }
+
+    #[test]
+    fn huge_lease_times_do_not_overflow() {
+        let options = DhcpOptions { lease_time: u32::MAX, ..DhcpOptions::new(DhcpServer::IscDhcpd) };
+        assert!(generate_dhcp(&[net("10.0.0.0/24")], &options).contains(&u32::MAX.to_string()));
+    }
+
+    #[test]
+    fn golden_configs() {
+        let v4 = [net("10.0.0.0/24"), net("10.0.1.0/26")];
+        let v6 = [net("2001:db8:0:1::/64")];
+        for server in DhcpServer::V4.into_iter().chain(DhcpServer::V6) {
+            let options = DhcpOptions {
+                dns_servers: Some("10.0.0.53, 10.0.1.53".to_string()),
+                domain: Some("example.net".to_string()),
+                exclude_start: 5,
+                ..DhcpOptions::new(server)
+            };
+            let subnets: &[IpNet] = if DhcpServer::V6.contains(&server) { &v6 } else { &v4 };
+            assert_golden(&format!("dhcp_{}.txt", server.key()), &generate_dhcp(subnets, &options));
+        }
+    }
+}
@@ -0,0 +1,6 @@
+// src/dhcp/mod.rs
+pub mod generator;
+pub mod panel;
+pub mod types;
+
+pub use panel::DhcpPanel;
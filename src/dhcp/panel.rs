@@ -0,0 +1,108 @@
+// src/dhcp/panel.rs
+use dioxus::prelude::*;
+use ipnet::IpNet;
+use crate::components::ExportButtons;
+use crate::dhcp::generator::{generate_dhcp, plan_scopes, MAX_DHCP_SCOPES};
+use crate::dhcp::types::{DhcpOptions, DhcpServer, GatewayPosition};
+
+fn non_empty(value: &str) -> Option<String> {
+    let trimmed = value.trim();
+    (!trimmed.is_empty()).then(|| trimmed.to_string())
+}
+
+#[component]
+pub fn DhcpPanel(subnets: Vec<IpNet>) -> Element {
+    let is_v6 = matches!(subnets.first(), Some(IpNet::V6(_)));
+    let servers: &[DhcpServer] = if is_v6 { &DhcpServer::V6 } else { &DhcpServer::V4 };
+
+    let mut server = use_signal(|| servers[0]);
+    let mut gateway = use_signal(|| GatewayPosition::First);
+    let mut exclude_start = use_signal(|| "0".to_string());
+    let mut exclude_end = use_signal(|| "0".to_string());
+    let mut lease_time = use_signal(|| "86400".to_string());
+    let mut dns_servers = use_signal(String::new);
+    let mut domain = use_signal(String::new);
+    let mut interface = use_signal(|| "eth0".to_string());
+
+    let defaults = DhcpOptions::new(server());
+    let options = DhcpOptions {
+        gateway: gateway(),
+        exclude_start: exclude_start().trim().parse().unwrap_or(0),
+        exclude_end: exclude_end().trim().parse().unwrap_or(0),
+        lease_time: lease_time().trim().parse().unwrap_or(defaults.lease_time),
+        dns_servers: non_empty(&dns_servers()),
+        domain: non_empty(&domain()),
+        interface: non_empty(&interface()).unwrap_or(defaults.interface.clone()),
+        ..defaults
+    };
+    let (_, skipped) = plan_scopes(&subnets, &options);
+    let config = generate_dhcp(&subnets, &options);
+    let field = "w-full px-3 py-2 text-sm border border-gray-600 rounded-lg bg-gray-700";
+
+    rsx! {
+        div { class: "text-left",
+            div { class: "grid grid-cols-2 gap-3 mb-4 text-sm",
+                div {
+                    label { class: "block mb-1 font-medium", "DHCP Server" }
+                    select {
+                        class: "{field}",
+                        onchange: move |e| server.set(DhcpServer::from_key(&e.value())),
+                        for s in servers.iter().copied() {
+                            option { value: s.key(), selected: server() == s, "{s.label()}" }
+                        }
+                    }
+                }
+                div {
+                    label { class: "block mb-1 font-medium", "Gateway Position" }
+                    select {
+                        class: "{field}",
+                        onchange: move |e| gateway.set(match e.value().as_str() {
+                            "last" => GatewayPosition::Last,
+                            "none" => GatewayPosition::None,
+                            _ => GatewayPosition::First,
+                        }),
+                        option { value: "first", selected: gateway() == GatewayPosition::First, "First usable host" }
+                        option { value: "last", selected: gateway() == GatewayPosition::Last, "Last usable host" }
+                        option { value: "none", selected: gateway() == GatewayPosition::None, "No gateway" }
+                    }
+                }
+                div {
+                    label { class: "block mb-1 font-medium", "Reserved Hosts at Start" }
+                    input { class: "{field}", r#type: "number", min: "0", value: "{exclude_start}", oninput: move |e| exclude_start.set(e.value()) }
+                }
+                div {
+                    label { class: "block mb-1 font-medium", "Reserved Hosts at End" }
+                    input { class: "{field}", r#type: "number", min: "0", value: "{exclude_end}", oninput: move |e| exclude_end.set(e.value()) }
+                }
+                div {
+                    label { class: "block mb-1 font-medium", "Lease Time (seconds)" }
+                    input { class: "{field}", r#type: "number", min: "60", value: "{lease_time}", oninput: move |e| lease_time.set(e.value()) }
+                }
+                div {
+                    label { class: "block mb-1 font-medium", "Interface" }
+                    input { class: "{field}", value: "{interface}", oninput: move |e| interface.set(e.value()) }
+                }
+                div {
+                    label { class: "block mb-1 font-medium", "DNS Servers" }
+                    input { class: "{field}", placeholder: "none, comma-separated", value: "{dns_servers}", oninput: move |e| dns_servers.set(e.value()) }
+                }
+                div {
+                    label { class: "block mb-1 font-medium", "Domain" }
+                    input { class: "{field}", placeholder: "none, e.g. example.lan", value: "{domain}", oninput: move |e| domain.set(e.value()) }
+                }
+            }
+            div { class: "flex items-center justify-between mb-2",
+                div { class: "text-xs text-gray-500",
+                    if subnets.len() > MAX_DHCP_SCOPES {
+                        p { "Scopes generated for the first {MAX_DHCP_SCOPES} of {subnets.len()} subnets." }
+                    }
+                    if skipped > 0 {
+                        p { "{skipped} subnet(s) skipped: no addresses left for a pool." }
+                    }
+                }
+                ExportButtons { content: config.clone(), filename: server().file_name().to_string(), mime: server().mime() }
+            }
+            pre { class: "max-h-96 overflow-auto bg-gray-900 p-3 rounded text-xs font-roboto", "{config}" }
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use ipnet::IpNet;
+use std::net::IpAddr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DhcpServer {
+    IscDhcpd,
+    Kea4,
+    Dnsmasq,
+    WindowsNetsh,
+    WindowsPowerShell,
+    Kea6,
+    Radvd,
+}
+
+impl DhcpServer {
+    pub const V4: [DhcpServer; 5] = [
+        DhcpServer::IscDhcpd,
+        DhcpServer::Kea4,
+        DhcpServer::Dnsmasq,
+        DhcpServer::WindowsNetsh,
+        DhcpServer::WindowsPowerShell,
+    ];
+    pub const V6: [DhcpServer; 2] = [DhcpServer::Kea6, DhcpServer::Radvd];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DhcpServer::IscDhcpd => "ISC dhcpd",
+            DhcpServer::Kea4 => "Kea DHCPv4 (JSON)",
+            DhcpServer::Dnsmasq => "dnsmasq",
+            DhcpServer::WindowsNetsh => "Windows netsh",
+            DhcpServer::WindowsPowerShell => "Windows PowerShell",
+            DhcpServer::Kea6 => "Kea DHCPv6 (JSON)",
+            DhcpServer::Radvd => "radvd",
+        }
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            DhcpServer::IscDhcpd => "isc",
+            DhcpServer::Kea4 => "kea4",
+            DhcpServer::Dnsmasq => "dnsmasq",
+            DhcpServer::WindowsNetsh => "netsh",
+            DhcpServer::WindowsPowerShell => "powershell",
+            DhcpServer::Kea6 => "kea6",
+            DhcpServer::Radvd => "radvd",
+        }
+    }
+
+    pub fn from_key(key: &str) -> DhcpServer {
+        DhcpServer::V4
+            .into_iter()
+            .chain(DhcpServer::V6)
+            .find(|s| s.key() == key)
+            .unwrap_or(DhcpServer::IscDhcpd)
+    }
+
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            DhcpServer::IscDhcpd => "dhcpd.conf",
+            DhcpServer::Kea4 => "kea-dhcp4.json",
+            DhcpServer::Dnsmasq => "dnsmasq-dhcp.conf",
+            DhcpServer::WindowsNetsh => "dhcp-scopes.cmd",
+            DhcpServer::WindowsPowerShell => "dhcp-scopes.ps1",
+            DhcpServer::Kea6 => "kea-dhcp6.json",
+            DhcpServer::Radvd => "radvd.conf",
+        }
+    }
+
+    pub fn mime(&self) -> &'static str {
+        match self {
+            DhcpServer::Kea4 | DhcpServer::Kea6 => "application/json",
+            _ => "text/plain",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GatewayPosition {
+    First,
+    Last,
+    None,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DhcpOptions {
+    pub server: DhcpServer,
+    pub gateway: GatewayPosition,
+    pub exclude_start: u32, // Hosts kept out of the pool at the start of the range
+    pub exclude_end: u32,   // ... and at the end
+    pub lease_time: u32,    // Seconds
+    pub dns_servers: Option<String>, // Comma-separated
+    pub domain: Option<String>,
+    pub interface: String,  // dnsmasq, Kea and radvd bind scopes to an interface
+}
+
+impl DhcpOptions {
+    pub fn new(server: DhcpServer) -> Self {
+        DhcpOptions {
+            server,
+            gateway: GatewayPosition::First,
+            exclude_start: 0,
+            exclude_end: 0,
+            lease_time: 86400,
+            dns_servers: None,
+            domain: None,
+            interface: "eth0".to_string(),
+        }
+    }
+}
+
+/// Pool layout for one subnet after applying the gateway and exclusions
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DhcpScope {
+    pub network: IpNet,
+    pub gateway: Option<IpAddr>,
+    pub pool_start: IpAddr,
+    pub pool_end: IpAddr,
+}
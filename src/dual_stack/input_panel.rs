@@ -0,0 +1,66 @@
+// src/dual_stack/input_panel.rs
+use dioxus::prelude::*;
+use crate::dual_stack::planner::{parse_ipv6_size, parse_segments, plan_dual_stack};
+use crate::dual_stack::types::{DualStackError, DualStackPlan};
+
+#[component]
+pub fn InputPanel(
+    ipv4_input: Signal<String>,
+    ipv6_input: Signal<String>,
+    ipv6_prefix_input: Signal<String>,
+    segments_input: Signal<String>,
+    result: Signal<Option<Result<DualStackPlan, DualStackError>>>,
+) -> Element {
+    let field = "w-full px-4 py-3 border border-gray-600 rounded-lg focus:ring-2 focus:ring-blue-500 focus:border-transparent";
+
+    rsx! {
+        div { class: "w-full h-150 bg-gray-800 rounded-lg shadow-lg p-6 flex flex-col text-left",
+            h2 { class: "text-2xl font-bold mb-6 text-center", "Dual-Stack Segments" }
+
+            div { class: "mb-4",
+                label { class: "block text-sm font-medium mb-2", "IPv4 Parent" }
+                input { class: "{field}", placeholder: "e.g. 10.20.0.0/16 or 10.20.0.0 255.255.0.0", value: "{ipv4_input}", oninput: move |e| ipv4_input.set(e.value()) }
+            }
+            div { class: "grid grid-cols-3 gap-3 mb-4",
+                div { class: "col-span-2",
+                    label { class: "block text-sm font-medium mb-2", "IPv6 Parent" }
+                    input { class: "{field}", placeholder: "e.g. 2001:db8:20::/48", value: "{ipv6_input}", oninput: move |e| ipv6_input.set(e.value()) }
+                }
+                div {
+                    label { class: "block text-sm font-medium mb-2", "IPv6 Size" }
+                    input { class: "{field}", placeholder: "64", value: "{ipv6_prefix_input}", oninput: move |e| ipv6_prefix_input.set(e.value()) }
+                }
+            }
+            div { class: "mb-4 flex-1 flex flex-col",
+                label { class: "block text-sm font-medium mb-2", "Segments (name, hosts – one per line)" }
+                textarea {
+                    class: "{field} flex-1 font-roboto text-sm",
+                    value: "{segments_input}",
+                    oninput: move |e| segments_input.set(e.value())
+                }
+            }
+
+            div { class: "grid grid-cols-3",
+                button {
+                    class: "w-full bg-blue-600 hover:bg-blue-700 text-white font-bold py-4 rounded-lg text-lg transition col-span-2",
+                    onclick: move |_| {
+                        let plan = parse_ipv6_size(&ipv6_prefix_input()).and_then(|ipv6_prefix| {
+                            parse_segments(&segments_input())
+                                .and_then(|segments| plan_dual_stack(&ipv4_input(), &ipv6_input(), ipv6_prefix, &segments))
+                        });
+                        result.set(Some(plan));
+                    },
+                    "Plan"
+                }
+                button {
+                    class: "w-full ml-2 bg-red-500 hover:bg-red-500 text-white font-bold py-4 rounded-lg text-lg transition",
+                    onclick: move |_| {
+                        segments_input.set(String::new());
+                        result.set(None);
+                    },
+                    "Clear"
+                }
+            }
+        }
+    }
+}
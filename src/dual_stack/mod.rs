@@ -0,0 +1,34 @@
+// src/dual_stack/mod.rs
+pub mod planner;
+pub mod types;
+pub mod input_panel;
+pub mod results_panel;
+
+use dioxus::prelude::*;
+use crate::dual_stack::input_panel::InputPanel;
+use crate::dual_stack::results_panel::ResultsPanel;
+use crate::dual_stack::types::{DualStackError, DualStackPlan};
+
+#[component]
+pub fn DualStackTab() -> Element {
+    let ipv4_input = use_signal(|| "10.20.0.0/16".to_string());
+    let ipv6_input = use_signal(|| "2001:db8:20::/48".to_string());
+    let ipv6_prefix_input = use_signal(|| "64".to_string());
+    let segments_input = use_signal(|| "users, 500\nservers, 120\nprinters, 30\nmanagement, 10".to_string());
+    let result = use_signal(|| None::<Result<DualStackPlan, DualStackError>>);
+
+    rsx! {
+        div { class: "grid grid-cols-3 gap-4",
+            InputPanel {
+                ipv4_input,
+                ipv6_input,
+                ipv6_prefix_input,
+                segments_input,
+                result
+            }
+            div { class: "col-span-2",
+                ResultsPanel { result: result.read().clone() }
+            }
+        }
+    }
+}
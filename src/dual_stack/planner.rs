@@ -0,0 +1,208 @@
+// src/dual_stack/planner.rs
+use ipnet::IpNet;
+use crate::address_math::HostBitsPolicy;
+use crate::dual_stack::types::{DualStackAllocation, DualStackError, DualStackPlan, Segment};
+use crate::free_space::{find_free, FitStrategy};
+use crate::ipv4::{calculator as ipv4_calc, types::PlatformProfile};
+use crate::ipv6::{calculator as ipv6_calc, types::{HierarchyPlacement, SubnetMode}};
+
+pub const MAX_SEGMENTS: usize = 256;
+
+/// One segment per line: "name, hosts" (or "name hosts"); blank lines and # comments are skipped
+pub fn parse_segments(text: &str) -> Result<Vec<Segment>, DualStackError> {
+    let mut segments = vec![];
+
+    for (i, raw) in text.lines().enumerate() {
+        let line = raw.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        let parsed = line
+            .rsplit_once([',', ' ', '\t'])
+            .and_then(|(name, hosts)| {
+                let name = name.trim().trim_end_matches(',').trim();
+                let hosts = hosts.trim().parse::<u32>().ok().filter(|&h| h >= 1)?;
+                (!name.is_empty()).then(|| Segment { name: name.to_string(), hosts })
+            });
+        match parsed {
+            Some(segment) => segments.push(segment),
+            None => return Err(DualStackError::InvalidSegment { line: i + 1, text: line.to_string() }),
+        }
+    }
+    if segments.is_empty() {
+        return Err(DualStackError::NoSegments);
+    }
+    if segments.len() > MAX_SEGMENTS {
+        return Err(DualStackError::TooManySegments);
+    }
+    Ok(segments)
+}
+
+/// IPv6 subnet size as "64" or "/64"; empty means /64
+pub fn parse_ipv6_size(text: &str) -> Result<u8, DualStackError> {
+    let trimmed = text.trim().trim_start_matches('/');
+    if trimmed.is_empty() {
+        return Ok(64);
+    }
+    trimmed
+        .parse::<u8>()
+        .ok()
+        .filter(|&p| p <= 128)
+        .ok_or_else(|| DualStackError::InvalidIpv6Size(text.trim().to_string()))
+}
+
+/// "address/prefix" or "address mask"; the prefix part is handed to the calculators as-is
+fn split_cidr(cidr: &str) -> (&str, &str) {
+    let cidr = cidr.trim();
+    cidr.split_once('/')
+        .or_else(|| cidr.split_once(char::is_whitespace))
+        .map(|(addr, prefix)| (addr.trim(), prefix.trim()))
+        .unwrap_or((cidr, ""))
+}
+
+/// Right-size an IPv4 subnet for every segment (largest first, VLSM-style) and pair it with
+/// the next IPv6 subnet of `ipv6_prefix`. Sizing goes through both calculators; IPv4 blocks are
+/// then taken first-fit from the whole parent, since the calculator's subnet list is truncated.
+pub fn plan_dual_stack(
+    ipv4_parent: &str,
+    ipv6_parent: &str,
+    ipv6_prefix: u8,
+    segments: &[Segment],
+) -> Result<DualStackPlan, DualStackError> {
+    let (v4_addr, v4_prefix) = split_cidr(ipv4_parent);
+    let (v6_addr, v6_prefix) = split_cidr(ipv6_parent);
+
+    let parent = ipv4_calc::calculate(v4_addr, v4_prefix, None, None, PlatformProfile::Standard, HostBitsPolicy::Normalize)
+        .map_err(|error| DualStackError::Ipv4 { segment: None, error })?;
+    let v6 = ipv6_calc::calculate(
+        v6_addr,
+        v6_prefix,
+        SubnetMode::ByPrefix,
+        None,
+        Some(ipv6_prefix),
+        vec![],
+        HierarchyPlacement::Contiguous,
+        None,
+        HostBitsPolicy::Normalize,
+    )
+    .map_err(DualStackError::Ipv6)?;
+    if (segments.len() as u128) > v6.total_subnets || segments.len() > v6.subnets.len() {
+        return Err(DualStackError::Ipv6Exhausted);
+    }
+
+    // Place the largest segments first so smaller ones fill the gaps between them
+    let mut order: Vec<usize> = (0..segments.len()).collect();
+    order.sort_by_key(|&i| std::cmp::Reverse(segments[i].hosts));
+
+    let v4_parent = IpNet::V4(parent.base_network.trunc());
+    let mut taken: Vec<IpNet> = vec![];
+    let mut v4_results = vec![None; segments.len()];
+    for i in order {
+        let segment = &segments[i];
+        let split = ipv4_calc::calculate(v4_addr, v4_prefix, Some(segment.hosts), None, PlatformProfile::Standard, HostBitsPolicy::Normalize)
+            .map_err(|error| DualStackError::Ipv4 { segment: Some(segment.name.clone()), error })?;
+        let exhausted = || DualStackError::Ipv4Exhausted(segment.name.clone());
+        let len = split.new_prefix.ok_or_else(exhausted)?;
+        let free = match find_free(v4_parent, &taken, len, 1, FitStrategy::First).first() {
+            Some(&IpNet::V4(free)) => free,
+            _ => return Err(exhausted()),
+        };
+        taken.push(IpNet::V4(free));
+        v4_results[i] = Some(ipv4_calc::build_subnet_result(free, PlatformProfile::Standard));
+    }
+
+    let allocations: Vec<DualStackAllocation> = segments
+        .iter()
+        .zip(v4_results)
+        .zip(&v6.subnets)
+        .filter_map(|((segment, v4), v6_sub)| {
+            let v4 = v4?;
+            Some(DualStackAllocation {
+                name: segment.name.clone(),
+                hosts: segment.hosts,
+                ipv4: v4.network,
+                usable_hosts: v4.usable_hosts,
+                gateway: v4.first_host,
+                ipv6: v6_sub.network,
+            })
+        })
+        .collect();
+
+    Ok(DualStackPlan {
+        ipv4_parent: parent.base_network.trunc(),
+        ipv6_parent: v6.base_network.trunc(),
+        ipv6_prefix,
+        ipv4_used: taken.iter().map(|n| 1u64 << (32 - n.prefix_len())).sum(),
+        allocations,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ipnet::Ipv4Net;
+
+    fn segment(name: &str, hosts: u32) -> Segment {
+        Segment { name: name.to_string(), hosts }
+    }
+
+    #[test]
+    fn parses_segment_lines() {
+        let segments = parse_segments("users, 200\n# servers next\nservers 50\n\ndmz,\t10 # public").unwrap();
+        assert_eq!(segments, [segment("users", 200), segment("servers", 50), segment("dmz", 10)]);
+        assert_eq!(parse_segments("ok 1\nbroken"), Err(DualStackError::InvalidSegment { line: 2, text: "broken".into() }));
+        assert_eq!(parse_segments("zero, 0").unwrap_err(), DualStackError::InvalidSegment { line: 1, text: "zero, 0".into() });
+        assert_eq!(parse_segments("# nothing"), Err(DualStackError::NoSegments));
+        let many: String = (0..=MAX_SEGMENTS).map(|i| format!("s{} 1\n", i)).collect();
+        assert_eq!(parse_segments(&many), Err(DualStackError::TooManySegments));
+    }
+
+    #[test]
+    fn parses_ipv6_sizes() {
+        assert_eq!(parse_ipv6_size(""), Ok(64));
+        assert_eq!(parse_ipv6_size("/56"), Ok(56));
+        assert_eq!(parse_ipv6_size("129"), Err(DualStackError::InvalidIpv6Size("129".into())));
+        assert!(parse_ipv6_size("big").is_err());
+    }
+
+    #[test]
+    fn places_largest_segments_first() {
+        let segments = [segment("small", 10), segment("large", 200), segment("medium", 50)];
+        let plan = plan_dual_stack("10.0.0.0/23", "2001:db8:1::/48", 64, &segments).unwrap();
+        let v4: Vec<String> = plan.allocations.iter().map(|a| a.ipv4.to_string()).collect();
+        assert_eq!(v4, ["10.0.1.64/28", "10.0.0.0/24", "10.0.1.0/26"]);
+        let v6: Vec<String> = plan.allocations.iter().map(|a| a.ipv6.to_string()).collect();
+        assert_eq!(v6, ["2001:db8:1::/64", "2001:db8:1:1::/64", "2001:db8:1:2::/64"]);
+        assert_eq!(plan.allocations[1].gateway.as_deref(), Some("10.0.0.1"));
+        assert_eq!(plan.ipv4_used, 256 + 64 + 16);
+    }
+
+    #[test]
+    fn accepts_mask_notation_for_the_parent() {
+        let plan = plan_dual_stack("10.0.0.0 255.255.255.0", "2001:db8::/48", 64, &[segment("only", 10)]);
+        assert_eq!(plan.unwrap().ipv4_parent, "10.0.0.0/24".parse::<Ipv4Net>().unwrap());
+    }
+
+    #[test]
+    fn finds_room_past_the_listed_subnets_of_a_large_parent() {
+        // The /8's /30 listing stops after 4086 blocks, all inside the /16, and then jumps to the last 10
+        let mut segments = vec![segment("big", 60000)];
+        segments.extend((0..12).map(|i| segment(&format!("s{}", i), 2)));
+        let plan = plan_dual_stack("10.0.0.0/8", "2001:db8::/48", 64, &segments).unwrap();
+        assert_eq!(plan.allocations[0].ipv4.to_string(), "10.0.0.0/16");
+        assert_eq!(plan.allocations[1].ipv4.to_string(), "10.1.0.0/30");
+        assert_eq!(plan.allocations[12].ipv4.to_string(), "10.1.0.44/30");
+        assert_eq!(plan.ipv4_used, 65536 + 12 * 4);
+    }
+
+    #[test]
+    fn reports_exhaustion() {
+        let segments = [segment("a", 100), segment("b", 100), segment("c", 100)];
+        assert_eq!(plan_dual_stack("10.0.0.0/24", "2001:db8::/48", 64, &segments), Err(DualStackError::Ipv4Exhausted("c".into())));
+        assert_eq!(plan_dual_stack("10.0.0.0/16", "2001:db8::/63", 64, &segments), Err(DualStackError::Ipv6Exhausted));
+        assert!(matches!(
+            plan_dual_stack("10.0.0.0/24", "2001:db8::/48", 64, &[segment("huge", 1000)]),
+            Err(DualStackError::Ipv4 { segment: Some(_), .. })
+        ));
+    }
+}
@@ -0,0 +1,84 @@
+// src/dual_stack/results_panel.rs
+use dioxus::prelude::*;
+use crate::components::ExportButtons;
+use crate::dual_stack::types::{DualStackError, DualStackPlan};
+use crate::iac::{types::IacSubnet, IacPanel};
+use crate::util::csv_field;
+
+fn plan_csv(plan: &DualStackPlan) -> String {
+    let mut out = String::from("name,hosts_needed,ipv4_subnet,usable_hosts,gateway,ipv6_subnet\n");
+    for a in &plan.allocations {
+        out.push_str(&format!(
+            "{},{},{},{},{},{}\n",
+            csv_field(&a.name),
+            a.hosts,
+            a.ipv4,
+            a.usable_hosts,
+            a.gateway.as_deref().unwrap_or(""),
+            a.ipv6
+        ));
+    }
+    out
+}
+
+#[component]
+pub fn ResultsPanel(result: Option<Result<DualStackPlan, DualStackError>>) -> Element {
+    rsx! {
+        div { class: "h-150 bg-gray-800 rounded-lg shadow-lg p-6 overflow-auto",
+            h2 { class: "text-xl font-bold mb-6 text-center", "Dual-Stack Plan" }
+
+            match result {
+                None => rsx! {
+                    p { class: "text-center text-gray-500 text-base py-20", "Enter parents and segments, then plan" }
+                },
+                Some(Err(err)) => rsx! {
+                    div { class: "bg-red-900/40 border font-roboto border-red-500 text-red-300 p-6 rounded-lg",
+                        strong { "Error: " } "{err}"
+                    }
+                },
+                Some(Ok(plan)) => {
+                    let total = 1u64 << (32 - plan.ipv4_parent.prefix_len());
+                    let pct = plan.ipv4_used as f64 / total as f64 * 100.0;
+                    let iac_subnets: Vec<IacSubnet> = plan
+                        .allocations
+                        .iter()
+                        .map(|a| IacSubnet { name: a.name.clone(), ipv4: Some(a.ipv4), ipv6: Some(a.ipv6) })
+                        .collect();
+
+                    rsx! {
+                        div { class: "flex items-center justify-between mb-3 text-sm text-left",
+                            span { class: "text-gray-400",
+                                "{plan.ipv4_parent}: {plan.ipv4_used} of {total} addresses allocated ({pct:.1}%) · {plan.ipv6_parent} → /{plan.ipv6_prefix} per segment"
+                            }
+                            ExportButtons { content: plan_csv(&plan), filename: "dual-stack-plan.csv".to_string(), mime: "text/csv" }
+                        }
+                        table { class: "w-full text-sm font-roboto text-left mb-8",
+                            thead { class: "bg-gray-700",
+                                tr {
+                                    th { class: "px-4 py-3", "Segment" }
+                                    th { class: "px-4 py-3", "Hosts" }
+                                    th { class: "px-4 py-3", "IPv4 Subnet" }
+                                    th { class: "px-4 py-3", "Gateway" }
+                                    th { class: "px-4 py-3", "IPv6 Subnet" }
+                                }
+                            }
+                            tbody {
+                                for a in plan.allocations.iter() {
+                                    tr { class: "border-t border-gray-700 hover:bg-gray-700/50",
+                                        td { class: "px-4 py-3", "{a.name}" }
+                                        td { class: "px-4 py-3", "{a.hosts} / {a.usable_hosts}" }
+                                        td { class: "px-4 py-3", "{a.ipv4}" }
+                                        td { class: "px-4 py-3", "{a.gateway.as_deref().unwrap_or(\"-\")}" }
+                                        td { class: "px-4 py-3", "{a.ipv6}" }
+                                    }
+                                }
+                            }
+                        }
+                        h3 { class: "mb-3 font-bold text-left", "Infrastructure as Code" }
+                        IacPanel { subnets: iac_subnets }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use ipnet::{Ipv4Net, Ipv6Net};
+use crate::ipv4::types::Ipv4InputError;
+use crate::ipv6::types::Ipv6InputError;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Segment {
+    pub name: String,
+    pub hosts: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DualStackAllocation {
+    pub name: String,
+    pub hosts: u32,
+    pub ipv4: Ipv4Net,
+    pub usable_hosts: u64,
+    pub gateway: Option<String>, // First usable IPv4 host
+    pub ipv6: Ipv6Net,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DualStackPlan {
+    pub ipv4_parent: Ipv4Net,
+    pub ipv6_parent: Ipv6Net,
+    pub ipv6_prefix: u8,
+    pub allocations: Vec<DualStackAllocation>, // In segment order
+    pub ipv4_used: u64, // Addresses allocated out of the IPv4 parent
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DualStackError {
+    InvalidSegment { line: usize, text: String },
+    NoSegments,
+    TooManySegments,
+    Ipv4 { segment: Option<String>, error: Ipv4InputError },
+    Ipv6(Ipv6InputError),
+    Ipv4Exhausted(String), // Segment that no longer fits
+    Ipv6Exhausted,
+    InvalidIpv6Size(String),
+}
+
+impl std::fmt::Display for DualStackError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DualStackError::InvalidSegment { line, text } => write!(f, "Line {}: '{}' is not 'name, hosts'", line, text),
+            DualStackError::NoSegments => write!(f, "Add at least one segment"),
+            DualStackError::TooManySegments => write!(f, "Too many segments"),
+            DualStackError::Ipv4 { segment: Some(name), error } => write!(f, "IPv4 parent, segment '{}': {}", name, error),
+            DualStackError::Ipv4 { segment: None, error } => write!(f, "IPv4 parent: {}", error),
+            DualStackError::Ipv6(error) => write!(f, "IPv6 parent: {}", error),
+            DualStackError::Ipv4Exhausted(name) => write!(f, "IPv4 parent has no room left for segment '{}'", name),
+            DualStackError::Ipv6Exhausted => write!(f, "IPv6 parent has fewer subnets of that size than there are segments"),
+            DualStackError::InvalidIpv6Size(text) => write!(f, "IPv6 size '{}' must be a prefix length between /0 and /128", text),
+        }
+    }
+}
+
+impl std::error::Error for DualStackError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DualStackError::Ipv4 { error, .. } => Some(error),
+            DualStackError::Ipv6(error) => Some(error),
+            _ => None,
+        }
+    }
+}
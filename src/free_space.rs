@@ -0,0 +1,334 @@
+// src/free_space.rs
+use ipnet::IpNet;
+use crate::address_math::{check_host_bits, HostBits, HostBitsPolicy};
+
+/// Which free blocks to hand out first
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FitStrategy {
+    First, // Lowest addresses first
+    Best,  // Smallest free block that fits first, keeping large blocks intact
+    Last,  // Highest addresses first
+}
+
+impl FitStrategy {
+    pub const ALL: [FitStrategy; 3] = [FitStrategy::First, FitStrategy::Best, FitStrategy::Last];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FitStrategy::First => "First fit",
+            FitStrategy::Best => "Best fit",
+            FitStrategy::Last => "Last fit",
+        }
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            FitStrategy::First => "first",
+            FitStrategy::Best => "best",
+            FitStrategy::Last => "last",
+        }
+    }
+
+    pub fn from_key(key: &str) -> FitStrategy {
+        FitStrategy::ALL.into_iter().find(|s| s.key() == key).unwrap_or(FitStrategy::First)
+    }
+}
+
+fn overlaps(a: &IpNet, b: &IpNet) -> bool {
+    a.contains(b) || b.contains(a)
+}
+
+fn address_bits(net: &IpNet) -> u32 {
+    match net {
+        IpNet::V4(_) => 32,
+        IpNet::V6(_) => 128,
+    }
+}
+
+/// Number of addresses in `net`, saturating for ::/0
+pub fn block_size(net: &IpNet) -> u128 {
+    1u128.checked_shl(address_bits(net) - net.prefix_len() as u32).unwrap_or(u128::MAX)
+}
+
+/// Largest blocks of `block` not covered by `used`, in address order. Halves are only
+/// visited when partly used, so the cost is bounded by used.len() × prefix depth.
+pub fn free_blocks(block: IpNet, used: &[IpNet]) -> Vec<IpNet> {
+    let inside: Vec<IpNet> = used.iter().copied().filter(|u| overlaps(u, &block)).collect();
+    if inside.is_empty() {
+        return vec![block];
+    }
+    if inside.iter().any(|u| u.contains(&block)) {
+        return vec![];
+    }
+    match block.subnets(block.prefix_len() + 1) {
+        Ok(halves) => halves.flat_map(|half| free_blocks(half, &inside)).collect(),
+        Err(_) => vec![],
+    }
+}
+
+/// The i-th aligned /len inside `block`, counted from the start or from the end
+fn nth_block(block: &IpNet, len: u8, i: u128, from_end: bool) -> Option<IpNet> {
+    let host_bits = address_bits(block) - len as u32;
+    // Index of the last /len; 2^128 - 1 when ::/0 is split into /128s
+    let split_bits = (len - block.prefix_len()) as u32;
+    let last = u128::MAX.checked_shr(128 - split_bits).unwrap_or(0);
+    if i > last {
+        return None;
+    }
+    let index = if from_end { last - i } else { i };
+    let offset = index.checked_shl(host_bits).unwrap_or(0);
+    let start = match block.network() {
+        std::net::IpAddr::V4(a) => u32::from(a) as u128,
+        std::net::IpAddr::V6(a) => u128::from(a),
+    } + offset;
+    let addr = match block {
+        IpNet::V4(_) => std::net::IpAddr::V4((start as u32).into()),
+        IpNet::V6(_) => std::net::IpAddr::V6(start.into()),
+    };
+    IpNet::new(addr, len).ok()
+}
+
+/// Up to `count` free aligned /len blocks inside `parent`, picked by `strategy`
+pub fn find_free(parent: IpNet, used: &[IpNet], len: u8, count: usize, strategy: FitStrategy) -> Vec<IpNet> {
+    let mut candidates: Vec<IpNet> = free_blocks(parent, used).into_iter().filter(|b| b.prefix_len() <= len).collect();
+    match strategy {
+        FitStrategy::First => {}
+        FitStrategy::Best => candidates.sort_by_key(|b| (std::cmp::Reverse(b.prefix_len()), b.network())),
+        FitStrategy::Last => candidates.reverse(),
+    }
+    let from_end = strategy == FitStrategy::Last;
+
+    let mut found = vec![];
+    for block in candidates {
+        let mut i = 0;
+        while found.len() < count {
+            match nth_block(&block, len, i, from_end) {
+                Some(net) => found.push(net),
+                None => break,
+            }
+            i += 1;
+        }
+        if found.len() >= count {
+            break;
+        }
+    }
+    found
+}
+
+/// Total free addresses in `parent`
+pub fn free_addresses(parent: IpNet, used: &[IpNet]) -> u128 {
+    free_blocks(parent, used).iter().map(block_size).fold(0u128, |acc, n| acc.saturating_add(n))
+}
+
+/// Outcome of a next-free search, shown alongside the found blocks
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FreeSearch {
+    pub used: Vec<IpNet>,     // Used prefixes inside the parent, after truncation
+    pub ignored: Vec<IpNet>,  // Used prefixes outside the parent
+    pub strategy: FitStrategy,
+    pub prefix_len: u8,
+    pub requested: usize,
+    pub free_addresses: u128,
+    pub normalized: Vec<HostBits>, // Used prefixes that were typed with host bits set
+}
+
+/// Problems with the next-free form fields, shared by both families
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FieldError {
+    InvalidEntry { line: usize, text: String }, // Used-list token that isn't a prefix or address
+    InvalidSize(String),
+    PrefixOutOfRange { max: u8 },
+    TooManyHosts { bits: u8 },                   // More hosts than a /0 of this family holds
+    InvalidCount(String),
+    HostBitsSet { line: usize, found: Box<HostBits> }, // Strict mode only
+}
+
+impl std::fmt::Display for FieldError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FieldError::InvalidEntry { line, text } => write!(f, "Line {}: '{}' is not a prefix or address", line, text),
+            FieldError::InvalidSize(text) => write!(f, "Size '{}' must be a host count or a prefix like /26", text),
+            FieldError::PrefixOutOfRange { max } => write!(f, "Prefix length must be between /0 and /{}", max),
+            FieldError::TooManyHosts { bits } => write!(f, "Too many hosts requested for a {}-bit address space", bits),
+            FieldError::InvalidCount(text) => write!(f, "Blocks wanted must be a whole number of at least 1 (got '{}')", text),
+            FieldError::HostBitsSet { line, found } => write!(f, "Line {}: {}", line, found),
+        }
+    }
+}
+
+impl std::error::Error for FieldError {}
+
+/// One prefix (or bare address) per line; commas and whitespace also separate entries.
+/// Prefixes with host bits set are normalized and returned alongside, or rejected under `policy`.
+pub fn parse_used(text: &str, policy: HostBitsPolicy) -> Result<(Vec<IpNet>, Vec<HostBits>), FieldError> {
+    let mut used = vec![];
+    let mut normalized = vec![];
+    for (i, raw) in text.lines().enumerate() {
+        let line = raw.split('#').next().unwrap_or("");
+        for token in line.split(|c: char| c == ',' || c.is_whitespace()).filter(|t| !t.is_empty()) {
+            let net = if let Ok(net) = token.parse::<IpNet>() {
+                let found = check_host_bits(net, policy).map_err(|found| FieldError::HostBitsSet { line: i + 1, found: Box::new(found) })?;
+                normalized.extend(found);
+                net.trunc()
+            } else if let Ok(addr) = token.parse::<std::net::IpAddr>() {
+                IpNet::from(addr)
+            } else {
+                return Err(FieldError::InvalidEntry { line: i + 1, text: token.to_string() });
+            };
+            used.push(net);
+        }
+    }
+    Ok((used, normalized))
+}
+
+/// Host-count or "/N" size field, turned into a prefix length. `reserved` is the number of
+/// addresses a subnet loses to network/broadcast or platform reservations.
+pub fn parse_size(size: &str, bits: u8, reserved: u128) -> Result<u8, FieldError> {
+    let trimmed = size.trim();
+    if let Some(prefix) = trimmed.strip_prefix('/') {
+        return match prefix.parse::<u8>() {
+            Ok(p) if p <= bits => Ok(p),
+            _ => Err(FieldError::PrefixOutOfRange { max: bits }),
+        };
+    }
+    let hosts: u128 = trimmed.parse().map_err(|_| FieldError::InvalidSize(trimmed.to_string()))?;
+    let required = hosts.checked_add(reserved).filter(|&n| n > 0).ok_or(FieldError::TooManyHosts { bits })?;
+    let host_bits = 128 - (required - 1).leading_zeros();
+    if host_bits > bits as u32 {
+        return Err(FieldError::TooManyHosts { bits });
+    }
+    Ok(bits - host_bits as u8)
+}
+
+/// "Blocks wanted" field: empty means one block; capped at `limit`
+pub fn parse_count(count: &str, limit: usize) -> Result<usize, FieldError> {
+    match count.trim() {
+        "" => Ok(1),
+        text => match text.parse::<usize>() {
+            Ok(n) if n >= 1 => Ok(n.min(limit)),
+            _ => Err(FieldError::InvalidCount(text.to_string())),
+        },
+    }
+}
+
+/// Split the used list by family/containment and run the search
+pub fn search(
+    parent: IpNet,
+    used: Vec<IpNet>,
+    normalized: Vec<HostBits>,
+    len: u8,
+    count: usize,
+    strategy: FitStrategy,
+) -> (Vec<IpNet>, FreeSearch) {
+    let (used, ignored): (Vec<IpNet>, Vec<IpNet>) = used.into_iter().partition(|u| overlaps(u, &parent));
+    let found = find_free(parent, &used, len, count, strategy);
+    let free = free_addresses(parent, &used);
+    (found, FreeSearch { used, ignored, strategy, prefix_len: len, requested: count, free_addresses: free, normalized })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn net(s: &str) -> IpNet {
+        s.parse().unwrap()
+    }
+
+    fn nets(list: &[&str]) -> Vec<IpNet> {
+        list.iter().map(|s| net(s)).collect()
+    }
+
+    #[test]
+    fn aggregates_free_space_into_largest_blocks() {
+        let free = free_blocks(net("10.0.0.0/24"), &nets(&["10.0.0.64/26"]));
+        assert_eq!(free, nets(&["10.0.0.0/26", "10.0.0.128/25"]));
+        assert_eq!(free_blocks(net("10.0.0.0/24"), &nets(&["10.0.0.0/16"])), vec![]);
+        assert_eq!(free_blocks(net("10.0.0.0/24"), &nets(&["192.168.0.0/16"])), nets(&["10.0.0.0/24"]));
+        assert_eq!(free_addresses(net("10.0.0.0/24"), &nets(&["10.0.0.64/26", "10.0.0.200/32"])), 191);
+        assert_eq!(block_size(&net("::/0")), u128::MAX);
+    }
+
+    #[test]
+    fn strategies_pick_different_blocks() {
+        let parent = net("10.0.0.0/24");
+        let used = nets(&["10.0.0.32/27", "10.0.0.128/26"]);
+        assert_eq!(find_free(parent, &used, 27, 2, FitStrategy::First), nets(&["10.0.0.0/27", "10.0.0.64/27"]));
+        // The lone /27 gap is the tightest fit
+        assert_eq!(find_free(parent, &used, 27, 1, FitStrategy::Best), nets(&["10.0.0.0/27"]));
+        assert_eq!(find_free(parent, &used, 26, 1, FitStrategy::Best), nets(&["10.0.0.64/26"]));
+        assert_eq!(find_free(parent, &used, 27, 2, FitStrategy::Last), nets(&["10.0.0.224/27", "10.0.0.192/27"]));
+        assert_eq!(find_free(parent, &used, 25, 1, FitStrategy::First), vec![]);
+    }
+
+    #[test]
+    fn splits_the_whole_ipv6_space() {
+        let found = find_free(net("::/0"), &[], 128, 2, FitStrategy::Last);
+        assert_eq!(found, nets(&["ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff/128", "ffff:ffff:ffff:ffff:ffff:ffff:ffff:fffe/128"]));
+    }
+
+    #[test]
+    fn parses_used_lists() {
+        let (used, normalized) = parse_used("10.0.0.0/24, 10.0.1.5\n# comment\n10.0.2.7/24 # note", HostBitsPolicy::Normalize).unwrap();
+        assert_eq!(used, nets(&["10.0.0.0/24", "10.0.1.5/32", "10.0.2.0/24"]));
+        assert_eq!(normalized.iter().map(|h| h.input).collect::<Vec<_>>(), nets(&["10.0.2.7/24"]));
+        assert_eq!(parse_used("2001:db8::1", HostBitsPolicy::Reject), Ok((nets(&["2001:db8::1/128"]), vec![])));
+        assert_eq!(parse_used("10.0.0.0/24\nbogus", HostBitsPolicy::Normalize), Err(FieldError::InvalidEntry { line: 2, text: "bogus".into() }));
+        assert!(matches!(
+            parse_used("10.0.0.0/24\n10.0.2.7/24", HostBitsPolicy::Reject),
+            Err(FieldError::HostBitsSet { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn parses_sizes_and_counts() {
+        assert_eq!(parse_size("/26", 32, 2), Ok(26));
+        assert_eq!(parse_size("62", 32, 2), Ok(26));
+        assert_eq!(parse_size("63", 32, 2), Ok(25));
+        assert_eq!(parse_size("1", 32, 5), Ok(29));
+        assert_eq!(parse_size("0", 128, 0), Err(FieldError::TooManyHosts { bits: 128 }));
+        assert!(parse_size("/33", 32, 2).is_err());
+        assert!(parse_size("4294967295", 32, 2).is_err());
+        assert!(parse_size("lots", 32, 2).is_err());
+        assert_eq!(parse_count("", 10), Ok(1));
+        assert_eq!(parse_count("500", 10), Ok(10));
+        assert!(parse_count("0", 10).is_err());
+    }
+
+    #[test]
+    fn search_separates_ignored_prefixes() {
+        let (found, search) = search(net("10.0.0.0/24"), nets(&["10.0.0.0/25", "172.16.0.0/12"]), vec![], 25, 4, FitStrategy::First);
+        assert_eq!(found, nets(&["10.0.0.128/25"]));
+        assert_eq!(search.used, nets(&["10.0.0.0/25"]));
+        assert_eq!(search.ignored, nets(&["172.16.0.0/12"]));
+        assert_eq!(search.free_addresses, 128);
+    }
+
+    proptest! {
+        #[test]
+        fn found_blocks_are_free_aligned_and_inside(
+            used in proptest::collection::vec((any::<u8>(), 24u8..=32), 0..12),
+            len in 24u8..=30,
+            count in 1usize..8,
+            strategy in prop_oneof![Just(FitStrategy::First), Just(FitStrategy::Best), Just(FitStrategy::Last)],
+        ) {
+            let parent = net("10.0.0.0/24");
+            let used: Vec<IpNet> = used
+                .into_iter()
+                .map(|(host, len)| IpNet::new(std::net::Ipv4Addr::new(10, 0, 0, host).into(), len).unwrap().trunc())
+                .collect();
+            let found = find_free(parent, &used, len, count, strategy);
+            prop_assert!(found.len() <= count);
+            for (i, block) in found.iter().enumerate() {
+                prop_assert!(parent.contains(block));
+                prop_assert_eq!(block.prefix_len(), len);
+                prop_assert!(used.iter().all(|u| !u.contains(block) && !block.contains(u)));
+                prop_assert!(found[i + 1..].iter().all(|other| other != block));
+            }
+            // Free space never exceeds the parent, and the found blocks fit inside it
+            let free = free_addresses(parent, &used);
+            prop_assert!(free <= 256);
+            prop_assert!(found.len() as u128 * block_size(&IpNet::V4(ipnet::Ipv4Net::new(std::net::Ipv4Addr::UNSPECIFIED, len).unwrap())) <= free);
+        }
+    }
+}
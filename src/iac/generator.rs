@@ -0,0 +1,221 @@
+// src/iac/generator.rs
+use ipnet::Ipv6Net;
+use crate::iac::types::{IacFormat, IacOptions, IacSubnet};
+use crate::ipv6::types::Ipv6InputError;
+
+pub const MAX_IAC_SUBNETS: usize = 256;
+
+/// Give each subnet the next /64 out of `block`, in order, for dual-stack plans
+pub fn assign_ipv6_64s(subnets: &[IacSubnet], block: Ipv6Net) -> Result<Vec<IacSubnet>, Ipv6InputError> {
+    if block.prefix_len() > 64 {
+        return Err(Ipv6InputError::PrefixOutOfRange { min: 0, max: 64 });
+    }
+    let available = 1u128.checked_shl(64 - block.prefix_len() as u32).unwrap_or(u128::MAX);
+    if subnets.len() as u128 > available {
+        let needed = (subnets.len() as u128).next_power_of_two().trailing_zeros();
+        return Err(Ipv6InputError::NotEnoughBits { needed, available: 64 - block.prefix_len() as u32 });
+    }
+    let base = u128::from(block.network());
+
+    Ok(subnets
+        .iter()
+        .enumerate()
+        .map(|(i, sub)| {
+            let addr = base + ((i as u128) << 64);
+            IacSubnet { ipv6: Ipv6Net::new(addr.into(), 64).ok(), ..sub.clone() }
+        })
+        .collect())
+}
+
+pub fn generate_iac(subnets: &[IacSubnet], options: &IacOptions) -> String {
+    let subnets = &subnets[..subnets.len().min(MAX_IAC_SUBNETS)];
+    match options.format {
+        IacFormat::TerraformAws => terraform_aws(subnets, options),
+        IacFormat::TerraformAzure => terraform_azure(subnets, options),
+        IacFormat::TerraformGcp => terraform_gcp(subnets, options),
+        IacFormat::CloudFormation => cloudformation(subnets, options),
+        IacFormat::Ansible => ansible(subnets),
+    }
+}
+
+/// Terraform resource names: lowercase letters, digits and underscores, not starting with a digit
+fn resource_name(name: &str) -> String {
+    let mut out: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .collect();
+    if out.is_empty() || out.starts_with(|c: char| c.is_ascii_digit()) {
+        out.insert_str(0, "subnet_");
+    }
+    out
+}
+
+/// CloudFormation logical IDs: alphanumeric only, so words are joined in CamelCase
+fn logical_id(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let mut chars = w.chars();
+            chars.next().map(|c| c.to_ascii_uppercase().to_string() + chars.as_str()).unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Double-quoted scalar; a JSON string literal is valid YAML, so `:`, `#` or a leading `-` stay text
+fn yaml_string(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+/// HCL string literal: JSON escaping for quotes and backslashes, plus `$${`/`%%{` so a name
+/// is never read as a template interpolation or directive
+fn hcl_string(value: &str) -> String {
+    yaml_string(value).replace("${", "$${").replace("%{", "%%{")
+}
+
+fn terraform_aws(subnets: &[IacSubnet], o: &IacOptions) -> String {
+    let mut out = String::new();
+
+    for sub in subnets {
+        out.push_str(&format!("resource \"aws_subnet\" \"{}\" {{\n", resource_name(&sub.name)));
+        out.push_str(&format!("  vpc_id = aws_vpc.{}.id\n", o.network));
+        if let Some(v4) = sub.ipv4 {
+            out.push_str(&format!("  cidr_block = \"{}\"\n", v4));
+        }
+        if let Some(v6) = sub.ipv6 {
+            out.push_str(&format!("  ipv6_cidr_block = \"{}\"\n", v6));
+            out.push_str("  assign_ipv6_address_on_creation = true\n");
+            if sub.ipv4.is_none() {
+                out.push_str("  ipv6_native = true\n");
+            }
+        }
+        out.push_str(&format!("\n  tags = {{\n    Name = {}\n  }}\n}}\n\n", hcl_string(&sub.name)));
+    }
+    out
+}
+
+fn terraform_azure(subnets: &[IacSubnet], o: &IacOptions) -> String {
+    let mut out = String::new();
+
+    for sub in subnets {
+        let prefixes: Vec<String> = sub
+            .ipv4
+            .map(|n| n.to_string())
+            .into_iter()
+            .chain(sub.ipv6.map(|n| n.to_string()))
+            .map(|p| format!("\"{}\"", p))
+            .collect();
+        out.push_str(&format!("resource \"azurerm_subnet\" \"{}\" {{\n", resource_name(&sub.name)));
+        out.push_str(&format!("  name                 = {}\n", hcl_string(&sub.name)));
+        out.push_str(&format!("  resource_group_name  = azurerm_resource_group.{}.name\n", o.network));
+        out.push_str(&format!("  virtual_network_name = azurerm_virtual_network.{}.name\n", o.network));
+        out.push_str(&format!("  address_prefixes     = [{}]\n", prefixes.join(", ")));
+        out.push_str("}\n\n");
+    }
+    out
+}
+
+// GCP assigns subnet IPv6 ranges itself, so only the stack type is requested
+fn terraform_gcp(subnets: &[IacSubnet], o: &IacOptions) -> String {
+    let mut out = String::new();
+
+    for sub in subnets {
+        out.push_str(&format!("resource \"google_compute_subnetwork\" \"{}\" {{\n", resource_name(&sub.name)));
+        out.push_str(&format!("  name = {}\n", hcl_string(&sub.name)));
+        out.push_str(&format!("  network = google_compute_network.{}.id\n", o.network));
+        if let Some(v4) = sub.ipv4 {
+            out.push_str(&format!("  ip_cidr_range = \"{}\"\n", v4));
+        }
+        if let Some(v6) = sub.ipv6 {
+            let stack = if sub.ipv4.is_some() { "IPV4_IPV6" } else { "IPV6_ONLY" };
+            out.push_str(&format!("  stack_type = \"{}\"\n", stack));
+            out.push_str("  ipv6_access_type = \"INTERNAL\"\n");
+            out.push_str(&format!("  # Planned IPv6 range {} (GCP allocates the /64)\n", v6));
+        }
+        out.push_str("}\n\n");
+    }
+    out
+}
+
+fn cloudformation(subnets: &[IacSubnet], o: &IacOptions) -> String {
+    let mut out = String::from("Resources:\n");
+
+    for sub in subnets {
+        out.push_str(&format!("  {}:\n", logical_id(&sub.name)));
+        out.push_str("    Type: AWS::EC2::Subnet\n");
+        out.push_str("    Properties:\n");
+        out.push_str(&format!("      VpcId: !Ref {}\n", logical_id(&o.network)));
+        if let Some(v4) = sub.ipv4 {
+            out.push_str(&format!("      CidrBlock: {}\n", v4));
+        }
+        if let Some(v6) = sub.ipv6 {
+            out.push_str(&format!("      Ipv6CidrBlock: {}\n", v6));
+            out.push_str("      AssignIpv6AddressOnCreation: true\n");
+            if sub.ipv4.is_none() {
+                out.push_str("      Ipv6Native: true\n");
+            }
+        }
+        out.push_str(&format!("      Tags:\n        - Key: Name\n          Value: {}\n", yaml_string(&sub.name)));
+    }
+    out
+}
+
+// Keys match the amazon.aws.ec2_vpc_subnet module arguments
+fn ansible(subnets: &[IacSubnet]) -> String {
+    let mut out = String::from("subnets:\n");
+
+    for sub in subnets {
+        out.push_str(&format!("  - name: {}\n", yaml_string(&sub.name)));
+        if let Some(v4) = sub.ipv4 {
+            out.push_str(&format!("    cidr: {}\n", v4));
+        }
+        if let Some(v6) = sub.ipv6 {
+            out.push_str(&format!("    ipv6_cidr: {}\n", v6));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::assert_golden;
+
+    fn subnet(name: &str, ipv4: &str) -> IacSubnet {
+        IacSubnet { name: name.to_string(), ipv4: Some(ipv4.parse().unwrap()), ipv6: None }
+    }
+
+    #[test]
+    fn assigns_consecutive_64s() {
+        let subnets = [subnet("web", "10.0.0.0/24"), subnet("db", "10.0.1.0/24")];
+        let assigned = assign_ipv6_64s(&subnets, "2001:db8::/56".parse().unwrap()).unwrap();
+        assert_eq!(assigned[1].ipv6, Some("2001:db8:0:1::/64".parse().unwrap()));
+        assert_eq!(assign_ipv6_64s(&subnets, "2001:db8::/64".parse().unwrap()), Err(Ipv6InputError::NotEnoughBits { needed: 1, available: 0 }));
+        assert_eq!(assign_ipv6_64s(&subnets, "2001:db8::/65".parse().unwrap()), Err(Ipv6InputError::PrefixOutOfRange { min: 0, max: 64 }));
+    }
+
+    #[test]
+    fn quotes_names_in_yaml() {
+        let subnets = [subnet("web: \"front\"", "10.0.0.0/24")];
+        for format in [IacFormat::CloudFormation, IacFormat::Ansible] {
+            let output = generate_iac(&subnets, &IacOptions::new(format));
+            assert!(output.contains(r#""web: \"front\"""#), "{:?} left the name unquoted", format);
+        }
+    }
+
+    #[test]
+    fn quotes_names_in_hcl() {
+        let subnets = [subnet("web \"front\" \\ ${var.x}", "10.0.0.0/24")];
+        for format in [IacFormat::TerraformAws, IacFormat::TerraformAzure, IacFormat::TerraformGcp] {
+            let output = generate_iac(&subnets, &IacOptions::new(format));
+            assert!(output.contains(r#""web \"front\" \\ $${var.x}""#), "{:?} left the name unescaped", format);
+        }
+    }
+
+    #[test]
+    fn golden_templates() {
+        let subnets = assign_ipv6_64s(&[subnet("web", "10.0.0.0/24"), subnet("db", "10.0.1.0/26")], "2001:db8::/56".parse().unwrap()).unwrap();
+        for format in IacFormat::ALL {
+            assert_golden(&format!("iac_{}.txt", format.key()), &generate_iac(&subnets, &IacOptions::new(format)));
+        }
+    }
+}
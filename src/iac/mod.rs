@@ -0,0 +1,6 @@
+// src/iac/mod.rs
+pub mod generator;
+pub mod panel;
+pub mod types;
+
+pub use panel::IacPanel;
@@ -0,0 +1,95 @@
+// src/iac/panel.rs
+use dioxus::prelude::*;
+use ipnet::Ipv6Net;
+use crate::address_math::host_bits;
+use crate::components::ExportButtons;
+use crate::iac::generator::{assign_ipv6_64s, generate_iac, MAX_IAC_SUBNETS};
+use crate::iac::types::{IacFormat, IacOptions, IacSubnet};
+use crate::ipv6::{types::Ipv6InputError, SharedIpv6Network};
+
+#[component]
+pub fn IacPanel(subnets: Vec<IacSubnet>) -> Element {
+    let mut format = use_signal(|| IacFormat::TerraformAws);
+    let mut network = use_signal(|| "main".to_string());
+    let mut ipv6_block = use_signal(String::new);
+    let shared_ipv6 = *use_context::<SharedIpv6Network>().0.read();
+
+    // IPv4-only plans can be made dual-stack by carving /64s out of an IPv6 block
+    let is_v4_only = subnets.iter().all(|s| s.ipv6.is_none());
+    let block_input = ipv6_block();
+    let normalized = block_input.trim().parse::<Ipv6Net>().ok().and_then(|block| host_bits(block.into()));
+    let (subnets, dual_stack_error) = match block_input.trim() {
+        "" => (subnets, None),
+        text => match text.parse::<Ipv6Net>() {
+            Ok(block) => match assign_ipv6_64s(&subnets, block.trunc()) {
+                Ok(assigned) => (assigned, None),
+                Err(Ipv6InputError::NotEnoughBits { .. }) => (subnets, Some(format!("{} has fewer /64s than there are subnets", block.trunc()))),
+                Err(err) => (subnets, Some(format!("IPv6 block: {}", err))),
+            },
+            Err(_) => (subnets, Some("Invalid IPv6 block, e.g. 2001:db8:10::/56".to_string())),
+        },
+    };
+
+    let options = IacOptions { network: network(), ..IacOptions::new(format()) };
+    let output = generate_iac(&subnets, &options);
+    let field = "w-full px-3 py-2 text-sm border border-gray-600 rounded-lg bg-gray-700";
+
+    rsx! {
+        div { class: "text-left",
+            div { class: "grid grid-cols-2 gap-3 mb-4 text-sm",
+                div {
+                    label { class: "block mb-1 font-medium", "Format" }
+                    select {
+                        class: "{field}",
+                        onchange: move |e| format.set(IacFormat::from_key(&e.value())),
+                        for f in IacFormat::ALL {
+                            option { value: f.key(), selected: format() == f, "{f.label()}" }
+                        }
+                    }
+                }
+                div {
+                    label { class: "block mb-1 font-medium", "VPC / VNet Resource Name" }
+                    input { class: "{field}", value: "{network}", oninput: move |e| network.set(e.value()) }
+                }
+                if is_v4_only {
+                    div { class: "col-span-2",
+                        label { class: "block mb-1 font-medium", "IPv6 Block for Dual-Stack (optional)" }
+                        p { class: "mb-2 text-xs text-gray-400",
+                            "Each subnet gets the next /64 carved from this block, in order. The button copies the IPv6 tab's base network, not its computed subnets."
+                        }
+                        div { class: "flex space-x-2",
+                            input {
+                                class: "{field}",
+                                placeholder: "none, e.g. 2001:db8:10::/56",
+                                value: "{ipv6_block}",
+                                oninput: move |e| ipv6_block.set(e.value())
+                            }
+                            if let Some(net) = shared_ipv6 {
+                                button {
+                                    class: "text-xs whitespace-nowrap bg-gray-600 hover:bg-gray-500 px-3 rounded",
+                                    onclick: move |_| ipv6_block.set(net.to_string()),
+                                    "Copy IPv6 tab's network ({net})"
+                                }
+                            }
+                        }
+                        if let Some(err) = &dual_stack_error {
+                            p { class: "mt-1 text-xs text-red-400", "{err}" }
+                        }
+                        if let Some(found) = normalized {
+                            p { class: "mt-1 text-xs text-yellow-400", "Normalized: {found}" }
+                        }
+                    }
+                }
+            }
+            div { class: "flex items-center justify-between mb-2",
+                p { class: "text-xs text-gray-500",
+                    if subnets.len() > MAX_IAC_SUBNETS {
+                        "Resources generated for the first {MAX_IAC_SUBNETS} of {subnets.len()} subnets."
+                    }
+                }
+                ExportButtons { content: output.clone(), filename: format().file_name().to_string(), mime: format().mime() }
+            }
+            pre { class: "max-h-96 overflow-auto bg-gray-900 p-3 rounded text-xs font-roboto", "{output}" }
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use ipnet::{Ipv4Net, Ipv6Net};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IacFormat {
+    TerraformAws,
+    TerraformAzure,
+    TerraformGcp,
+    CloudFormation,
+    Ansible,
+}
+
+impl IacFormat {
+    pub const ALL: [IacFormat; 5] = [
+        IacFormat::TerraformAws,
+        IacFormat::TerraformAzure,
+        IacFormat::TerraformGcp,
+        IacFormat::CloudFormation,
+        IacFormat::Ansible,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            IacFormat::TerraformAws => "Terraform – aws_subnet",
+            IacFormat::TerraformAzure => "Terraform – azurerm_subnet",
+            IacFormat::TerraformGcp => "Terraform – google_compute_subnetwork",
+            IacFormat::CloudFormation => "CloudFormation (YAML)",
+            IacFormat::Ansible => "Ansible vars (YAML)",
+        }
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            IacFormat::TerraformAws => "tf-aws",
+            IacFormat::TerraformAzure => "tf-azure",
+            IacFormat::TerraformGcp => "tf-gcp",
+            IacFormat::CloudFormation => "cfn",
+            IacFormat::Ansible => "ansible",
+        }
+    }
+
+    pub fn from_key(key: &str) -> IacFormat {
+        IacFormat::ALL.into_iter().find(|f| f.key() == key).unwrap_or(IacFormat::TerraformAws)
+    }
+
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            IacFormat::TerraformAws | IacFormat::TerraformAzure | IacFormat::TerraformGcp => "subnets.tf",
+            IacFormat::CloudFormation => "subnets.cfn.yaml",
+            IacFormat::Ansible => "subnets.yml",
+        }
+    }
+
+    pub fn mime(&self) -> &'static str {
+        match self {
+            IacFormat::TerraformAws | IacFormat::TerraformAzure | IacFormat::TerraformGcp => "text/plain",
+            IacFormat::CloudFormation | IacFormat::Ansible => "application/yaml",
+        }
+    }
+}
+
+/// One named allocation; dual-stack subnets carry both families
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IacSubnet {
+    pub name: String,
+    pub ipv4: Option<Ipv4Net>,
+    pub ipv6: Option<Ipv6Net>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IacOptions {
+    pub format: IacFormat,
+    pub network: String, // Terraform resource / CloudFormation logical ID of the VPC or VNet
+}
+
+impl IacOptions {
+    pub fn new(format: IacFormat) -> Self {
+        IacOptions { format, network: "main".to_string() }
+    }
+}
@@ -1,6 +1,9 @@
-use ipnet::{Ipv4Net};
+use ipnet::{IpNet, Ipv4Net};
 use std::net::Ipv4Addr;
-use crate::ipv4::types::{CalculationResult, Ipv4InputError, SubnetResult};
+use crate::ipv4::types::{CalculationResult, Ipv4InputError, PlatformProfile, SubnetResult};
+use crate::ipv4::wildcard::analyze_wildcard;
+use crate::address_math::{check_host_bits, HostBitsPolicy};
+use crate::free_space::{self, parse_count, parse_size, parse_used, FitStrategy};
 
 pub const LIMIT: usize = 4096;  // Maximum number of subnets to process/display
 pub const LAST_N: usize = 10;    // Always show the last N subnets when truncated
@@ -8,34 +11,40 @@ pub const LAST_N: usize = 10;    // Always show the last N subnets when truncate
 pub fn parse_network(ip: &str, mask_or_prefix: &str) -> Result<Ipv4Net, Ipv4InputError> {
     let ip: Ipv4Addr = ip.trim()
     .parse()
-    .map_err(|e: std::net::AddrParseError| Ipv4InputError::ParseError(e.to_string()))?;
+    .map_err(|_| Ipv4InputError::InvalidAddress(ip.trim().to_string()))?;
 
     let trimmed = mask_or_prefix.trim();
 
     // Try as CIDR prefix first (e.g. "24")
     if let Ok(prefix) = trimmed.strip_prefix('/').unwrap_or(trimmed).parse::<u8>() {
-        if prefix > 32 {
-            return Err(Ipv4InputError::InvalidPrefix);
-        }
-        return Ok(Ipv4Net::new(ip, prefix).map_err(|e| Ipv4InputError::InvalidPrefix)?);
+        return Ipv4Net::new(ip, prefix).map_err(|_| Ipv4InputError::PrefixOutOfRange { min: 0, max: 32 });
     }
 
     if let Ok(mask) = trimmed.parse::<Ipv4Addr>() {
         return Ipv4Net::with_netmask(ip, mask)
-            .map_err(|_| Ipv4InputError::InvalidMask);
+            .map_err(|_| Ipv4InputError::InvalidMask(trimmed.to_string()));
     }
 
-    Err(Ipv4InputError::ParseError("Invalid CIDR or subnet mask".into()))
+    Err(Ipv4InputError::InvalidMask(trimmed.to_string()))
 }
 
-fn build_subnet_result(net: Ipv4Net) -> SubnetResult {
+/// Addresses, masks and usable host range of one subnet under the platform's reservations
+pub fn build_subnet_result(net: Ipv4Net, platform: PlatformProfile) -> SubnetResult {
 
-    let total = 2u32.pow(32 - net.prefix_len() as u32);
+    // u64 so 0.0.0.0/0 (2^32 addresses) is counted exactly
+    let total = 1u64 << (32 - net.prefix_len() as u32);
     let hosts = net.hosts();
     let mut iter = hosts;
-    let first = iter.next().map(|h| h.to_string());
-    let last = iter.last().map(|h| h.to_string());
-    let usable = if total >= 2 { total - 2 } else { 0 };
+    let mut first = iter.next().map(|h| h.to_string());
+    let mut last = iter.last().map(|h| h.to_string());
+    let mut usable = if total >= 2 { total - 2 } else { 0 };
+
+    // Cloud platforms keep extra addresses at both ends of every subnet
+    if platform != PlatformProfile::Standard {
+        usable = total.saturating_sub(platform.reserved() as u64);
+        first = (usable > 0).then(|| Ipv4Addr::from(u32::from(net.network()) + platform.reserved_start()).to_string());
+        last = (usable > 0).then(|| Ipv4Addr::from(u32::from(net.broadcast()) - platform.reserved_end()).to_string());
+    }
 
     SubnetResult {
         network: net,
@@ -53,43 +62,55 @@ pub fn calculate(
     mask_or_prefix: &str,
     needed_hosts: Option<u32>,
     needed_subnets: Option<u32>,
+    platform: PlatformProfile,
+    policy: HostBitsPolicy,
 ) -> Result<CalculationResult, Ipv4InputError> {
     let base_network = parse_network(ip, mask_or_prefix)?;
+    let host_bits = check_host_bits(base_network.into(), policy).map_err(|found| Ipv4InputError::HostBitsSet(Box::new(found)))?;
+    let (min_prefix, max_prefix) = platform.prefix_range();
 
     let mut subnets = Vec::new();
 
     let (new_prefix, subnet_iter): (Option<u8>, Box<dyn Iterator<Item = Ipv4Net>>) = if let Some(hosts) = needed_hosts {
         // Find smallest prefix that gives at least 'hosts' usable
-        let required = hosts + 2; // include network + broadcast
-        let new_prefix = 32 - (required.next_power_of_two().trailing_zeros());
-
-
-        let available_usable = if 2u32.pow(32 - base_network.prefix_len() as u32) >= 2 {
-                2u32.pow(32 - base_network.prefix_len() as u32) - 2
-            } else {
-                0
-            };
+        // include network + broadcast (+ platform reservations), in u64 so the sum can't wrap
+        let required = (hosts as u64 + platform.reserved() as u64).next_power_of_two();
+        let available_usable = (1u64 << (32 - base_network.prefix_len() as u32)).saturating_sub(platform.reserved() as u64);
+        if required > 1u64 << 32 || hosts as u64 > available_usable {
+            return Err(Ipv4InputError::TooManyHosts { requested: hosts, max: available_usable, platform });
+        }
+        // Never go below the platform's smallest subnet
+        let new_prefix = (32 - required.trailing_zeros()).min(max_prefix as u32);
 
-        if hosts > available_usable {
-                return Err(Ipv4InputError::ParseError(format!(
-                    "Too many hosts requested",
-                )));
-            }
+        // The platform's largest subnet (clamped max_prefix) is still smaller than the base network
+        if (new_prefix as u8) < base_network.prefix_len() {
+            return Err(Ipv4InputError::PlatformPrefixLimit { platform, prefix: base_network.prefix_len() });
+        }
 
-        (Some(new_prefix as u8), Box::new(base_network.subnets(new_prefix as u8).unwrap()))
+        (Some(new_prefix as u8), Box::new(base_network.subnets(new_prefix as u8).map_err(|_| Ipv4InputError::PrefixOutOfRange { min: base_network.prefix_len(), max: 32 })?))
     } else if let Some(count) = needed_subnets {
-        if count == 0 || count > 2u32.pow(32 - base_network.prefix_len() as u32) {
-            return Err(Ipv4InputError::ParseError("Too many subnets requested".into()));
+        let max_count = 1u64 << (32 - base_network.prefix_len() as u32);
+        if count == 0 {
+            return Err(Ipv4InputError::NoSubnets);
         }
-        let bits_needed = (count as f32).log2().ceil() as u8;
+        if count as u64 > max_count {
+            return Err(Ipv4InputError::TooManySubnets { requested: count, max: max_count });
+        }
+        // ceil(log2(count)) without float rounding
+        let bits_needed = (count as u64).next_power_of_two().trailing_zeros() as u8;
         let new_prefix = base_network.prefix_len() + bits_needed;
 
-        (Some(new_prefix), Box::new(base_network.subnets(new_prefix).unwrap()))
+        (Some(new_prefix), Box::new(base_network.subnets(new_prefix).map_err(|_| Ipv4InputError::PrefixOutOfRange { min: base_network.prefix_len(), max: 32 })?))
     } else {
         // Basic mode
-        (None, Box::new(base_network.subnets(base_network.prefix_len()).unwrap()))
+        (None, Box::new(std::iter::once(base_network.trunc())))
     };
 
+    let subnet_prefix = new_prefix.unwrap_or(base_network.prefix_len());
+    if subnet_prefix < min_prefix || subnet_prefix > max_prefix {
+        return Err(Ipv4InputError::PlatformPrefixLimit { platform, prefix: subnet_prefix });
+    }
+
     // Calculate total number of subnets that would be created
     let total_subnets: u64 = if let Some(np) = new_prefix {
         // For subnetting: 2^(new_prefix - base_prefix)
@@ -105,7 +126,7 @@ pub fn calculate(
     if (total_subnets as usize) <= LIMIT {
         // Total subnets fit within our limit - show all of them
         for net in iter.by_ref().take(LIMIT) {
-            subnets.push(build_subnet_result(net));
+            subnets.push(build_subnet_result(net, platform));
         }
     } else {
         // Too many subnets - show first (LIMIT - LAST_N) + last LAST_N subnets
@@ -114,14 +135,15 @@ pub fn calculate(
         // Collect first chunk of subnets
         for _ in 0..first_k {
             if let Some(net) = iter.next() {
-                subnets.push(build_subnet_result(net));
+                subnets.push(build_subnet_result(net, platform));
             }
         }
 
-        // Skip to the end and collect last chunk
+        // Skip to the end and collect last chunk (only reachable when splitting)
+        let new_prefix = new_prefix.ok_or(Ipv4InputError::PrefixOutOfRange { min: base_network.prefix_len(), max: 32 })?;
         // Calculate subnet size in addresses
-        let subnet_size: u64 = 1 << (32 - new_prefix.unwrap() as u32);
-        
+        let subnet_size: u64 = 1 << (32 - new_prefix as u32);
+
         // Calculate offset to reach the last subnets
         for k in 0..LAST_N {
             // ID of this subnet in the total sequence (1-based)
@@ -132,15 +154,359 @@ pub fn calculate(
             let start_u64 = u32::from(base_network.network()) as u64 + offset;
             let start = Ipv4Addr::from((start_u64 as u32));
             // Create the network
-            let net = Ipv4Net::new(start, new_prefix.unwrap()).unwrap();
-            subnets.push(build_subnet_result(net));
+            let net = Ipv4Net::new(start, new_prefix).map_err(|_| Ipv4InputError::PrefixOutOfRange { min: base_network.prefix_len(), max: 32 })?;
+            subnets.push(build_subnet_result(net, platform));
         }
     }
     Ok(CalculationResult {
         base_network,
-        summary: build_subnet_result(base_network),
+        summary: build_subnet_result(base_network, platform),
         subnets,
         new_prefix,
-        total_subnets: total_subnets
+        total_subnets: total_subnets,
+        requested_subnets: needed_subnets,
+        wildcard: None,
+        free_search: None,
+        host_bits,
+    })
+}
+
+/// Wildcard mode: any address + wildcard mask, contiguous or not. The base network is the
+/// smallest CIDR covering every match, and the subnets are the match set as CIDRs.
+pub fn calculate_wildcard(ip: &str, wildcard: &str, test: &str) -> Result<CalculationResult, Ipv4InputError> {
+    let address: Ipv4Addr = ip.trim()
+        .parse()
+        .map_err(|_| Ipv4InputError::InvalidAddress(ip.trim().to_string()))?;
+    let wildcard: Ipv4Addr = wildcard.trim()
+        .parse()
+        .map_err(|_| Ipv4InputError::InvalidWildcard(wildcard.trim().to_string()))?;
+    let test = match test.trim() {
+        "" => None,
+        t => Some(t.parse::<Ipv4Addr>().map_err(|_| Ipv4InputError::InvalidAddress(t.to_string()))?),
+    };
+
+    let analysis = analyze_wildcard(address, wildcard, test);
+    let covering = Ipv4Net::new(analysis.address, u32::from(wildcard).leading_zeros() as u8)
+        .map_err(|_| Ipv4InputError::InvalidWildcard(wildcard.to_string()))?
+        .trunc();
+    let subnets: Vec<SubnetResult> = analysis.cidrs.iter().flatten().map(|&net| build_subnet_result(net, PlatformProfile::Standard)).collect();
+
+    Ok(CalculationResult {
+        base_network: covering,
+        summary: build_subnet_result(covering, PlatformProfile::Standard),
+        total_subnets: subnets.len() as u64,
+        subnets,
+        new_prefix: None,
+        requested_subnets: None,
+        wildcard: Some(analysis),
+        free_search: None,
+        host_bits: None,
     })
-}
\ No newline at end of file
+}
+/// Next-free mode: the first `count` free aligned blocks of `size` (host count or "/N") inside
+/// the parent network, skipping every prefix listed in `used`
+#[allow(clippy::too_many_arguments)]
+pub fn find_free_subnets(
+    ip: &str,
+    mask_or_prefix: &str,
+    used: &str,
+    size: &str,
+    count: &str,
+    strategy: FitStrategy,
+    platform: PlatformProfile,
+    policy: HostBitsPolicy,
+) -> Result<CalculationResult, Ipv4InputError> {
+    let parsed = parse_network(ip, mask_or_prefix)?;
+    let host_bits = check_host_bits(parsed.into(), policy).map_err(|found| Ipv4InputError::HostBitsSet(Box::new(found)))?;
+    let base_network = parsed.trunc();
+    let (used, normalized) = parse_used(used, policy)?;
+    if let Some(&v6) = used.iter().find(|n| matches!(n, IpNet::V6(_))) {
+        return Err(Ipv4InputError::WrongFamily(v6));
+    }
+    let (min_prefix, max_prefix) = platform.prefix_range();
+    let mut new_prefix = parse_size(size, 32, platform.reserved() as u128)?;
+    // Host counts round up to the platform's smallest subnet, as in ByHosts mode
+    if !size.trim().starts_with('/') {
+        new_prefix = new_prefix.min(max_prefix);
+    }
+    if new_prefix < min_prefix || new_prefix > max_prefix {
+        return Err(Ipv4InputError::PlatformPrefixLimit { platform, prefix: new_prefix });
+    }
+    if new_prefix < base_network.prefix_len() {
+        return Err(Ipv4InputError::PrefixOutOfRange { min: base_network.prefix_len(), max: 32 });
+    }
+
+    let count = parse_count(count, LIMIT)?;
+    let (found, search) = free_space::search(IpNet::V4(base_network), used, normalized, new_prefix, count, strategy);
+    let subnets: Vec<SubnetResult> = found
+        .into_iter()
+        .filter_map(|net| match net {
+            IpNet::V4(net) => Some(build_subnet_result(net, platform)),
+            IpNet::V6(_) => None,
+        })
+        .collect();
+
+    Ok(CalculationResult {
+        base_network,
+        summary: build_subnet_result(base_network, platform),
+        total_subnets: subnets.len() as u64,
+        subnets,
+        new_prefix: Some(new_prefix),
+        requested_subnets: Some(count as u32),
+        wildcard: None,
+        free_search: Some(search),
+        host_bits,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::assert_golden;
+    use proptest::prelude::*;
+
+    fn render(result: &CalculationResult) -> String {
+        let mut out = format!("base {} new {:?} total {}\n", result.base_network, result.new_prefix, result.total_subnets);
+        for s in &result.subnets {
+            out += &format!(
+                "{} {} {} {}-{} {} {}\n",
+                s.network, s.netmask, s.wildcard,
+                s.first_host.as_deref().unwrap_or("-"), s.last_host.as_deref().unwrap_or("-"),
+                s.broadcast, s.usable_hosts
+            );
+        }
+        out
+    }
+
+    #[test]
+    fn parses_prefix_and_mask_notation() {
+        let expected: Ipv4Net = "192.168.1.0/24".parse().unwrap();
+        assert_eq!(parse_network("192.168.1.0", "24"), Ok(expected));
+        assert_eq!(parse_network(" 192.168.1.0 ", "/24"), Ok(expected));
+        assert_eq!(parse_network("192.168.1.0", "255.255.255.0"), Ok(expected));
+        assert_eq!(parse_network("192.168.1.0", "33"), Err(Ipv4InputError::PrefixOutOfRange { min: 0, max: 32 }));
+        assert_eq!(parse_network("192.168.1.0", "255.0.255.0"), Err(Ipv4InputError::InvalidMask("255.0.255.0".into())));
+        assert_eq!(parse_network("192.168.1", "24"), Err(Ipv4InputError::InvalidAddress("192.168.1".into())));
+        assert_eq!(parse_network("192.168.1.0", "abc"), Err(Ipv4InputError::InvalidMask("abc".into())));
+    }
+
+    #[test]
+    fn sizes_subnets_by_hosts_and_count() {
+        let by_hosts = calculate("192.168.1.0", "24", Some(50), None, PlatformProfile::Standard, HostBitsPolicy::Normalize).unwrap();
+        assert_eq!(by_hosts.new_prefix, Some(26));
+        assert_eq!(by_hosts.total_subnets, 4);
+        assert_eq!(by_hosts.subnets[0].usable_hosts, 62);
+
+        let by_count = calculate("192.168.1.0", "24", None, Some(5), PlatformProfile::Standard, HostBitsPolicy::Normalize).unwrap();
+        assert_eq!(by_count.new_prefix, Some(27));
+        assert_eq!(by_count.total_subnets, 8);
+        assert_eq!(by_count.requested_subnets, Some(5));
+
+        let inspect = calculate("192.168.1.77", "24", None, None, PlatformProfile::Standard, HostBitsPolicy::Normalize).unwrap();
+        assert_eq!(inspect.total_subnets, 1);
+        assert_eq!(inspect.summary.usable_hosts, 254);
+        assert_eq!(inspect.summary.first_host.as_deref(), Some("192.168.1.1"));
+        assert_eq!(inspect.summary.broadcast, "192.168.1.255");
+        let found = inspect.host_bits.unwrap();
+        assert_eq!((found.network.to_string().as_str(), found.host_index), ("192.168.1.0/24", Some(77)));
+        assert_eq!(by_hosts.host_bits, None);
+
+        let strict = calculate("192.168.1.77", "24", None, None, PlatformProfile::Standard, HostBitsPolicy::Reject).unwrap_err();
+        assert_eq!(strict, Ipv4InputError::HostBitsSet(Box::new(found)));
+        assert_eq!(strict.suggestion().as_deref(), Some("Did you mean 192.168.1.0/24?"));
+    }
+
+    #[test]
+    fn too_many_hosts_hint_follows_the_platform() {
+        let platforms = [(PlatformProfile::Standard, 23), (PlatformProfile::Aws, 22), (PlatformProfile::Azure, 22), (PlatformProfile::Gcp, 22)];
+        for (platform, parent) in platforms {
+            let err = calculate("10.0.0.0", "24", Some(510), None, platform, HostBitsPolicy::Normalize).unwrap_err();
+            assert_eq!(err.suggestion(), Some(format!("510 hosts need a /{} or larger parent", parent)), "{:?}", platform);
+            let fits = calculate("10.0.0.0", &parent.to_string(), Some(510), None, platform, HostBitsPolicy::Normalize).unwrap();
+            assert_eq!(fits.new_prefix, Some(parent), "{:?}", platform);
+        }
+    }
+
+    #[test]
+    fn errors_explain_and_suggest() {
+        let short = parse_network("10.1", "16").unwrap_err();
+        assert_eq!(short.suggestion().as_deref(), Some("Did you mean 10.1.0.0?"));
+        let cidr = parse_network("10.0.0.0/8", "").unwrap_err();
+        assert_eq!(cidr.suggestion().as_deref(), Some("Put the prefix in the mask field: 10.0.0.0 and /8"));
+        let wildcard = parse_network("10.0.0.0", "0.0.0.255").unwrap_err();
+        assert_eq!(wildcard.suggestion().as_deref(), Some("0.0.0.255 looks like a wildcard mask; did you mean /24?"));
+
+        let hosts = calculate("192.168.1.0", "24", Some(500), None, PlatformProfile::Standard, HostBitsPolicy::Normalize).unwrap_err();
+        assert_eq!(hosts, Ipv4InputError::TooManyHosts { requested: 500, max: 254, platform: PlatformProfile::Standard });
+        assert_eq!(hosts.to_string(), "500 hosts requested but the network holds at most 254");
+        assert_eq!(hosts.suggestion().as_deref(), Some("500 hosts need a /23 or larger parent"));
+
+        let subnets = calculate("192.168.1.0", "30", None, Some(5), PlatformProfile::Standard, HostBitsPolicy::Normalize).unwrap_err();
+        assert_eq!(subnets, Ipv4InputError::TooManySubnets { requested: 5, max: 4 });
+        assert_eq!(calculate("192.168.1.0", "24", None, Some(0), PlatformProfile::Standard, HostBitsPolicy::Normalize), Err(Ipv4InputError::NoSubnets));
+
+        let used = find_free_subnets("10.0.0.0", "24", "10.0.0.0/26\nbogus", "/26", "1", FitStrategy::First, PlatformProfile::Standard, HostBitsPolicy::Normalize).unwrap_err();
+        assert_eq!(used.to_string(), "Line 2: 'bogus' is not a prefix or address");
+        assert!(std::error::Error::source(&used).is_some());
+    }
+
+    #[test]
+    fn whole_address_space_is_exact() {
+        let all = calculate("0.0.0.0", "0", None, None, PlatformProfile::Standard, HostBitsPolicy::Normalize).unwrap();
+        assert_eq!(all.summary.usable_hosts, (1u64 << 32) - 2);
+
+        let half = calculate("0.0.0.0", "0", Some(1 << 31), None, PlatformProfile::Standard, HostBitsPolicy::Normalize).unwrap();
+        assert_eq!(half.new_prefix, Some(0));
+        assert_eq!(half.subnets[0].usable_hosts, (1u64 << 32) - 2);
+        assert!(calculate("0.0.0.0", "0", Some(u32::MAX), None, PlatformProfile::Standard, HostBitsPolicy::Normalize).is_err());
+
+        let hosts = calculate("0.0.0.0", "0", None, Some(u32::MAX), PlatformProfile::Standard, HostBitsPolicy::Normalize).unwrap();
+        assert_eq!(hosts.new_prefix, Some(32));
+        assert_eq!(hosts.total_subnets, 1u64 << 32);
+        assert_eq!(hosts.subnets.last().unwrap().network.to_string(), "255.255.255.255/32");
+    }
+
+    #[test]
+    fn point_to_point_and_host_routes() {
+        let p2p = calculate("10.0.0.0", "31", None, None, PlatformProfile::Standard, HostBitsPolicy::Normalize).unwrap();
+        assert_eq!(p2p.summary.first_host.as_deref(), Some("10.0.0.0"));
+        assert_eq!(p2p.summary.last_host.as_deref(), Some("10.0.0.1"));
+        let host = calculate("10.0.0.9", "32", None, None, PlatformProfile::Standard, HostBitsPolicy::Normalize).unwrap();
+        assert_eq!(host.summary.first_host.as_deref(), Some("10.0.0.9"));
+    }
+
+    #[test]
+    fn applies_platform_reservations_and_limits() {
+        let aws = calculate("10.0.0.0", "24", Some(20), None, PlatformProfile::Aws, HostBitsPolicy::Normalize).unwrap();
+        assert_eq!(aws.new_prefix, Some(27));
+        assert_eq!(aws.subnets[0].usable_hosts, 27);
+        assert_eq!(aws.subnets[0].first_host.as_deref(), Some("10.0.0.4"));
+        assert_eq!(aws.subnets[0].last_host.as_deref(), Some("10.0.0.30"));
+
+        // One host still needs a /28 on AWS
+        let tiny = calculate("10.0.0.0", "24", Some(1), None, PlatformProfile::Aws, HostBitsPolicy::Normalize).unwrap();
+        assert_eq!(tiny.new_prefix, Some(28));
+
+        assert!(matches!(
+            calculate("10.0.0.0", "8", None, None, PlatformProfile::Aws, HostBitsPolicy::Normalize),
+            Err(Ipv4InputError::PlatformPrefixLimit { prefix: 8, .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_impossible_requests() {
+        assert!(calculate("192.168.1.0", "24", Some(300), None, PlatformProfile::Standard, HostBitsPolicy::Normalize).is_err());
+        assert!(calculate("192.168.1.0", "24", None, Some(0), PlatformProfile::Standard, HostBitsPolicy::Normalize).is_err());
+        assert!(calculate("192.168.1.0", "24", None, Some(257), PlatformProfile::Standard, HostBitsPolicy::Normalize).is_err());
+    }
+
+    #[test]
+    fn truncates_long_listings_but_keeps_the_tail() {
+        let result = calculate("10.0.0.0", "8", None, Some(65536), PlatformProfile::Standard, HostBitsPolicy::Normalize).unwrap();
+        assert_eq!(result.total_subnets, 65536);
+        assert_eq!(result.subnets.len(), LIMIT);
+        assert_eq!(result.subnets.last().unwrap().network, "10.255.255.0/24".parse::<Ipv4Net>().unwrap());
+    }
+
+    #[test]
+    fn wildcard_mode_lists_the_match_set() {
+        let result = calculate_wildcard("10.0.0.0", "0.0.1.1", "10.0.1.1").unwrap();
+        // 10.0.0.0/31 and 10.0.1.0/31
+        assert_eq!(result.total_subnets, 2);
+        assert_eq!(result.wildcard.as_ref().unwrap().matching, 4);
+        assert_eq!(result.wildcard.as_ref().unwrap().test, Some(("10.0.1.1".parse().unwrap(), true)));
+        assert!(calculate_wildcard("10.0.0.0", "0.0.1", "").is_err());
+    }
+
+    #[test]
+    fn next_free_skips_used_prefixes() {
+        let result = find_free_subnets("10.0.0.0", "24", "10.0.0.0/26, 10.0.0.128/27", "/26", "2", FitStrategy::First, PlatformProfile::Standard, HostBitsPolicy::Normalize).unwrap();
+        let found: Vec<String> = result.subnets.iter().map(|s| s.network.to_string()).collect();
+        assert_eq!(found, ["10.0.0.64/26", "10.0.0.192/26"]);
+        assert!(find_free_subnets("10.0.0.0", "24", "2001:db8::/64", "/26", "1", FitStrategy::First, PlatformProfile::Standard, HostBitsPolicy::Normalize).is_err());
+        assert!(find_free_subnets("10.0.0.0", "24", "", "/23", "1", FitStrategy::First, PlatformProfile::Standard, HostBitsPolicy::Normalize).is_err());
+    }
+
+    #[test]
+    fn golden_outputs() {
+        let plain = calculate("192.168.1.0", "24", Some(50), None, PlatformProfile::Standard, HostBitsPolicy::Normalize).unwrap();
+        assert_golden("ipv4_by_hosts.txt", &render(&plain));
+        let azure = calculate("10.20.0.0", "24", None, Some(6), PlatformProfile::Azure, HostBitsPolicy::Normalize).unwrap();
+        assert_golden("ipv4_azure_by_count.txt", &render(&azure));
+        let wildcard = calculate_wildcard("10.0.0.0", "0.0.1.1", "").unwrap();
+        assert_golden("ipv4_wildcard.txt", &render(&wildcard));
+    }
+
+    proptest! {
+        #[test]
+        fn subnets_tile_the_base_network(addr in any::<u32>(), base in 0u8..=30, extra in 0u8..=31) {
+            let new = (base + extra).min(32);
+            let count = 1u32 << (new - base);
+            let result = calculate(&Ipv4Addr::from(addr).to_string(), &base.to_string(), None, Some(count), PlatformProfile::Standard, HostBitsPolicy::Normalize).unwrap();
+            let parent = Ipv4Net::new(Ipv4Addr::from(addr), base).unwrap().trunc();
+
+            prop_assert_eq!(result.total_subnets, 1u64 << (new - base));
+            prop_assert_eq!(result.subnets.len() as u64, result.total_subnets.min(LIMIT as u64));
+            for s in &result.subnets {
+                prop_assert!(parent.contains(&s.network));
+                prop_assert_eq!(s.network.prefix_len(), new);
+            }
+            // Disjoint and contiguous: each subnet starts right after the previous one, except
+            // at the gap left by truncation
+            let size = 1u64 << (32 - new);
+            for (i, pair) in result.subnets.windows(2).enumerate() {
+                let gap = u32::from(pair[1].network.network()) as u64 - u32::from(pair[0].network.network()) as u64;
+                if i + 1 != LIMIT - LAST_N {
+                    prop_assert_eq!(gap, size);
+                } else {
+                    prop_assert!(gap >= size);
+                }
+            }
+            prop_assert_eq!(result.subnets[0].network.network(), parent.network());
+            prop_assert_eq!(result.subnets.last().unwrap().network.broadcast(), parent.broadcast());
+        }
+
+        #[test]
+        fn tail_matches_the_iterator(addr in any::<u32>(), base in 0u8..=16, extra in 13u8..=16) {
+            let new = (base + extra).min(32);
+            let result = calculate(&Ipv4Addr::from(addr).to_string(), &base.to_string(), None, Some(1 << (new - base)), PlatformProfile::Standard, HostBitsPolicy::Normalize).unwrap();
+            let parent = Ipv4Net::new(Ipv4Addr::from(addr), base).unwrap().trunc();
+            let all: Vec<Ipv4Net> = parent.subnets(new).unwrap().collect();
+            let shown: Vec<Ipv4Net> = result.subnets.iter().map(|s| s.network).collect();
+            if all.len() <= LIMIT {
+                prop_assert_eq!(shown, all);
+            } else {
+                prop_assert_eq!(&shown[..LIMIT - LAST_N], &all[..LIMIT - LAST_N]);
+                prop_assert_eq!(&shown[LIMIT - LAST_N..], &all[all.len() - LAST_N..]);
+            }
+        }
+
+        #[test]
+        fn host_sizing_is_minimal(base in 0u8..=30, hosts in 1u32..) {
+            if let Ok(result) = calculate("10.0.0.0", &base.to_string(), Some(hosts), None, PlatformProfile::Standard, HostBitsPolicy::Normalize) {
+                let usable = result.subnets[0].usable_hosts;
+                prop_assert!(usable >= hosts as u64 || result.new_prefix == Some(32));
+                // One bit larger would have been too small
+                let new = result.new_prefix.unwrap();
+                if new < 31 {
+                    prop_assert!((1u64 << (32 - new - 1)).saturating_sub(2) < hosts as u64);
+                }
+            } else {
+                prop_assert!(hosts as u64 > (1u64 << (32 - base)) - 2);
+            }
+        }
+
+        #[test]
+        fn count_sizing_is_minimal(base in 0u8..=31, count in 1u32..) {
+            let available = 1u64 << (32 - base);
+            match calculate("0.0.0.0", &base.to_string(), None, Some(count), PlatformProfile::Standard, HostBitsPolicy::Normalize) {
+                Ok(result) => {
+                    let bits = result.new_prefix.unwrap() - base;
+                    prop_assert!(1u64 << bits >= count as u64);
+                    prop_assert!(bits == 0 || 1u64 << (bits - 1) < count as u64);
+                }
+                Err(_) => prop_assert!(count as u64 > available),
+            }
+        }
+    }
+}
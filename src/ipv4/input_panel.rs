@@ -1,7 +1,10 @@
 // src/ipv4/input_panel.rs
 use dioxus::prelude::*;
 
-use crate::ipv4::{calculator::calculate, types::{CIDR_OPTIONS, CalculationResult, Ipv4InputError, SubnetMode}};
+use crate::address_math::HostBitsPolicy;
+use crate::components::{FreeSearchFields, HostBitsToggle};
+use crate::free_space::FitStrategy;
+use crate::ipv4::{calculator::{calculate, calculate_wildcard, find_free_subnets}, types::{CIDR_OPTIONS, CalculationResult, Ipv4InputError, PlatformProfile, SubnetMode}};
 
 
 
@@ -11,14 +14,23 @@ pub fn InputPanel(
     cidr_input: Signal<String>,
     mode: Signal<SubnetMode>,
     count_input: Signal<String>,
+    wildcard_input: Signal<String>,
+    test_input: Signal<String>,
+    platform: Signal<PlatformProfile>,
+    used_input: Signal<String>,
+    size_input: Signal<String>,
+    strategy: Signal<FitStrategy>,
+    host_bits_policy: Signal<HostBitsPolicy>,
     result: Signal<Option<Result<CalculationResult, Ipv4InputError>>>,
 ) -> Element {
     let current_mode = mode.read().clone();
     let count_is_valid = count_input.read().trim().parse::<u32>().is_ok_and(|n| n >= 1);
     let show_error = matches!(current_mode, SubnetMode::ByHosts | SubnetMode::BySubnets) && !count_is_valid;
     
-    let is_disabled = if *mode.read() == SubnetMode::Inspect {
+    let is_disabled = if matches!(*mode.read(), SubnetMode::Inspect | SubnetMode::Wildcard) {
         false
+    } else if *mode.read() == SubnetMode::NextFree {
+        size_input.read().trim().is_empty()
     } else {
         count_input.read().trim().is_empty() || count_input.read().parse::<u32>().is_err()
     };
@@ -30,7 +42,7 @@ pub fn InputPanel(
     };
 
     rsx! {
-        div { class: "w-full h-150  bg-gray-800 rounded-lg shadow-lg p-6 flex flex-col",
+        div { class: "w-full h-150 overflow-y-auto bg-gray-800 rounded-lg shadow-lg p-6 flex flex-col",
             h2 { class: "text-2xl font-bold mb-6 text-center", "Enter IPv4 Network Information" }
 
             // IP Input
@@ -45,28 +57,42 @@ pub fn InputPanel(
                 }
             }
 
-            // CIDR or Subnet Mask Input
-            div { class: "mb-6",
-                label { class: "block text-left text-sm font-medium  mb-2", "Subnet Mask" }
-                select {
-                    class: "w-full px-4 py-3 pr-10  border border-gray-600 rounded-lg focus:ring-2 focus:ring-blue-500 focus:border-transparent appearance-none",
-                    style: "background-image: url(\"data:image/svg+xml,%3csvg xmlns='http://www.w3.org/2000/svg' fill='none' viewBox='0 0 20 20'%3e%3cpath stroke='%236b7280' stroke-linecap='round' stroke-linejoin='round' stroke-width='1.5' d='m6 8 4 4 4-4'/%3e%3c/svg%3e\"); background-position: right 0.75rem center; background-repeat: no-repeat; background-size: 1.5em;",
+            // Wildcard mode takes any ACL wildcard instead of a netmask
+            if *mode.read() == SubnetMode::Wildcard {
+                div { class: "mb-6",
+                    label { class: "block text-left text-sm font-medium  mb-2", "Wildcard Mask" }
+                    input {
+                        class: "w-full px-4 py-3 border border-gray-600 rounded-lg focus:ring-2 focus:ring-blue-500 focus:border-transparent",
+                        r#type: "text",
+                        placeholder: "e.g. 0.0.254.255",
+                        value: "{wildcard_input}",
+                        oninput: move |evt| wildcard_input.set(evt.value())
+                    }
+                }
+            } else {
+                // CIDR or Subnet Mask Input
+                div { class: "mb-6",
+                    label { class: "block text-left text-sm font-medium  mb-2", "Subnet Mask" }
+                    select {
+                        class: "w-full px-4 py-3 pr-10  border border-gray-600 rounded-lg focus:ring-2 focus:ring-blue-500 focus:border-transparent appearance-none",
+                        style: "background-image: url(\"data:image/svg+xml,%3csvg xmlns='http://www.w3.org/2000/svg' fill='none' viewBox='0 0 20 20'%3e%3cpath stroke='%236b7280' stroke-linecap='round' stroke-linejoin='round' stroke-width='1.5' d='m6 8 4 4 4-4'/%3e%3c/svg%3e\"); background-position: right 0.75rem center; background-repeat: no-repeat; background-size: 1.5em;",
 
-                    // Control the select with the current signal value
-                    value: "{cidr_input}",
+                        // Control the select with the current signal value
+                        value: "{cidr_input}",
 
-                    onchange: move |evt| cidr_input.set(evt.value()),
+                        onchange: move |evt| cidr_input.set(evt.value()),
 
-                    { CIDR_OPTIONS.iter().map(|&(_prefix, cidr, mask)| {
-                        let is_selected = *cidr_input.read() == *cidr;
-                        rsx! {
-                            option {
-                                value: "{cidr}",
-                                selected: is_selected,
-                                "{cidr} — {mask}"
+                        { CIDR_OPTIONS.iter().map(|&(_prefix, cidr, mask)| {
+                            let is_selected = *cidr_input.read() == *cidr;
+                            rsx! {
+                                option {
+                                    value: "{cidr}",
+                                    selected: is_selected,
+                                    "{cidr} — {mask}"
+                                }
                             }
-                        }
-                    })}
+                        })}
+                    }
                 }
             }
             // Subnet Mode Selector (placeholder for now)
@@ -80,6 +106,8 @@ pub fn InputPanel(
                         mode.set(match val.as_str() {
                             "hosts" => SubnetMode::ByHosts,
                             "subnets" => SubnetMode::BySubnets,
+                            "wildcard" => SubnetMode::Wildcard,
+                            "next-free" => SubnetMode::NextFree,
                             _ => SubnetMode::Inspect,
                         });
                     },
@@ -87,11 +115,56 @@ pub fn InputPanel(
                     option { value: "inspect", selected: *mode.read() == SubnetMode::Inspect, "Inspect given subnet" }
                     option { value: "hosts", selected: *mode.read() == SubnetMode::ByHosts, "Number of hosts" }
                     option { value: "subnets", selected: *mode.read() == SubnetMode::BySubnets, "Number of subnets" }
+                    option { value: "wildcard", selected: *mode.read() == SubnetMode::Wildcard, "Wildcard mask analysis" }
+                    option { value: "next-free", selected: *mode.read() == SubnetMode::NextFree, "Next free subnet" }
+                }
+            }
+
+            // Cloud providers reserve extra addresses and limit subnet sizes
+            if *mode.read() != SubnetMode::Wildcard {
+                div { class: "mb-6",
+                    label { class: "block text-left text-sm font-medium  mb-2", "Platform" }
+                    select {
+                        class: "block w-60 mx-0 px-4 py-3 border border-gray-600 rounded-lg bg-gray-700",
+                        onchange: move |e| platform.set(PlatformProfile::from_key(&e.value())),
+                        for p in PlatformProfile::ALL {
+                            option { value: p.key(), selected: platform() == p, "{p.label()}" }
+                        }
+                    }
+                }
+            }
+
+            // A wildcard address is expected to have bits under the mask set
+            if *mode.read() != SubnetMode::Wildcard {
+                HostBitsToggle { policy: host_bits_policy }
+            }
+
+            if *mode.read() == SubnetMode::Wildcard {
+                div { class: "mb-4",
+                    label { class: "block text-left text-sm font-medium  mb-2", "Test Address (optional)" }
+                    input {
+                        class: "w-full px-4 py-3 border border-gray-600 rounded-lg bg-gray-700",
+                        r#type: "text",
+                        placeholder: "e.g. 10.0.4.17",
+                        value: "{test_input}",
+                        oninput: move |e| test_input.set(e.value())
+                    }
+                }
+            }
+
+            if *mode.read() == SubnetMode::NextFree {
+                FreeSearchFields {
+                    used_input,
+                    size_input,
+                    count_input,
+                    strategy,
+                    used_placeholder: "10.0.0.0/24\n10.0.2.0/23",
+                    size_placeholder: "e.g. /26 or 50",
                 }
             }
 
             // Number of Hosts or Subnets field
-            if *mode.read() != SubnetMode::Inspect{
+            if matches!(*mode.read(), SubnetMode::ByHosts | SubnetMode::BySubnets) {
                 div { class: "mb-4",
                     label { class: "block text-sm font-medium  mb-2",
                         if *mode.read() == SubnetMode::ByHosts { "Number of Hosts Needed" } else { "Number of Subnets Needed" }
@@ -116,6 +189,14 @@ pub fn InputPanel(
                     disabled: is_disabled,
                     onclick: move |_| {
                         if is_disabled { return; }
+                        if *mode.read() == SubnetMode::Wildcard {
+                            result.set(Some(calculate_wildcard(&ip_input(), &wildcard_input(), &test_input())));
+                            return;
+                        }
+                        if *mode.read() == SubnetMode::NextFree {
+                            result.set(Some(find_free_subnets(&ip_input(), &cidr_input(), &used_input(), &size_input(), &count_input(), strategy(), platform(), host_bits_policy())));
+                            return;
+                        }
                         let hosts = if *mode.read() == SubnetMode::ByHosts {
                             count_input.read().parse().ok()
                         } else { None };
@@ -123,7 +204,7 @@ pub fn InputPanel(
                             count_input.read().parse().ok()
                         } else { None };
 
-                        let res = calculate(&ip_input(), &cidr_input(), hosts, subnets);
+                        let res = calculate(&ip_input(), &cidr_input(), hosts, subnets, platform(), host_bits_policy());
                         result.set(Some(res));
                     },
                     "Calculate"
@@ -134,6 +215,13 @@ pub fn InputPanel(
                         ip_input.set("192.168.1.0".to_string());
                         mode.set(SubnetMode::Inspect);
                         count_input.set("".to_string());
+                        wildcard_input.set("0.0.0.255".to_string());
+                        test_input.set("".to_string());
+                        platform.set(PlatformProfile::Standard);
+                        used_input.set("".to_string());
+                        size_input.set("".to_string());
+                        strategy.set(FitStrategy::First);
+                        host_bits_policy.set(HostBitsPolicy::Normalize);
                         result.set(None);
                     },
                     "Clear"
@@ -3,9 +3,13 @@ pub mod calculator;
 pub mod types;
 pub mod input_panel;
 pub mod results_panel;
+pub mod multicast;
+pub mod wildcard;
 
 use dioxus::prelude::*;
-use crate::ipv4::types::{CalculationResult, Ipv4InputError, SubnetMode};
+use crate::address_math::HostBitsPolicy;
+use crate::free_space::FitStrategy;
+use crate::ipv4::types::{CalculationResult, Ipv4InputError, PlatformProfile, SubnetMode};
 use crate::ipv4::input_panel::{InputPanel};
 use crate::ipv4::results_panel::ResultsPanel;
 
@@ -15,6 +19,13 @@ pub fn Ipv4Tab() -> Element {
     let cidr_input = use_signal(|| "/24".to_string());
     let mode = use_signal(|| SubnetMode::Inspect);
     let count_input = use_signal(|| "".to_string());
+    let wildcard_input = use_signal(|| "0.0.0.255".to_string());
+    let test_input = use_signal(|| "".to_string());
+    let platform = use_signal(|| PlatformProfile::Standard);
+    let used_input = use_signal(|| "".to_string());
+    let size_input = use_signal(|| "".to_string());
+    let strategy = use_signal(|| FitStrategy::First);
+    let host_bits_policy = use_signal(HostBitsPolicy::default);
     let result = use_signal(|| None::<Result<CalculationResult, Ipv4InputError>>);
 
 
@@ -26,6 +37,13 @@ pub fn Ipv4Tab() -> Element {
                     cidr_input,
                     mode,
                     count_input,
+                    wildcard_input,
+                    test_input,
+                    platform,
+                    used_input,
+                    size_input,
+                    strategy,
+                    host_bits_policy,
                     result
                 }
             
@@ -0,0 +1,138 @@
+// src/ipv4/multicast.rs
+use ipnet::Ipv4Net;
+use std::net::Ipv4Addr;
+use crate::ipv4::types::MulticastInfo;
+
+// IANA IPv4 Multicast Address Space Registry (RFC 5771) and RFC 2365 scopes;
+// the most specific matching block wins
+const BLOCKS: &[(Ipv4Addr, u8, &str, &str, &str)] = &[
+    (Ipv4Addr::new(224, 0, 0, 0), 4, "Reserved", "RFC 5771", "Global"),
+    (Ipv4Addr::new(224, 0, 0, 0), 24, "Local Network Control Block", "RFC 5771", "Link-local (TTL 1, never forwarded)"),
+    (Ipv4Addr::new(224, 0, 1, 0), 24, "Internetwork Control Block", "RFC 5771", "Global"),
+    (Ipv4Addr::new(224, 0, 2, 0), 23, "AD-HOC Block I", "RFC 5771", "Global"),
+    (Ipv4Addr::new(224, 0, 4, 0), 22, "AD-HOC Block I", "RFC 5771", "Global"),
+    (Ipv4Addr::new(224, 0, 8, 0), 21, "AD-HOC Block I", "RFC 5771", "Global"),
+    (Ipv4Addr::new(224, 0, 16, 0), 20, "AD-HOC Block I", "RFC 5771", "Global"),
+    (Ipv4Addr::new(224, 0, 32, 0), 19, "AD-HOC Block I", "RFC 5771", "Global"),
+    (Ipv4Addr::new(224, 0, 64, 0), 18, "AD-HOC Block I", "RFC 5771", "Global"),
+    (Ipv4Addr::new(224, 0, 128, 0), 17, "AD-HOC Block I", "RFC 5771", "Global"),
+    (Ipv4Addr::new(224, 1, 0, 0), 16, "Reserved", "RFC 5771", "Global"),
+    (Ipv4Addr::new(224, 2, 0, 0), 16, "SDP/SAP Block", "RFC 2974", "Global"),
+    (Ipv4Addr::new(224, 3, 0, 0), 16, "AD-HOC Block II", "RFC 5771", "Global"),
+    (Ipv4Addr::new(224, 4, 0, 0), 16, "AD-HOC Block II", "RFC 5771", "Global"),
+    (Ipv4Addr::new(224, 252, 0, 0), 14, "DIS Transient Groups", "RFC 5771", "Global"),
+    (Ipv4Addr::new(232, 0, 0, 0), 8, "Source-Specific Multicast Block", "RFC 4607", "Global (SSM, (S,G) only)"),
+    (Ipv4Addr::new(233, 0, 0, 0), 8, "GLOP Block", "RFC 3180", "Global"),
+    (Ipv4Addr::new(233, 252, 0, 0), 14, "AD-HOC Block III", "RFC 5771", "Global"),
+    (Ipv4Addr::new(234, 0, 0, 0), 8, "Unicast-Prefix-based IPv4 Multicast", "RFC 6034", "Global"),
+    (Ipv4Addr::new(239, 0, 0, 0), 8, "Administratively Scoped Block", "RFC 2365", "Administrative"),
+    (Ipv4Addr::new(239, 192, 0, 0), 14, "Organization-Local Scope", "RFC 2365", "Organization-local"),
+    (Ipv4Addr::new(239, 255, 0, 0), 16, "IPv4 Local Scope", "RFC 2365", "Site-local"),
+];
+
+const WELL_KNOWN: &[(Ipv4Addr, &str)] = &[
+    (Ipv4Addr::new(224, 0, 0, 1), "All Systems"),
+    (Ipv4Addr::new(224, 0, 0, 2), "All Routers"),
+    (Ipv4Addr::new(224, 0, 0, 5), "OSPF AllSPFRouters"),
+    (Ipv4Addr::new(224, 0, 0, 6), "OSPF AllDRouters"),
+    (Ipv4Addr::new(224, 0, 0, 9), "RIPv2 Routers"),
+    (Ipv4Addr::new(224, 0, 0, 10), "EIGRP Routers"),
+    (Ipv4Addr::new(224, 0, 0, 13), "All PIM Routers"),
+    (Ipv4Addr::new(224, 0, 0, 18), "VRRP"),
+    (Ipv4Addr::new(224, 0, 0, 22), "IGMPv3 Reports"),
+    (Ipv4Addr::new(224, 0, 0, 102), "HSRPv2 / GLBP"),
+    (Ipv4Addr::new(224, 0, 0, 251), "mDNS"),
+    (Ipv4Addr::new(224, 0, 0, 252), "LLMNR"),
+    (Ipv4Addr::new(224, 0, 1, 1), "NTP"),
+    (Ipv4Addr::new(224, 0, 1, 39), "Cisco RP Announce"),
+    (Ipv4Addr::new(224, 0, 1, 40), "Cisco RP Discovery"),
+    (Ipv4Addr::new(224, 0, 1, 129), "PTP Primary"),
+    (Ipv4Addr::new(239, 255, 255, 250), "SSDP"),
+];
+
+/// 01:00:5e followed by the low 23 bits of the group (RFC 1112)
+pub fn multicast_mac(addr: Ipv4Addr) -> String {
+    let o = addr.octets();
+    format!("01:00:5e:{:02x}:{:02x}:{:02x}", o[1] & 0x7f, o[2], o[3])
+}
+
+/// The 32 groups that map to the same MAC: the 5 high-order bits dropped by the mapping, varied
+pub fn overlapping_groups(addr: Ipv4Addr) -> Vec<Ipv4Addr> {
+    let low23 = u32::from(addr) & 0x007f_ffff;
+    (0..32u32)
+        .map(|dropped| Ipv4Addr::from(0xe000_0000 | (dropped << 23) | low23))
+        .collect()
+}
+
+/// Classify a 224.0.0.0/4 address; `None` for anything else
+pub fn decode_multicast(addr: Ipv4Addr) -> Option<MulticastInfo> {
+    if !addr.is_multicast() {
+        return None;
+    }
+    let (block, block_name, rfc, scope) = BLOCKS
+        .iter()
+        .map(|&(net, len, name, rfc, scope)| (Ipv4Net::new(net, len).unwrap(), name, rfc, scope))
+        .filter(|(net, ..)| net.contains(&addr))
+        .max_by_key(|(net, ..)| net.prefix_len())
+        .unwrap();
+    let o = addr.octets();
+
+    Some(MulticastInfo {
+        block,
+        block_name,
+        rfc,
+        scope,
+        well_known: WELL_KNOWN.iter().find(|(group, _)| *group == addr).map(|(_, name)| *name),
+        // GLOP: 233.X.Y.0/24 belongs to 16-bit AS number X*256+Y
+        glop_asn: (block_name == "GLOP Block").then(|| u16::from_be_bytes([o[1], o[2]])),
+        // RFC 6034: 234.A.B.C is owned by the holder of unicast A.B.C.0/24
+        unicast_prefix: (o[0] == 234).then(|| Ipv4Net::new(Ipv4Addr::new(o[1], o[2], o[3], 0), 24).unwrap()),
+        mac: multicast_mac(addr),
+        overlapping: overlapping_groups(addr),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(s: &str) -> MulticastInfo {
+        decode_multicast(s.parse().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn ignores_unicast() {
+        assert_eq!(decode_multicast(Ipv4Addr::new(10, 0, 0, 1)), None);
+        assert_eq!(decode_multicast(Ipv4Addr::new(240, 0, 0, 1)), None);
+    }
+
+    #[test]
+    fn picks_the_most_specific_block() {
+        let ospf = decode("224.0.0.5");
+        assert_eq!(ospf.block_name, "Local Network Control Block");
+        assert_eq!(ospf.well_known, Some("OSPF AllSPFRouters"));
+        assert_eq!(decode("224.3.1.1").block_name, "AD-HOC Block II");
+        assert_eq!(decode("239.255.255.250").well_known, Some("SSDP"));
+        assert_eq!(decode("239.192.0.1").scope, "Organization-local");
+        assert_eq!(decode("233.252.0.1").block_name, "AD-HOC Block III");
+        assert_eq!(decode("225.1.1.1").block_name, "Reserved");
+    }
+
+    #[test]
+    fn decodes_glop_and_unicast_prefix_groups() {
+        assert_eq!(decode("233.22.30.1").glop_asn, Some(5662));
+        // AS 65000 would land in 233.252.0.0/14, which is AD-HOC III rather than GLOP
+        assert_eq!(decode("233.253.232.1").glop_asn, None);
+        assert_eq!(decode("234.192.0.2").unicast_prefix, Some("192.0.2.0/24".parse().unwrap()));
+        assert_eq!(decode("232.1.1.1").glop_asn, None);
+    }
+
+    #[test]
+    fn maps_groups_to_mac_addresses() {
+        let info = decode("239.129.1.1");
+        assert_eq!(info.mac, "01:00:5e:01:01:01");
+        assert_eq!(info.overlapping.len(), 32);
+        assert!(info.overlapping.contains(&Ipv4Addr::new(224, 1, 1, 1)));
+        assert!(info.overlapping.iter().all(|g| multicast_mac(*g) == info.mac));
+    }
+}
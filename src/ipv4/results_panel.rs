@@ -1,6 +1,11 @@
 // src/ipv4/results_panel.rs
 use dioxus::prelude::*;
-use crate::ipv4::{calculator::{LAST_N, LIMIT}, types::{CalculationResult, Ipv4InputError, SubnetResult}};
+use crate::util::get_tab_grid_class;
+use crate::components::{AddressArithmetic, FreeSearchSummary, HostBitsWarning, SplitMap};
+use crate::dhcp::DhcpPanel;
+use crate::iac::{types::IacSubnet, IacPanel};
+use crate::router_config::{types::ConfigSubnet, RouterConfigPanel};
+use crate::ipv4::{calculator::{LAST_N, LIMIT}, multicast::decode_multicast, types::{CalculationResult, Ipv4InputError, MulticastInfo, SubnetResult, WildcardAnalysis}};
 
 
 fn get_tab_class(is_active: bool) -> &'static str {
@@ -13,7 +18,7 @@ fn get_tab_class(is_active: bool) -> &'static str {
 
 #[component]
 pub fn ResultsPanel(result: Option<Result<CalculationResult, Ipv4InputError>>) -> Element {
-    let mut active_tab = use_signal(|| 0); // 0 = Summary, 1 = Subnets
+    let mut active_tab = use_signal(|| 0); // 0 = Summary, 1 = Subnets, 2 = Map, 3 = Config, 4 = DHCP, 5 = IaC
 
     rsx! {
         div { class: "h-150 bg-gray-800 rounded-lg shadow-lg p-6 overflow-auto",
@@ -24,14 +29,27 @@ pub fn ResultsPanel(result: Option<Result<CalculationResult, Ipv4InputError>>) -
                 Some(Err(err)) => rsx! { ErrorMessage { err } },
                 Some(Ok(calc)) => {
                     let has_subnets = !calc.subnets.is_empty();
+                    let has_map = calc.new_prefix.is_some() && calc.free_search.is_none();
+                    let available = [true, has_subnets, has_map, has_subnets, has_subnets, has_subnets];
+
+                    // Fall back to the summary when the selected tab doesn't apply to this result
+                    let tab = if available.get(*active_tab.read()).copied().unwrap_or(false) { *active_tab.read() } else { 0 };
 
                     // Compute classes outside rsx!
-                    let summary_tab_class = get_tab_class(*active_tab.read() == 0);
-                    let subnets_tab_class = get_tab_class(*active_tab.read() == 1);
+                    let summary_tab_class = get_tab_class(tab == 0);
+                    let subnets_tab_class = get_tab_class(tab == 1);
+                    let map_tab_class = get_tab_class(tab == 2);
+                    let config_tab_class = get_tab_class(tab == 3);
+                    let dhcp_tab_class = get_tab_class(tab == 4);
+                    let iac_tab_class = get_tab_class(tab == 5);
+                    let tab_cols = get_tab_grid_class(available.iter().filter(|&&a| a).count());
 
                     rsx! {
+                        if let Some(found) = calc.host_bits {
+                            HostBitsWarning { found }
+                        }
                         // Tabs bar
-                        div { class: "flex grid grid-cols-2 border-b border-gray-600 mb-6",
+                        div { class: "flex grid {tab_cols} border-b border-gray-600 mb-6",
                             button {
                                 class: "{summary_tab_class} transition-colors",
                                 onclick: move |_| active_tab.set(0),
@@ -44,20 +62,80 @@ pub fn ResultsPanel(result: Option<Result<CalculationResult, Ipv4InputError>>) -
                                     "Subnets ({calc.total_subnets})"
                                 }
                             }
+                            if has_map {
+                                button {
+                                    class: "{map_tab_class} transition-colors",
+                                    onclick: move |_| active_tab.set(2),
+                                    "Map"
+                                }
+                            }
+                            if has_subnets {
+                                button {
+                                    class: "{config_tab_class} transition-colors",
+                                    onclick: move |_| active_tab.set(3),
+                                    "Config"
+                                }
+                            }
+                            if has_subnets {
+                                button {
+                                    class: "{dhcp_tab_class} transition-colors",
+                                    onclick: move |_| active_tab.set(4),
+                                    "DHCP"
+                                }
+                            }
+                            if has_subnets {
+                                button {
+                                    class: "{iac_tab_class} transition-colors",
+                                    onclick: move |_| active_tab.set(5),
+                                    "IaC"
+                                }
+                            }
                         }
 
                         // Tab content
-                        if *active_tab.read() == 0 || !has_subnets {
-                            SummaryTable { 
-                                summary: calc.summary.clone(), 
-                                new_prefix: calc.new_prefix, 
-                                subnets: calc.subnets.clone() 
+                        if tab == 0 {
+                            if let Some(analysis) = calc.wildcard.clone() {
+                                WildcardDetails { analysis, covering: calc.base_network.to_string() }
+                            } else if let Some(search) = calc.free_search.clone() {
+                                FreeSearchSummary { search, found: calc.subnets.len() }
+                                SummaryTable { summary: calc.summary.clone(), new_prefix: None, subnets: vec![] }
+                            } else {
+                                SummaryTable { 
+                                    summary: calc.summary.clone(), 
+                                    new_prefix: calc.new_prefix, 
+                                    subnets: calc.subnets.clone() 
+                                }
                             }
-
+                            AddressArithmetic { network: calc.base_network.into() }
                         }
-                        if *active_tab.read() == 1 && has_subnets {
+                        if tab == 1 {
                             SubnetTable { subnets: calc.subnets.clone(), base_prefix: calc.base_network.prefix_len(), total_subnets: calc.total_subnets }
                         }
+                        if tab == 2 {
+                            SplitMap {
+                                key: "{calc.base_network}-{calc.new_prefix:?}",
+                                base: u32::from(calc.base_network.network()) as u128,
+                                new_prefix: calc.new_prefix.unwrap_or(32),
+                                total: calc.total_subnets as u128,
+                                used: calc.requested_subnets.map_or(calc.total_subnets as u128, |n| n as u128),
+                                is_v4: true,
+                            }
+                        }
+                        if tab == 3 {
+                            RouterConfigPanel { subnets: calc.subnets.iter().map(ConfigSubnet::from).collect::<Vec<_>>() }
+                        }
+                        if tab == 4 {
+                            DhcpPanel { subnets: calc.subnets.iter().map(|sub| sub.network.into()).collect::<Vec<_>>() }
+                        }
+                        if tab == 5 {
+                            IacPanel {
+                                subnets: calc.subnets.iter().enumerate().map(|(i, sub)| IacSubnet {
+                                    name: format!("subnet-{}", i + 1),
+                                    ipv4: Some(sub.network),
+                                    ipv6: None,
+                                }).collect::<Vec<_>>()
+                            }
+                        }
                     }
                 }
             }
@@ -77,14 +155,12 @@ fn PlaceholderMessage() -> Element {
 
 #[component]
 fn ErrorMessage(err: Ipv4InputError) -> Element {
-    let msg = match err {
-        Ipv4InputError::ParseError(s) => s,
-        Ipv4InputError::InvalidMask => "Invalid subnet mask".to_string(),
-        Ipv4InputError::InvalidPrefix => "Invalid prefix length".to_string(),
-    };
     rsx! {
         div { class: "bg-red-900/40 border font-roboto border-red-500 text-red-300 p-6 rounded-lg",
-            strong { "Error: " } "{msg}"
+            strong { "Error: " } "{err}"
+            if let Some(hint) = err.suggestion() {
+                p { class: "mt-2 text-sm text-red-200", "{hint}" }
+            }
         }
     }
 }
@@ -104,6 +180,7 @@ fn SummaryTable(summary: SubnetResult, new_prefix: Option<u8>, subnets: Vec<Subn
     };
 
     let base_prefix = summary.network.prefix_len();
+    let multicast = decode_multicast(summary.network.addr());
     rsx! {
         div {
             class: "overflow-y-auto pr-2 ",  // ← This makes it scrollable
@@ -128,6 +205,72 @@ fn SummaryTable(summary: SubnetResult, new_prefix: Option<u8>, subnets: Vec<Subn
                     }
                 }
             }
+            if let Some(info) = multicast {
+                MulticastDetails { info }
+            }
+        }
+    }
+}
+
+#[component]
+fn MulticastDetails(info: MulticastInfo) -> Element {
+    rsx! {
+        h3 { class: "mt-6 mb-2 font-bold text-left", "Multicast Details" }
+        table { class: "w-full text-sm text-left border-collapse",
+            tbody {
+                SummaryRow { label: "Block", value: format!("{} – {} ({})", info.block, info.block_name, info.rfc) }
+                SummaryRow { label: "Scope", value: info.scope.to_string() }
+                if let Some(name) = info.well_known {
+                    SummaryRow { label: "Well-Known Group", value: name.to_string() }
+                }
+                if let Some(asn) = info.glop_asn {
+                    SummaryRow { label: "GLOP AS Number", value: format!("AS{}", asn) }
+                }
+                if let Some(prefix) = info.unicast_prefix {
+                    SummaryRow { label: "Unicast Prefix Owner", value: prefix.to_string() }
+                }
+                SummaryRow { label: "Ethernet MAC", value: info.mac.clone() }
+            }
+        }
+        h4 { class: "mt-4 mb-2 text-sm font-medium text-left text-gray-300", "Groups sharing this MAC (32)" }
+        div { class: "grid grid-cols-4 gap-1 text-xs font-roboto text-left",
+            for group in info.overlapping.iter() {
+                span { "{group}" }
+            }
+        }
+    }
+}
+
+#[component]
+fn WildcardDetails(analysis: WildcardAnalysis, covering: String) -> Element {
+    let cidrs = match &analysis.cidrs {
+        Some(nets) if analysis.contiguous => format!("Contiguous – equivalent to {}", nets[0]),
+        Some(_) => format!("{} CIDR blocks (see Subnets tab)", analysis.cidr_count),
+        None => format!("{} CIDR blocks – too many to list", analysis.cidr_count),
+    };
+    let octet_labels = ["Octet 1", "Octet 2", "Octet 3", "Octet 4"];
+
+    rsx! {
+        div { class: "overflow-y-auto pr-2",
+            table { class: "w-full text-sm text-left border-collapse",
+                tbody {
+                    SummaryRow { label: "Match", value: format!("{} {}", analysis.address, analysis.wildcard) }
+                    SummaryRow { label: "Bit Pattern", value: analysis.pattern.clone() }
+                    for (label, text) in octet_labels.iter().zip(analysis.octets.iter()) {
+                        SummaryRow { label, value: text.clone() }
+                    }
+                    SummaryRow { label: "Matching Addresses", value: analysis.matching.to_string() }
+                    SummaryRow { label: "As CIDRs", value: cidrs }
+                    SummaryRow { label: "Covering Prefix", value: covering }
+                    if let Some((addr, matched)) = analysis.test {
+                        SummaryRow {
+                            label: "Test Address",
+                            value: format!("{} {}", addr, if matched { "matches" } else { "does not match" })
+                        }
+                    }
+                }
+            }
+            p { class: "mt-4 text-xs text-gray-500 text-left", "x = wildcard bit (ignored), 0/1 = bit must equal the address" }
         }
     }
 }
@@ -1,10 +1,100 @@
-use ipnet::{Ipv4Net};
+use ipnet::{IpNet, Ipv4Net};
+use std::net::Ipv4Addr;
+use crate::address_math::HostBits;
+use crate::free_space::{FieldError, FreeSearch};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Ipv4InputError {
-    ParseError(String),
-    InvalidMask,
-    InvalidPrefix,
+    InvalidAddress(String),                 // Text that isn't a dotted-quad address
+    InvalidMask(String),                    // Neither a prefix length nor a contiguous netmask
+    InvalidWildcard(String),
+    HostBitsSet(Box<HostBits>),             // Strict mode only
+    PrefixOutOfRange { min: u8, max: u8 },
+    TooManyHosts { requested: u32, max: u64, platform: PlatformProfile },
+    TooManySubnets { requested: u32, max: u64 },
+    NoSubnets,
+    PlatformPrefixLimit { platform: PlatformProfile, prefix: u8 }, // Subnet size outside the platform's limits
+    WrongFamily(IpNet),                     // IPv6 prefix in an IPv4 used list
+    Field(FieldError),
+}
+
+impl std::fmt::Display for Ipv4InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Ipv4InputError::InvalidAddress(text) => write!(f, "'{}' is not a valid IPv4 address", text),
+            Ipv4InputError::InvalidMask(text) => write!(f, "'{}' is not a prefix length or subnet mask", text),
+            Ipv4InputError::InvalidWildcard(text) => write!(f, "'{}' is not a valid wildcard mask", text),
+            Ipv4InputError::HostBitsSet(found) => write!(f, "{}", found),
+            Ipv4InputError::PrefixOutOfRange { min, max } => write!(f, "Prefix length must be between /{} and /{}", min, max),
+            Ipv4InputError::TooManyHosts { requested, max, .. } => write!(f, "{} hosts requested but the network holds at most {}", requested, max),
+            Ipv4InputError::TooManySubnets { requested, max } => write!(f, "{} subnets requested but the network splits into at most {}", requested, max),
+            Ipv4InputError::NoSubnets => write!(f, "Subnet count must be at least 1"),
+            Ipv4InputError::PlatformPrefixLimit { platform, prefix } => {
+                let (min, max) = platform.prefix_range();
+                write!(f, "{} only allows subnets from /{} to /{} (got /{})", platform.label(), min, max, prefix)
+            }
+            Ipv4InputError::WrongFamily(net) => write!(f, "{} is not an IPv4 prefix", net),
+            Ipv4InputError::Field(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl std::error::Error for Ipv4InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Ipv4InputError::Field(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<FieldError> for Ipv4InputError {
+    fn from(err: FieldError) -> Self {
+        Ipv4InputError::Field(err)
+    }
+}
+
+impl Ipv4InputError {
+    /// What to change in the input, when there's something concrete to offer
+    pub fn suggestion(&self) -> Option<String> {
+        match self {
+            Ipv4InputError::InvalidAddress(text) => {
+                // "10.0.0.0/8" typed into the address box
+                if let Some((addr, prefix)) = text.split_once('/') {
+                    return Some(format!("Put the prefix in the mask field: {} and /{}", addr.trim(), prefix.trim()));
+                }
+                // Short forms like "10.1" or "192.168.1."
+                let octets: Vec<&str> = text.trim_end_matches('.').split('.').collect();
+                if octets.len() < 4 && octets.iter().all(|o| o.parse::<u8>().is_ok()) {
+                    let padded: Vec<&str> = octets.iter().copied().chain(std::iter::repeat("0")).take(4).collect();
+                    return Some(format!("Did you mean {}?", padded.join(".")));
+                }
+                None
+            }
+            Ipv4InputError::InvalidMask(text) => {
+                let mask = u32::from(text.parse::<Ipv4Addr>().ok()?);
+                // Inverted (wildcard) mask, e.g. 0.0.0.255
+                if (!mask).leading_ones() + (!mask).trailing_zeros() == 32 {
+                    return Some(format!("{} looks like a wildcard mask; did you mean /{}?", text, (!mask).leading_ones()));
+                }
+                Some("Discontiguous masks are only supported in Wildcard mode".to_string())
+            }
+            Ipv4InputError::HostBitsSet(found) => Some(format!("Did you mean {}?", found.network)),
+            Ipv4InputError::TooManyHosts { requested, platform, .. } => {
+                // Sized like ByHosts mode: the hosts plus the platform's reserved addresses
+                let bits = (*requested as u64 + platform.reserved() as u64).next_power_of_two().trailing_zeros();
+                (bits <= 32).then(|| format!("{} hosts need a /{} or larger parent", requested, 32 - bits))
+            }
+            Ipv4InputError::TooManySubnets { max, .. } => Some(format!("Ask for {} subnets or fewer, or use a larger parent", max)),
+            Ipv4InputError::PlatformPrefixLimit { platform, .. } => {
+                let (min, max) = platform.prefix_range();
+                Some(format!("Pick a size between /{} and /{}, or switch to the Standard platform", min, max))
+            }
+            Ipv4InputError::WrongFamily(_) => Some("Remove it from the list, or check it on the IPv6 tab".to_string()),
+            Ipv4InputError::Field(FieldError::InvalidEntry { .. }) => Some("List one prefix or address per line, e.g. 10.0.0.0/24".to_string()),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -15,7 +105,7 @@ pub struct SubnetResult {
     pub broadcast: String,
     pub first_host: Option<String>,
     pub last_host: Option<String>,
-    pub usable_hosts: u32,
+    pub usable_hosts: u64,
 }
 
 #[derive(Clone, PartialEq)]
@@ -23,6 +113,75 @@ pub enum SubnetMode {
     ByHosts,
     BySubnets,
     Inspect,
+    Wildcard,
+    NextFree,
+}
+
+/// Where the subnets will live; cloud providers reserve extra addresses and limit subnet sizes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlatformProfile {
+    Standard,
+    Aws,
+    Azure,
+    Gcp,
+}
+
+impl PlatformProfile {
+    pub const ALL: [PlatformProfile; 4] = [PlatformProfile::Standard, PlatformProfile::Aws, PlatformProfile::Azure, PlatformProfile::Gcp];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PlatformProfile::Standard => "Standard (on-premises)",
+            PlatformProfile::Aws => "AWS VPC",
+            PlatformProfile::Azure => "Azure VNet",
+            PlatformProfile::Gcp => "Google Cloud VPC",
+        }
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            PlatformProfile::Standard => "standard",
+            PlatformProfile::Aws => "aws",
+            PlatformProfile::Azure => "azure",
+            PlatformProfile::Gcp => "gcp",
+        }
+    }
+
+    pub fn from_key(key: &str) -> PlatformProfile {
+        PlatformProfile::ALL.into_iter().find(|p| p.key() == key).unwrap_or(PlatformProfile::Standard)
+    }
+
+    /// Addresses unusable at the start of each subnet, including the network address.
+    /// AWS and Azure: network, router, two for DNS/future use; GCP: network and gateway.
+    pub fn reserved_start(&self) -> u32 {
+        match self {
+            PlatformProfile::Standard => 1,
+            PlatformProfile::Aws | PlatformProfile::Azure => 4,
+            PlatformProfile::Gcp => 2,
+        }
+    }
+
+    /// Addresses unusable at the end, including the broadcast address (GCP also keeps the one before it)
+    pub fn reserved_end(&self) -> u32 {
+        match self {
+            PlatformProfile::Gcp => 2,
+            _ => 1,
+        }
+    }
+
+    pub fn reserved(&self) -> u32 {
+        self.reserved_start() + self.reserved_end()
+    }
+
+    /// Largest and smallest subnet the platform accepts, as (min, max) prefix length
+    pub fn prefix_range(&self) -> (u8, u8) {
+        match self {
+            PlatformProfile::Standard => (0, 32),
+            PlatformProfile::Aws => (16, 28),
+            PlatformProfile::Azure => (2, 29),
+            PlatformProfile::Gcp => (4, 29),
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -32,9 +191,42 @@ pub struct CalculationResult {
     pub subnets: Vec<SubnetResult>,  // Empty if no splitting
     pub new_prefix: Option<u8>,     // Only when splitting
     pub total_subnets: u64,
+    pub requested_subnets: Option<u32>, // Only in BySubnets mode
+    pub wildcard: Option<WildcardAnalysis>, // Only in Wildcard mode
+    pub free_search: Option<FreeSearch>,    // Only in NextFree mode
+    pub host_bits: Option<HostBits>,        // Parent typed as a host address, e.g. 192.168.1.77/24
+}
+
+/// Match set of an address + (possibly discontiguous) ACL wildcard mask
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WildcardAnalysis {
+    pub address: Ipv4Addr, // Wildcard bits cleared
+    pub wildcard: Ipv4Addr,
+    pub contiguous: bool,  // Equivalent to a regular netmask
+    pub pattern: String,   // e.g. 00001010.00000000.xxxxxxx0.xxxxxxxx
+    pub octets: Vec<String>,
+    pub matching: u64,
+    pub cidr_count: u64,
+    pub cidrs: Option<Vec<Ipv4Net>>, // None when there are too many to list
+    pub test: Option<(Ipv4Addr, bool)>,
+}
+
+/// Decoded details of a 224.0.0.0/4 address
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MulticastInfo {
+    pub block: Ipv4Net,
+    pub block_name: &'static str,
+    pub rfc: &'static str,
+    pub scope: &'static str,
+    pub well_known: Option<&'static str>,
+    pub glop_asn: Option<u16>,
+    pub unicast_prefix: Option<Ipv4Net>,
+    pub mac: String,
+    pub overlapping: Vec<Ipv4Addr>, // The 32 groups sharing this MAC, including itself
 }
 
 pub const CIDR_OPTIONS: &[(u8, &str, &str)] = &[
+    (0,  "/0",   "0.0.0.0"),
     (1,  "/1",   "128.0.0.0"),
     (2,  "/2",   "192.0.0.0"),
     (3,  "/3",   "224.0.0.0"),
@@ -66,4 +258,5 @@ pub const CIDR_OPTIONS: &[(u8, &str, &str)] = &[
     (29, "/29",  "255.255.255.248"),
     (30, "/30",  "255.255.255.252"),
     (31, "/31",  "255.255.255.254"),
+    (32, "/32",  "255.255.255.255"),
 ];
\ No newline at end of file
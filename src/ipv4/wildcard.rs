@@ -0,0 +1,141 @@
+// src/ipv4/wildcard.rs
+use ipnet::Ipv4Net;
+use std::net::Ipv4Addr;
+use crate::ipv4::calculator::LIMIT;
+use crate::ipv4::types::WildcardAnalysis;
+
+/// Bit pattern with fixed bits as 0/1 and wildcard ("don't care") bits as x, dotted per octet
+fn bit_pattern(address: u32, wildcard: u32) -> String {
+    (0..4)
+        .map(|octet| {
+            (0..8)
+                .map(|i| {
+                    let bit = 31 - (octet * 8 + i);
+                    if wildcard >> bit & 1 == 1 {
+                        'x'
+                    } else if address >> bit & 1 == 1 {
+                        '1'
+                    } else {
+                        '0'
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+fn describe_octet(value: u8, wildcard: u8) -> String {
+    let count = 1u32 << wildcard.count_ones();
+    match wildcard {
+        0 => format!("exactly {}", value),
+        255 => "any value (0–255)".to_string(),
+        w if (w as u16 + 1).is_power_of_two() => format!("{}–{} ({} values)", value, value | w, count),
+        254 if value & 1 == 0 => "even values (128 values)".to_string(),
+        254 => "odd values (128 values)".to_string(),
+        w => {
+            let fixed: Vec<String> = (0..8)
+                .rev()
+                .filter(|bit| w >> bit & 1 == 0)
+                .map(|bit| format!("bit {}={}", bit, value >> bit & 1))
+                .collect();
+            format!("{} values where {}", count, fixed.join(", "))
+        }
+    }
+}
+
+/// Analyze an address/wildcard pair as a Cisco ACL would match it; any wildcard is accepted,
+/// including discontiguous ones like 0.0.254.255
+pub fn analyze_wildcard(address: Ipv4Addr, wildcard: Ipv4Addr, test: Option<Ipv4Addr>) -> WildcardAnalysis {
+    let w = u32::from(wildcard);
+    let base = u32::from(address) & !w; // Bits under the wildcard are ignored by the match
+
+    // Trailing wildcard bits form the host part of each CIDR; every combination of the
+    // remaining wildcard bits is one prefix, and no two of them are adjacent siblings
+    let host_bits = w.trailing_ones();
+    let scattered: Vec<u32> = (host_bits..32).filter(|bit| w >> bit & 1 == 1).collect();
+    let cidr_count = 1u64 << scattered.len();
+    let cidrs = (cidr_count <= LIMIT as u64).then(|| {
+        (0..cidr_count)
+            .map(|combo| {
+                let network = scattered
+                    .iter()
+                    .enumerate()
+                    .fold(base, |acc, (i, bit)| acc | (((combo >> i) & 1) as u32) << bit);
+                Ipv4Net::new(Ipv4Addr::from(network), (32 - host_bits) as u8).unwrap_or_default()
+            })
+            .collect()
+    });
+
+    WildcardAnalysis {
+        address: Ipv4Addr::from(base),
+        wildcard,
+        contiguous: scattered.is_empty(),
+        pattern: bit_pattern(base, w),
+        octets: base
+            .to_be_bytes()
+            .iter()
+            .zip(w.to_be_bytes())
+            .map(|(&value, mask)| describe_octet(value, mask))
+            .collect(),
+        matching: 1u64 << w.count_ones(),
+        cidr_count,
+        cidrs,
+        test: test.map(|addr| (addr, u32::from(addr) & !w == base)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn ip(s: &str) -> Ipv4Addr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn contiguous_wildcard_is_one_cidr() {
+        let analysis = analyze_wildcard(ip("10.1.2.3"), ip("0.0.0.255"), Some(ip("10.1.2.200")));
+        assert!(analysis.contiguous);
+        assert_eq!(analysis.address, ip("10.1.2.0"));
+        assert_eq!(analysis.matching, 256);
+        assert_eq!(analysis.cidrs, Some(vec!["10.1.2.0/24".parse().unwrap()]));
+        assert_eq!(analysis.test, Some((ip("10.1.2.200"), true)));
+        assert_eq!(analysis.pattern, "00001010.00000001.00000010.xxxxxxxx");
+    }
+
+    #[test]
+    fn discontiguous_wildcard_lists_every_cidr() {
+        // Even third octets only
+        let analysis = analyze_wildcard(ip("10.0.0.0"), ip("0.0.254.255"), Some(ip("10.0.3.1")));
+        assert!(!analysis.contiguous);
+        assert_eq!(analysis.cidr_count, 128);
+        assert_eq!(analysis.matching, 128 * 256);
+        assert_eq!(analysis.test, Some((ip("10.0.3.1"), false)));
+        assert_eq!(analysis.octets[2], "even values (128 values)");
+        assert_eq!(analysis.cidrs.as_ref().unwrap()[1], "10.0.2.0/24".parse().unwrap());
+    }
+
+    #[test]
+    fn too_many_cidrs_are_not_listed() {
+        let analysis = analyze_wildcard(ip("0.0.0.0"), ip("255.255.255.254"), None);
+        assert_eq!(analysis.cidr_count, 1 << 31);
+        assert_eq!(analysis.cidrs, None);
+    }
+
+    proptest! {
+        #[test]
+        fn cidrs_cover_exactly_the_matching_addresses(address in any::<u32>(), wildcard in any::<u32>(), probe in any::<u32>()) {
+            // Keep the listing small
+            let wildcard = wildcard & 0x0000_0fff;
+            let analysis = analyze_wildcard(address.into(), wildcard.into(), Some(probe.into()));
+            let cidrs = analysis.cidrs.unwrap();
+            let total: u64 = cidrs.iter().map(|n| 1u64 << (32 - n.prefix_len())).sum();
+            prop_assert_eq!(total, analysis.matching);
+            let matches = probe & !wildcard == address & !wildcard;
+            prop_assert_eq!(analysis.test.unwrap().1, matches);
+            prop_assert_eq!(cidrs.iter().any(|n| n.contains(&Ipv4Addr::from(probe))), matches);
+        }
+    }
+}
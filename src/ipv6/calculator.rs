@@ -1,10 +1,26 @@
-use ipnet::{Ipv6Net, Ipv6Subnets};
+use ipnet::{IpNet, Ipv6Net, Ipv6Subnets};
 use std::net::Ipv6Addr;
-use std::str::FromStr;
-use crate::ipv6::types::{CalculationResult, HierarchyLevel, HierarchyNode, HierarchyResult, Ipv6InputError, SubnetMode, SubnetResult};
+use crate::address_math::{check_host_bits, HostBitsPolicy};
+use crate::free_space::{self, parse_count, parse_size, parse_used, FitStrategy};
+use crate::ipv6::registry::classify;
+use crate::ipv6::types::{CalculationResult, MAX_USABLE_SUBNETS, HierarchyLevel, HierarchyNode, HierarchyPlacement, HierarchyResult, HierarchyRow, Ipv6InputError, LevelUsage, PdPlan, PdPool, SubnetMode, SubnetResult};
 
 pub const LIMIT: usize = 8192;
 pub const LAST_N: usize = 10;
+pub const PD_SAMPLE_LANS: usize = 16;
+
+/// Address and prefix length ("48" or "/48") from separate fields
+pub fn parse_network(addr: &str, prefix_str: &str) -> Result<Ipv6Net, Ipv6InputError> {
+    let ip: Ipv6Addr = addr.trim()
+        .parse()
+        .map_err(|_| Ipv6InputError::InvalidAddress(addr.trim().to_string()))?;
+    let trimmed = prefix_str.trim();
+    trimmed.strip_prefix('/').unwrap_or(trimmed)
+        .parse::<u8>()
+        .ok()
+        .and_then(|prefix| Ipv6Net::new(ip, prefix).ok())
+        .ok_or(Ipv6InputError::PrefixOutOfRange { min: 0, max: 128 })
+}
 
 pub fn expand_ipv6(addr: Ipv6Addr) -> String {
     format!("{:04x}:{:04x}:{:04x}:{:04x}:{:04x}:{:04x}:{:04x}:{:04x}", 
@@ -19,29 +35,9 @@ pub fn expand_ipv6(addr: Ipv6Addr) -> String {
     )
 }
 
-fn is_unicast_global(addr: Ipv6Addr) -> bool {
-    let segments = addr.segments();
-
-    // Exclude documentation ranges: 2001:db8::/32 and 3fff::/20 (ORCHIDv2, but treated similarly)
-    if segments[0] == 0x2001 && segments[1] == 0xdb8 { return false; } // 2001:db8::/32
-    if segments[0] == 0x3fff && (segments[1] & 0xffc0) == 0x0000 { return false; } // 3fff::/20 (top 20 bits)
-
-    // Exclude benchmarking: 2001:2::/48
-    if segments[0] == 0x2001 && segments[1] == 0x0002 && segments[2] == 0x0000 { return false; }
-
-    // Exclude 6to4: 2002::/16
-    if segments[0] == 0x2002 { return false; }
-
-    // Exclude Teredo: 2001::/32
-    if segments[0] == 0x2001 && segments[1] == 0x0000 { return false; }
-
-    // Everything else in 2000::/3 is global unicast
-    (segments[0] & 0xe000) == 0x2000
-}
-
 fn collect_subnets(mut iter: Ipv6Subnets, total: u128, subnet_prefix: u8, base_network: Ipv6Net) -> Vec<SubnetResult> {
     let mut subnets = vec![];
-    if (total as usize) <= LIMIT {
+    if total <= LIMIT as u128 {
         for net in iter.take(LIMIT) {
             subnets.push(build_subnet_result(net));
         }
@@ -55,45 +51,70 @@ fn collect_subnets(mut iter: Ipv6Subnets, total: u128, subnet_prefix: u8, base_n
             }
         }
 
-        // Now calculate the last `LAST_N` manually
-        let subnet_size = 1u128 << (128 - subnet_prefix as u32);
-        let base_u128 = u128::from(base_network.network());
+        // Now calculate the last `LAST_N` manually, backwards from the end of the base network
+        // (`total` saturates at 2^128 - 1, so counting forward could be off by one)
+        let subnet_size = 1u128.checked_shl(128 - subnet_prefix as u32).unwrap_or(0);
+        let last_u128 = u128::from(base_network.broadcast());
 
-        for k in 0..LAST_N {
-            //let n = total - (LAST_N as u128 - k as u128);  // index of the k-th last subnet (1-based)
-            let n = total - (LAST_N as u128 - k as u128 - 1);
-            let offset = (n - 1) * subnet_size;
-            let start = Ipv6Addr::from(base_u128 + offset);
-            let net = Ipv6Net::new(start, subnet_prefix).unwrap();
-            subnets.push(build_subnet_result(net));
+        for k in (0..LAST_N as u128).rev() {
+            let start = Ipv6Addr::from(last_u128 - (k * subnet_size + subnet_size - 1));
+            if let Ok(net) = Ipv6Net::new(start, subnet_prefix) {
+                subnets.push(build_subnet_result(net));
+            }
         }
     }
     subnets
 }
 
-pub fn get_addr_type(addr: Ipv6Addr) -> String {
-    if addr.is_unspecified() {
-        "Unspecified".to_string()
-    } else if addr.is_loopback() {
-        "Loopback".to_string()
-    } else if addr.is_unique_local() {
-        "Unique Local".to_string()
-    } else if addr.is_unicast_link_local() {
-        "Link-Local".to_string()
-    } else if addr.is_multicast() {
-        "Multicast".to_string()
-    } else if is_unicast_global(addr) {  // Note: this is the stable name!
-        "Global Unicast".to_string()
-    } else {
-        "Other".to_string()
+/// Child slot indices used inside a parent with `slots` positions. Sparse children sit at the
+/// start of equal power-of-two blocks, so each can widen its prefix without renumbering.
+fn placement_slots(slots: u128, num: u128, placement: HierarchyPlacement) -> Vec<u128> {
+    let num = num.min(slots);
+    match placement {
+        HierarchyPlacement::Contiguous => (0..num).collect(),
+        HierarchyPlacement::Sparse => {
+            let stride = slots / num.max(1).next_power_of_two();
+            (0..num).map(|i| i * stride).collect()
+        }
     }
 }
 
+/// Aggregate the unused slots of a parent into the fewest aligned prefixes
+fn free_blocks(parent_start: u128, child_prefix: u8, slots: u128, used: &[u128]) -> Vec<Ipv6Net> {
+    let child_size = 1u128 << (128 - child_prefix as u32);
+    let mut blocks = vec![];
+    let mut run_start = 0u128;
+
+    // `used` is sorted; each gap between used slots is one free run
+    for &run_end in used.iter().chain(std::iter::once(&slots)) {
+        let mut start = run_start;
+        while start < run_end {
+            // Largest aligned power-of-two block that starts here and fits in the run
+            let mut size = if start == 0 { 1u128 << 127 } else { 1u128 << start.trailing_zeros() };
+            while size > run_end - start {
+                size >>= 1;
+            }
+            let prefix = child_prefix - size.trailing_zeros() as u8;
+            if let Ok(block) = Ipv6Net::new(Ipv6Addr::from(parent_start + start * child_size), prefix) {
+                blocks.push(block);
+            }
+            start += size;
+        }
+        run_start = run_end + 1;
+    }
+    blocks
+}
+
+pub fn get_addr_type(addr: Ipv6Addr) -> String {
+    classify(addr).name.to_string()
+}
+
 fn build_subnet_result(net: Ipv6Net) -> SubnetResult {
     let addr = net.network();
     let compressed = addr.to_string();
     let expanded = expand_ipv6(addr);
     let addr_type = get_addr_type(addr);
+    let classification = classify(addr);
     let first = net.hosts().next().unwrap_or(addr).to_string();
     let last = net.hosts().last().unwrap_or(addr).to_string();
     SubnetResult {
@@ -101,11 +122,13 @@ fn build_subnet_result(net: Ipv6Net) -> SubnetResult {
         compressed,
         expanded,
         addr_type,
+        classification,
         first_host: first,
         last_host: last,
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn calculate(
     addr: &str,
     prefix_str: &str,
@@ -113,9 +136,12 @@ pub fn calculate(
     needed_subnets: Option<u32>,
     child_prefix: Option<u8>,
     hierarchy_levels: Vec<HierarchyLevel>,
+    placement: HierarchyPlacement,
+    pd_pools: Option<u32>,
+    policy: HostBitsPolicy,
 ) -> Result<CalculationResult, Ipv6InputError> {
-    let base_network = Ipv6Net::from_str(&format!("{}/{}", addr.trim(), prefix_str.trim().strip_prefix('/').unwrap_or(prefix_str)))
-        .map_err(|e| Ipv6InputError::ParseError(e.to_string()))?;
+    let base_network = parse_network(addr, prefix_str)?;
+    let host_bits = check_host_bits(base_network.into(), policy).map_err(|found| Ipv6InputError::HostBitsSet(Box::new(found)))?;
     let base_prefix = base_network.prefix_len();
 
     let mut subnets = vec![];
@@ -124,8 +150,11 @@ pub fn calculate(
 
     // New: Optional hierarchy result
     let mut hierarchy: Option<HierarchyResult> = None;
+    let mut pd_plan: Option<PdPlan> = None;
 
     match mode {
+        SubnetMode::NextFree => return Err(Ipv6InputError::UnsupportedMode),
+
         SubnetMode::Inspect => {
             new_prefix = None;
             total_subnets = 1;
@@ -134,31 +163,121 @@ pub fn calculate(
         }
 
         SubnetMode::BySubnets => {
-            let count = needed_subnets.ok_or(Ipv6InputError::ParseError("Missing count".into()))? as u128;
+            let count = needed_subnets.ok_or(Ipv6InputError::MissingInput("Subnet count"))? as u128;
+            if count == 0 {
+                return Err(Ipv6InputError::NoSubnets);
+            }
             let bits_needed = count.next_power_of_two().trailing_zeros() as u8;
-            let np = base_prefix.checked_add(bits_needed)
-                .ok_or(Ipv6InputError::InsufficientBits)?;
-            if np > 128 {
-                return Err(Ipv6InputError::InsufficientBits);
+            let available = 128 - base_prefix as u32;
+            if bits_needed as u32 > available {
+                return Err(Ipv6InputError::NotEnoughBits { needed: bits_needed as u32, available });
             }
+            let np = base_prefix + bits_needed;
             new_prefix = Some(np);
             total_subnets = 1u128 << bits_needed as u32;
-            let iter = base_network.subnets(np).unwrap();
+            let iter = base_network.subnets(np).map_err(|_| Ipv6InputError::PrefixOutOfRange { min: base_prefix, max: 128 })?;
             subnets = collect_subnets(iter, total_subnets, np, base_network);
         }
 
         SubnetMode::ByPrefix => {
-            let np = child_prefix.ok_or(Ipv6InputError::ParseError("Missing prefix".into()))?;
+            let np = child_prefix.ok_or(Ipv6InputError::MissingInput("Subnet prefix"))?;
             if np <= base_prefix || np > 128 {
-                return Err(Ipv6InputError::InvalidPrefix);
+                return Err(Ipv6InputError::PrefixOutOfRange { min: base_prefix + 1, max: 128 });
             }
             new_prefix = Some(np);
-            total_subnets = 1u128 << (np - base_prefix) as u32;
-            let iter = base_network.subnets(np).unwrap();
+            // ::/0 split into /128s is 2^128 subnets, one more than u128 holds
+            total_subnets = 1u128.checked_shl((np - base_prefix) as u32).unwrap_or(u128::MAX);
+            let iter = base_network.subnets(np).map_err(|_| Ipv6InputError::PrefixOutOfRange { min: base_prefix + 1, max: 128 })?;
             subnets = collect_subnets(iter, total_subnets, np, base_network);
         }
 
+        SubnetMode::PrefixDelegation => {
+            let delegated = child_prefix.ok_or(Ipv6InputError::MissingInput("Delegated prefix length"))?;
+            let customers = needed_subnets.ok_or(Ipv6InputError::MissingInput("Customer count"))? as u128;
+            let pools = pd_pools.unwrap_or(1) as u128;
+            if delegated <= base_prefix || delegated > 64 {
+                return Err(Ipv6InputError::PrefixOutOfRange { min: base_prefix + 1, max: 64 });
+            }
+            if pools == 0 {
+                return Err(Ipv6InputError::NoSubnets);
+            }
+            if pools as usize > LIMIT {
+                return Err(Ipv6InputError::TooManySubnets { requested: pools, max: LIMIT as u128 });
+            }
+
+            // Split the aggregate evenly between routers, same arithmetic as ByPrefix
+            let pool_bits = pools.next_power_of_two().trailing_zeros() as u8;
+            let pool_prefix = base_prefix + pool_bits;
+            if pool_prefix > delegated {
+                return Err(Ipv6InputError::NotEnoughBits { needed: pool_bits as u32, available: (delegated - base_prefix) as u32 });
+            }
+            let per_pool_capacity = 1u128 << (delegated - pool_prefix) as u32;
+            let capacity = per_pool_capacity * pools;
+            if customers > capacity {
+                return Err(Ipv6InputError::TooManyCustomers { requested: customers, capacity });
+            }
+
+            new_prefix = Some(pool_prefix);
+            total_subnets = 1u128 << pool_bits as u32;
+            let iter = base_network.subnets(pool_prefix).map_err(|_| Ipv6InputError::PrefixOutOfRange { min: base_prefix, max: 64 })?;
+            subnets = collect_subnets(iter, total_subnets, pool_prefix, base_network);
+
+            let per_pool_customers = customers.div_ceil(pools);
+            let mut remaining = customers;
+            let delegated_size = 1u128 << (128 - delegated as u32);
+            let pool_list = subnets
+                .iter()
+                .take(pools as usize)
+                .enumerate()
+                .map(|(i, pool)| {
+                    let start = u128::from(pool.network.network());
+                    let assigned = remaining.min(per_pool_customers);
+                    remaining -= assigned;
+                    let delegated_at = |addr: u128| Ipv6Net::new(Ipv6Addr::from(addr), delegated).map_err(|_| Ipv6InputError::PrefixOutOfRange { min: base_prefix + 1, max: 64 });
+                    Ok(PdPool {
+                        name: format!("PD-POOL-{}", i + 1),
+                        prefix: pool.network,
+                        first_delegated: delegated_at(start)?,
+                        last_delegated: delegated_at(start + (per_pool_capacity - 1) * delegated_size)?,
+                        capacity: per_pool_capacity,
+                        customers: assigned,
+                    })
+                })
+                .collect::<Result<Vec<_>, Ipv6InputError>>()?;
+
+            // Per-customer LAN layout: the /64s inside one delegated prefix
+            let first_customer = pool_list.first().ok_or(Ipv6InputError::NoSubnets)?.first_delegated;
+            let sample_lans = first_customer
+                .subnets(64)
+                .map_err(|_| Ipv6InputError::PrefixOutOfRange { min: base_prefix + 1, max: 64 })?
+                .take(PD_SAMPLE_LANS)
+                .collect();
+
+            pd_plan = Some(PdPlan {
+                delegated_prefix: delegated,
+                customers,
+                capacity,
+                pools: pool_list,
+                lans_per_customer: 1u128 << (64 - delegated as u32),
+                sample_lans,
+            });
+        }
+
         SubnetMode::ByHierarchy => {
+            // Every leaf becomes a node in the tree, so cap them as the input panel does
+            let leaves = hierarchy_levels.iter().fold(1u128, |acc, l| acc.saturating_mul(l.num as u128));
+            if leaves > MAX_USABLE_SUBNETS {
+                return Err(Ipv6InputError::TooManySubnets { requested: leaves, max: MAX_USABLE_SUBNETS });
+            }
+            // A zero-count level would empty the tree and let later levels skip the leaf cap
+            if let Some(i) = hierarchy_levels.iter().position(|l| l.num == 0 || l.bits == 0 || l.bits >= 128) {
+                return Err(Ipv6InputError::InvalidHierarchyLevel { index: i + 1 });
+            }
+            let total_bits: u32 = hierarchy_levels.iter().map(|l| l.bits as u32).sum();
+            if total_bits > 128 - base_prefix as u32 {
+                return Err(Ipv6InputError::NotEnoughBits { needed: total_bits, available: 128 - base_prefix as u32 });
+            }
+
             if hierarchy_levels.is_empty() {
                 new_prefix = None;
                 total_subnets = 1;
@@ -170,47 +289,54 @@ pub fn calculate(
                     prefix: base_network,
                     label: "Original Network".to_string(),
                     children: vec![],
+                    reserved: vec![],
                 };
 
                 // Current set of parents to add children to (starts with root)
                 let mut current_parents: Vec<&mut HierarchyNode> = vec![&mut root];
 
                 let mut current_prefix = base_prefix;
+                let mut usage = vec![];
 
-                for level in hierarchy_levels.iter() {
-                    // Calculate min bits needed
-                    let bits_needed = (level.num as f64).log2().ceil() as u8;
+                for (index, level) in hierarchy_levels.iter().enumerate() {
+                    // Calculate min bits needed: ceil(log2(num)) without float rounding
+                    let bits_needed = (level.num as u64).next_power_of_two().trailing_zeros() as u8;
                     if bits_needed > level.bits {
-                        return Err(Ipv6InputError::InsufficientBits);
+                        return Err(Ipv6InputError::LevelTooSmall { index: index + 1, needed: bits_needed, bits: level.bits });
                     }
 
-                    // Update cumulative prefix for child subnets
-                    current_prefix = current_prefix.checked_add(level.bits)
-                        .ok_or(Ipv6InputError::InsufficientBits)?;
-                    if current_prefix > 128 {
-                        return Err(Ipv6InputError::InsufficientBits);
-                    }
+                    // Update cumulative prefix for child subnets; the total was checked above
+                    current_prefix += level.bits;
+
+                    let slots = 1u128 << level.bits as u32;
+                    let used_slots = placement_slots(slots, level.num as u128, placement);
+                    usage.push(LevelUsage {
+                        name: level.name.clone(),
+                        used: current_parents.len() as u128 * used_slots.len() as u128,
+                        capacity: (current_parents.len() as u128).saturating_mul(slots),
+                    });
 
                     // Prepare next set of parents
                     let mut new_parents = vec![];
 
                     // For each current parent, generate and attach children
                     for parent in current_parents {
-                        let mut children = vec![];
-
-                        // Generate subnets from parent's prefix at current_prefix length
-                        if let Ok(iter) = parent.prefix.subnets(current_prefix) {
-                            for (i, net) in iter.enumerate().take(level.num as usize) {
-                                children.push(HierarchyNode {
-                                    prefix: net,
+                        let parent_start = u128::from(parent.prefix.network());
+                        let child_size = 1u128 << (128 - current_prefix as u32);
+
+                        parent.children = used_slots
+                            .iter()
+                            .enumerate()
+                            .filter_map(|(i, &slot)| {
+                                Some(HierarchyNode {
+                                    prefix: Ipv6Net::new(Ipv6Addr::from(parent_start + slot * child_size), current_prefix).ok()?,
                                     label: format!("{} {}", level.name, i + 1),
                                     children: vec![],
-                                });
-                            }
-                        }
-
-                        // Attach to parent
-                        parent.children = children;
+                                    reserved: vec![],
+                                })
+                            })
+                            .collect();
+                        parent.reserved = free_blocks(parent_start, current_prefix, slots, &used_slots);
 
                         // Add children as next parents (mutable refs)
                         for child in parent.children.iter_mut() {
@@ -229,6 +355,8 @@ pub fn calculate(
                 hierarchy = Some(HierarchyResult {
                     levels: hierarchy_levels.clone(),
                     tree,
+                    usage,
+                    placement,
                 });
 
                 new_prefix = None;
@@ -248,6 +376,362 @@ pub fn calculate(
         subnets,
         new_prefix,
         total_subnets,
+        requested_subnets: match mode {
+            SubnetMode::BySubnets => needed_subnets,
+            SubnetMode::PrefixDelegation => pd_pools.or(Some(1)),
+            _ => None,
+        },
         hierarchy,
+        pd_plan,
+        free_search: None,
+        host_bits,
     })
 }
+
+/// Next-free mode: the first `count` free aligned blocks of `size` ("/N" or an address count)
+/// inside the base network, skipping every prefix listed in `used`
+pub fn find_free_subnets(
+    addr: &str,
+    prefix_str: &str,
+    used: &str,
+    size: &str,
+    count: &str,
+    strategy: FitStrategy,
+    policy: HostBitsPolicy,
+) -> Result<CalculationResult, Ipv6InputError> {
+    let parsed = parse_network(addr, prefix_str)?;
+    let host_bits = check_host_bits(parsed.into(), policy).map_err(|found| Ipv6InputError::HostBitsSet(Box::new(found)))?;
+    let base_network = parsed.trunc();
+    let (used, normalized) = parse_used(used, policy)?;
+    if let Some(&v4) = used.iter().find(|n| matches!(n, IpNet::V4(_))) {
+        return Err(Ipv6InputError::WrongFamily(v4));
+    }
+    let new_prefix = parse_size(size, 128, 0)?;
+    if new_prefix < base_network.prefix_len() {
+        return Err(Ipv6InputError::PrefixOutOfRange { min: base_network.prefix_len(), max: 128 });
+    }
+
+    let count = parse_count(count, LIMIT)?;
+    let (found, search) = free_space::search(IpNet::V6(base_network), used, normalized, new_prefix, count, strategy);
+    let subnets: Vec<SubnetResult> = found
+        .into_iter()
+        .filter_map(|net| match net {
+            IpNet::V6(net) => Some(build_subnet_result(net)),
+            IpNet::V4(_) => None,
+        })
+        .collect();
+
+    Ok(CalculationResult {
+        base_network,
+        summary: build_subnet_result(base_network),
+        total_subnets: subnets.len() as u128,
+        subnets,
+        new_prefix: Some(new_prefix),
+        requested_subnets: Some(count as u32),
+        hierarchy: None,
+        pd_plan: None,
+        free_search: Some(search),
+        host_bits,
+    })
+}
+
+/// Flatten a hierarchy tree into one row per node (depth-first, parents before children)
+pub fn flatten_hierarchy(hierarchy: &HierarchyResult) -> Vec<HierarchyRow> {
+    fn walk(node: &HierarchyNode, levels: &[HierarchyLevel], depth: usize, parent: Option<(&str, Ipv6Net)>, rows: &mut Vec<HierarchyRow>) {
+        let path = match parent {
+            Some((parent_path, _)) => format!("{} / {}", parent_path, node.label),
+            None => node.label.clone(),
+        };
+        // Child slots offered by the next level, minus the ones actually used
+        let spare_capacity = levels
+            .get(depth)
+            .map(|level| (1u128 << level.bits as u32).saturating_sub(node.children.len() as u128))
+            .unwrap_or(0);
+
+        rows.push(HierarchyRow {
+            path: path.clone(),
+            depth,
+            prefix: node.prefix,
+            first_address: node.prefix.network(),
+            last_address: node.prefix.broadcast(),
+            parent: parent.map(|(_, net)| net),
+            children: node.children.len(),
+            spare_capacity,
+            reserved: node.reserved.clone(),
+        });
+
+        for child in &node.children {
+            walk(child, levels, depth + 1, Some((&path, node.prefix)), rows);
+        }
+    }
+
+    let mut rows = vec![];
+    for node in &hierarchy.tree {
+        walk(node, &hierarchy.levels, 0, None, &mut rows);
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::assert_golden;
+    use proptest::prelude::*;
+
+    fn by_prefix(addr: &str, prefix: &str, child: u8) -> Result<CalculationResult, Ipv6InputError> {
+        calculate(addr, prefix, SubnetMode::ByPrefix, None, Some(child), vec![], HierarchyPlacement::Contiguous, None, HostBitsPolicy::Normalize)
+    }
+
+    fn hierarchy(levels: Vec<HierarchyLevel>, placement: HierarchyPlacement) -> Result<CalculationResult, Ipv6InputError> {
+        calculate("2001:db8::", "48", SubnetMode::ByHierarchy, None, None, levels, placement, None, HostBitsPolicy::Normalize)
+    }
+
+    fn level(name: &str, num: u32, bits: u8) -> HierarchyLevel {
+        HierarchyLevel { name: name.to_string(), num, bits }
+    }
+
+    /// Every child lies inside its parent, and siblings never overlap
+    fn check_tree(node: &HierarchyNode) -> Result<(), TestCaseError> {
+        for (i, child) in node.children.iter().enumerate() {
+            prop_assert!(node.prefix.contains(&child.prefix), "{} not in {}", child.prefix, node.prefix);
+            for sibling in &node.children[i + 1..] {
+                prop_assert!(!child.prefix.contains(&sibling.prefix) && !sibling.prefix.contains(&child.prefix));
+            }
+            for reserved in &node.reserved {
+                prop_assert!(!reserved.contains(&child.prefix) && !child.prefix.contains(reserved));
+            }
+            check_tree(child)?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn expands_every_group() {
+        assert_eq!(expand_ipv6("2001:db8::1".parse().unwrap()), "2001:0db8:0000:0000:0000:0000:0000:0001");
+        assert_eq!(expand_ipv6(Ipv6Addr::UNSPECIFIED), "0000:0000:0000:0000:0000:0000:0000:0000");
+        assert_eq!(expand_ipv6("fe80::abcd:12".parse().unwrap()), "fe80:0000:0000:0000:0000:0000:abcd:0012");
+    }
+
+    #[test]
+    fn names_address_types() {
+        assert_eq!(get_addr_type("2001:db8::".parse().unwrap()), classify("2001:db8::".parse().unwrap()).name);
+        assert_ne!(get_addr_type("fe80::1".parse().unwrap()), get_addr_type("fd00::1".parse().unwrap()));
+    }
+
+    #[test]
+    fn warns_or_rejects_host_bits() {
+        let found = by_prefix("2001:db8::1", "48", 52).unwrap().host_bits.unwrap();
+        assert_eq!((found.network, found.offset, found.host_index), ("2001:db8::/48".parse().unwrap(), 1, Some(2)));
+        assert_eq!(by_prefix("2001:db8::", "48", 52).unwrap().host_bits, None);
+
+        let strict = calculate("2001:db8::1", "48", SubnetMode::ByPrefix, None, Some(52), vec![], HierarchyPlacement::Contiguous, None, HostBitsPolicy::Reject);
+        assert_eq!(strict.unwrap_err(), Ipv6InputError::HostBitsSet(Box::new(found)));
+        let used = find_free_subnets("2001:db8::", "48", "2001:db8::5/64", "/64", "1", FitStrategy::First, HostBitsPolicy::Reject);
+        assert!(used.is_err());
+    }
+
+    #[test]
+    fn splits_by_prefix_and_count() {
+        let result = by_prefix("2001:db8::", "48", 52).unwrap();
+        assert_eq!(result.total_subnets, 16);
+        assert_eq!(result.subnets[1].network, "2001:db8:0:1000::/52".parse::<Ipv6Net>().unwrap());
+
+        let by_count = calculate("2001:db8::", "/48", SubnetMode::BySubnets, Some(5), None, vec![], HierarchyPlacement::Contiguous, None, HostBitsPolicy::Normalize).unwrap();
+        assert_eq!(by_count.new_prefix, Some(51));
+        assert_eq!(by_count.total_subnets, 8);
+
+        assert_eq!(by_prefix("2001:db8::", "48", 48), Err(Ipv6InputError::PrefixOutOfRange { min: 49, max: 128 }));
+        assert_eq!(by_prefix("2001:db8::", "48", 129), Err(Ipv6InputError::PrefixOutOfRange { min: 49, max: 128 }));
+        assert_eq!(by_prefix("2001:db8::g", "48", 64), Err(Ipv6InputError::InvalidAddress("2001:db8::g".into())));
+        assert_eq!(by_prefix("2001:db8::", "129", 130), Err(Ipv6InputError::PrefixOutOfRange { min: 0, max: 128 }));
+        assert_eq!(
+            calculate("2001:db8::", "48", SubnetMode::NextFree, None, None, vec![], HierarchyPlacement::Contiguous, None, HostBitsPolicy::Normalize),
+            Err(Ipv6InputError::UnsupportedMode)
+        );
+    }
+
+    #[test]
+    fn truncates_long_listings_but_keeps_the_tail() {
+        let result = by_prefix("2001:db8::", "32", 64).unwrap();
+        assert_eq!(result.total_subnets, 1u128 << 32);
+        assert_eq!(result.subnets.len(), LIMIT);
+        assert_eq!(result.subnets.last().unwrap().network, "2001:db8:ffff:ffff::/64".parse::<Ipv6Net>().unwrap());
+    }
+
+    #[test]
+    fn plans_prefix_delegation_pools() {
+        let result = calculate("2001:db8::", "40", SubnetMode::PrefixDelegation, Some(1000), Some(56), vec![], HierarchyPlacement::Contiguous, Some(3), HostBitsPolicy::Normalize).unwrap();
+        let plan = result.pd_plan.unwrap();
+        assert_eq!(plan.pools.len(), 3);
+        assert_eq!(plan.pools.iter().map(|p| p.customers).sum::<u128>(), 1000);
+        assert_eq!(plan.pools[0].capacity, 16384); // Four /42 pools of 2^14 /56s each
+        assert_eq!(plan.lans_per_customer, 256);
+        assert!(plan.pools.iter().all(|p| p.prefix.contains(&p.first_delegated) && p.prefix.contains(&p.last_delegated)));
+
+        assert!(calculate("2001:db8::", "40", SubnetMode::PrefixDelegation, Some(1000), Some(48), vec![], HierarchyPlacement::Contiguous, Some(3), HostBitsPolicy::Normalize).is_err());
+    }
+
+    #[test]
+    fn hierarchy_reports_usage_and_reserved_space() {
+        let result = hierarchy(vec![level("Region", 3, 2), level("Site", 2, 4)], HierarchyPlacement::Contiguous).unwrap();
+        let tree = result.hierarchy.unwrap();
+        assert_eq!(tree.usage[0].used, 3);
+        assert_eq!(tree.usage[0].capacity, 4);
+        assert_eq!(tree.usage[1].used, 6);
+        assert_eq!(tree.usage[1].capacity, 48);
+        assert_eq!(tree.tree[0].reserved, vec!["2001:db8:0:c000::/50".parse::<Ipv6Net>().unwrap()]);
+
+        assert_eq!(
+            hierarchy(vec![level("Region", 5, 2)], HierarchyPlacement::Contiguous),
+            Err(Ipv6InputError::LevelTooSmall { index: 1, needed: 3, bits: 2 })
+        );
+        assert_eq!(
+            hierarchy(vec![level("A", 1, 60), level("B", 1, 30)], HierarchyPlacement::Contiguous),
+            Err(Ipv6InputError::NotEnoughBits { needed: 90, available: 80 })
+        );
+    }
+
+    #[test]
+    fn errors_explain_and_suggest() {
+        let v4 = by_prefix("10.0.0.0", "8", 16).unwrap_err();
+        assert_eq!(v4.suggestion().as_deref(), Some("That's an IPv4 address; use the IPv4 tab"));
+        let cidr = by_prefix("2001:db8::/32", "", 48).unwrap_err();
+        assert_eq!(cidr.suggestion().as_deref(), Some("Put the prefix in the prefix field: 2001:db8:: and /32"));
+
+        let level = hierarchy(vec![level("Region", 5, 2)], HierarchyPlacement::Contiguous).unwrap_err();
+        assert_eq!(level.to_string(), "Level 1 needs 3 bits for its subnets but only has 2");
+        assert_eq!(level.suggestion().as_deref(), Some("Give level 1 at least 3 bits"));
+
+        let split = calculate("2001:db8::", "126", SubnetMode::BySubnets, Some(8), None, vec![], HierarchyPlacement::Contiguous, None, HostBitsPolicy::Normalize).unwrap_err();
+        assert_eq!(split, Ipv6InputError::NotEnoughBits { needed: 3, available: 2 });
+        assert_eq!(split.suggestion().as_deref(), Some("Shorten the parent prefix by 1 bit"));
+    }
+
+    #[test]
+    fn hierarchy_rejects_degenerate_levels() {
+        // Found by the ipv6_hierarchy fuzz target: an empty level let a huge later level through
+        assert_eq!(
+            hierarchy(vec![level("A", 0, 32), level("B", 875_838_965, 32)], HierarchyPlacement::Contiguous),
+            Err(Ipv6InputError::InvalidHierarchyLevel { index: 1 })
+        );
+        assert_eq!(hierarchy(vec![level("A", 1, 0)], HierarchyPlacement::Contiguous), Err(Ipv6InputError::InvalidHierarchyLevel { index: 1 }));
+        assert_eq!(hierarchy(vec![level("A", 1, 128)], HierarchyPlacement::Contiguous), Err(Ipv6InputError::InvalidHierarchyLevel { index: 1 }));
+        assert_eq!(
+            hierarchy(vec![level("A", 200_000, 18)], HierarchyPlacement::Contiguous),
+            Err(Ipv6InputError::TooManySubnets { requested: 200_000, max: MAX_USABLE_SUBNETS })
+        );
+    }
+
+    #[test]
+    fn next_free_skips_used_prefixes() {
+        let result = find_free_subnets("2001:db8::", "48", "2001:db8::/64\n2001:db8:0:2::/63", "/64", "3", FitStrategy::First, HostBitsPolicy::Normalize).unwrap();
+        let found: Vec<String> = result.subnets.iter().map(|s| s.network.to_string()).collect();
+        assert_eq!(found, ["2001:db8:0:1::/64", "2001:db8:0:4::/64", "2001:db8:0:5::/64"]);
+        assert!(find_free_subnets("2001:db8::", "48", "10.0.0.0/8", "/64", "1", FitStrategy::First, HostBitsPolicy::Normalize).is_err());
+        assert_eq!(
+            find_free_subnets("2001:db8::", "48", "", "/40", "1", FitStrategy::First, HostBitsPolicy::Normalize),
+            Err(Ipv6InputError::PrefixOutOfRange { min: 48, max: 128 })
+        );
+    }
+
+    #[test]
+    fn sparse_children_start_aligned_gaps() {
+        for slots in (0..=6).map(|bits| 1u128 << bits) {
+            for num in 1..=slots {
+                let used = placement_slots(slots, num, HierarchyPlacement::Sparse);
+                let block = slots / num.next_power_of_two();
+                assert_eq!(used.len() as u128, num);
+                for (i, &slot) in used.iter().enumerate() {
+                    let next = used.get(i + 1).copied().unwrap_or(slots);
+                    assert_eq!(slot % block, 0, "{} of {}: slot {} not aligned to {}", num, slots, slot, block);
+                    assert!(next - slot >= block, "{} of {}: slot {} has no room to grow", num, slots, slot);
+                }
+            }
+        }
+        assert_eq!(placement_slots(16, 3, HierarchyPlacement::Sparse), [0, 4, 8]);
+    }
+
+    #[test]
+    fn golden_outputs() {
+        let split = by_prefix("2001:db8:abcd::", "48", 50).unwrap();
+        let mut out = String::new();
+        for s in &split.subnets {
+            out += &format!("{} {} {} {}-{}\n", s.network, s.expanded, s.addr_type, s.first_host, s.last_host);
+        }
+        assert_golden("ipv6_by_prefix.txt", &out);
+
+        let result = hierarchy(vec![level("Region", 2, 2), level("Site", 3, 4)], HierarchyPlacement::Sparse).unwrap();
+        let rows = flatten_hierarchy(result.hierarchy.as_ref().unwrap());
+        let mut out = String::new();
+        for row in rows {
+            let reserved: Vec<String> = row.reserved.iter().map(|n| n.to_string()).collect();
+            out += &format!("{} | {} | children {} spare {} | reserved {}\n", row.path, row.prefix, row.children, row.spare_capacity, reserved.join(" "));
+        }
+        assert_golden("ipv6_hierarchy_sparse.txt", &out);
+    }
+
+    proptest! {
+        #[test]
+        fn subnets_tile_the_base_network(addr in any::<u128>(), base in 0u8..=120, extra in 1u8..=16) {
+            let new = (base + extra).min(128);
+            prop_assume!(new > base);
+            let result = by_prefix(&Ipv6Addr::from(addr).to_string(), &base.to_string(), new).unwrap();
+            let parent = Ipv6Net::new(Ipv6Addr::from(addr), base).unwrap().trunc();
+
+            prop_assert_eq!(result.total_subnets, 1u128 << (new - base));
+            prop_assert_eq!(result.subnets.len() as u128, result.total_subnets.min(LIMIT as u128));
+            let size = 1u128 << (128 - new as u32);
+            for (i, pair) in result.subnets.windows(2).enumerate() {
+                prop_assert!(parent.contains(&pair[0].network));
+                let gap = u128::from(pair[1].network.network()) - u128::from(pair[0].network.network());
+                if i + 1 != LIMIT - LAST_N {
+                    prop_assert_eq!(gap, size);
+                } else {
+                    prop_assert!(gap >= size);
+                }
+            }
+            prop_assert_eq!(result.subnets[0].network.network(), parent.network());
+            prop_assert_eq!(result.subnets.last().unwrap().network.broadcast(), parent.broadcast());
+        }
+
+        #[test]
+        fn tail_matches_the_iterator(base in 32u8..=100, extra in 12u8..=16) {
+            let new = base + extra;
+            let result = by_prefix("2001:db8::", &base.to_string(), new).unwrap();
+            let parent = Ipv6Net::new("2001:db8::".parse().unwrap(), base).unwrap();
+            let all: Vec<Ipv6Net> = parent.subnets(new).unwrap().collect();
+            let shown: Vec<Ipv6Net> = result.subnets.iter().map(|s| s.network).collect();
+            if all.len() <= LIMIT {
+                prop_assert_eq!(shown, all);
+            } else {
+                prop_assert_eq!(&shown[..LIMIT - LAST_N], &all[..LIMIT - LAST_N]);
+                prop_assert_eq!(&shown[LIMIT - LAST_N..], &all[all.len() - LAST_N..]);
+            }
+        }
+
+        #[test]
+        fn hierarchy_children_stay_inside_parents(
+            levels in proptest::collection::vec((1u32..=40, 1u8..=6), 1..=3),
+            sparse in any::<bool>(),
+        ) {
+            let levels: Vec<HierarchyLevel> = levels.into_iter().enumerate().map(|(i, (num, bits))| level(&format!("L{}", i), num, bits)).collect();
+            let placement = if sparse { HierarchyPlacement::Sparse } else { HierarchyPlacement::Contiguous };
+            match hierarchy(levels.clone(), placement) {
+                Ok(result) => {
+                    prop_assert!(levels.iter().all(|l| l.num <= 1 << l.bits));
+                    let tree = result.hierarchy.unwrap();
+                    check_tree(&tree.tree[0])?;
+                    for (usage, level) in tree.usage.iter().zip(&levels) {
+                        prop_assert!(usage.used <= usage.capacity);
+                        prop_assert_eq!(usage.capacity % (1u128 << level.bits), 0);
+                    }
+                }
+                Err(e) => {
+                    let too_small = matches!(e, Ipv6InputError::LevelTooSmall { .. });
+                    prop_assert!(too_small);
+                    prop_assert!(levels.iter().any(|l| l.num > 1 << l.bits));
+                }
+            }
+        }
+    }
+}
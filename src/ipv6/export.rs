@@ -0,0 +1,118 @@
+// src/ipv6/export.rs
+use crate::ipv6::types::{HierarchyExportFormat, HierarchyRow, PdPlan, PdPoolFormat};
+use crate::util::csv_field;
+
+impl HierarchyExportFormat {
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            HierarchyExportFormat::Csv => "hierarchy.csv",
+            HierarchyExportFormat::Json => "hierarchy.json",
+            HierarchyExportFormat::NetBox => "netbox-prefixes.csv",
+        }
+    }
+
+    pub fn mime(&self) -> &'static str {
+        match self {
+            HierarchyExportFormat::Json => "application/json",
+            _ => "text/csv",
+        }
+    }
+}
+
+pub fn export_hierarchy(rows: &[HierarchyRow], format: HierarchyExportFormat) -> String {
+    match format {
+        HierarchyExportFormat::Csv => to_csv(rows),
+        HierarchyExportFormat::Json => serde_json::to_string_pretty(rows).unwrap_or_default(),
+        HierarchyExportFormat::NetBox => to_netbox_csv(rows),
+    }
+}
+
+fn to_csv(rows: &[HierarchyRow]) -> String {
+    let mut out = String::from("path,depth,prefix,first_address,last_address,parent,children,spare_capacity,reserved\n");
+    for row in rows {
+        let reserved: Vec<String> = row.reserved.iter().map(|r| r.to_string()).collect();
+        out.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{}\n",
+            csv_field(&row.path),
+            row.depth,
+            row.prefix,
+            row.first_address,
+            row.last_address,
+            row.parent.map(|p| p.to_string()).unwrap_or_default(),
+            row.children,
+            row.spare_capacity,
+            reserved.join(" "),
+        ));
+    }
+    out
+}
+
+// NetBox bulk import (IPAM > Prefixes > Import): nodes with children become containers,
+// and space held back for growth is imported as reserved prefixes
+fn to_netbox_csv(rows: &[HierarchyRow]) -> String {
+    let mut out = String::from("prefix,status,description\n");
+    for row in rows {
+        let status = if row.children > 0 { "container" } else { "active" };
+        out.push_str(&format!("{},{},{}\n", row.prefix, status, csv_field(&row.path)));
+        for reserved in &row.reserved {
+            out.push_str(&format!("{},reserved,{}\n", reserved, csv_field(&format!("{} / spare", row.path))));
+        }
+    }
+    out
+}
+
+impl PdPoolFormat {
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            PdPoolFormat::Kea => "kea-pd-pools.json",
+            PdPoolFormat::CiscoIos => "pd-pools-ios.txt",
+            PdPoolFormat::IscDhcpd => "pd-pools-dhcpd.conf",
+        }
+    }
+
+    pub fn mime(&self) -> &'static str {
+        match self {
+            PdPoolFormat::Kea => "application/json",
+            _ => "text/plain",
+        }
+    }
+}
+
+/// Pool definitions for each BNG/router, ready to paste into the DHCPv6 server config
+pub fn pd_pool_definitions(plan: &PdPlan, format: PdPoolFormat) -> String {
+    match format {
+        PdPoolFormat::Kea => {
+            let pools: Vec<_> = plan
+                .pools
+                .iter()
+                .map(|pool| {
+                    serde_json::json!({
+                        "prefix": pool.prefix.network().to_string(),
+                        "prefix-len": pool.prefix.prefix_len(),
+                        "delegated-len": plan.delegated_prefix,
+                        "user-context": { "name": pool.name },
+                    })
+                })
+                .collect();
+            serde_json::to_string_pretty(&serde_json::json!({ "pd-pools": pools })).unwrap_or_default()
+        }
+        PdPoolFormat::CiscoIos => plan
+            .pools
+            .iter()
+            .map(|pool| format!("ipv6 local pool {} {} {}\n", pool.name, pool.prefix, plan.delegated_prefix))
+            .collect(),
+        PdPoolFormat::IscDhcpd => plan
+            .pools
+            .iter()
+            .map(|pool| {
+                format!(
+                    "# {}\nprefix6 {} {} /{};\n",
+                    pool.name,
+                    pool.first_delegated.network(),
+                    pool.last_delegated.network(),
+                    plan.delegated_prefix
+                )
+            })
+            .collect(),
+    }
+}
@@ -1,6 +1,10 @@
 use dioxus::prelude::*;
-use crate::ipv6::types::{CalculationResult, HierarchyLevel, Ipv6InputError, MAX_USABLE_SUBNETS, PREFIX_OPTIONS, SubnetMode};
-use crate::ipv6::calculator::calculate;
+use crate::ipv6::types::{CalculationResult, HierarchyLevel, HierarchyPlacement, Ipv6InputError, MAX_USABLE_SUBNETS, PREFIX_OPTIONS, SubnetMode};
+use crate::address_math::HostBitsPolicy;
+use crate::components::{FreeSearchFields, HostBitsToggle};
+use crate::free_space::FitStrategy;
+use crate::ipv6::calculator::{calculate, find_free_subnets};
+use crate::ipv6::ula::generate_ula;
 
 #[component]
 pub fn InputPanel(
@@ -9,14 +13,22 @@ pub fn InputPanel(
     mode: Signal<SubnetMode>,
     count_input: Signal<String>,
     child_prefix_input: Signal<String>,
+    pool_count_input: Signal<String>,
     hierarchy_levels: Signal<Vec<HierarchyLevel>>,
+    placement: Signal<HierarchyPlacement>,
+    used_input: Signal<String>,
+    size_input: Signal<String>,
+    strategy: Signal<FitStrategy>,
+    host_bits_policy: Signal<HostBitsPolicy>,
     result: Signal<Option<Result<CalculationResult, Ipv6InputError>>>,
 ) -> Element {
     let current_mode = mode.read().clone();
     let is_by_subnets = current_mode == SubnetMode::BySubnets;
     let is_by_prefix = current_mode == SubnetMode::ByPrefix;
     let is_by_hierarchy = current_mode == SubnetMode::ByHierarchy;
+    let is_pd = current_mode == SubnetMode::PrefixDelegation;
     let is_inspect = current_mode == SubnetMode::Inspect;
+    let is_next_free = current_mode == SubnetMode::NextFree;
     let total_usable_subnets = if is_by_hierarchy && !hierarchy_levels.read().is_empty() {
         hierarchy_levels.read().iter().fold(1u128, |acc, l| acc * l.num as u128)
     } else {
@@ -59,6 +71,18 @@ pub fn InputPanel(
     // New: Signals for current (single) hierarchy level inputs - always visible in hierarchy mode
     let mut current_label = use_signal(|| String::new());
     let mut current_bits = use_signal(|| 0u8); // 0 means no selection
+    let mut current_used = use_signal(String::new); // Empty means every block is used
+
+    // Blocks actually used at the new level; fewer than 2^bits leaves spare capacity
+    let current_num = {
+        let slots = 1u32 << *current_bits.read();
+        let used = current_used.read();
+        if used.trim().is_empty() {
+            Some(slots)
+        } else {
+            used.trim().parse::<u32>().ok().filter(|&n| n >= 1 && n <= slots)
+        }
+    };
 
     // New: Compute sum of bits in existing levels
     let sum_previous_bits: u32 = hierarchy_levels.read().iter().map(|l| l.bits as u32).sum();
@@ -76,7 +100,12 @@ pub fn InputPanel(
     //Disable calculation button
     let is_disabled = match current_mode {
         SubnetMode::BySubnets => count_input.with(|input| input.trim().parse::<u32>().map_or(true, |n| n < 1)),
-        SubnetMode::ByPrefix => child_prefix_input.with(|input| input.trim().parse::<u8>().map_or(true, |p| p > 64)),
+        SubnetMode::ByPrefix => child_prefix_input.with(|input| input.trim().parse::<u8>().map_or(true, |p| p > 128)),
+        SubnetMode::PrefixDelegation => {
+            child_prefix_input.with(|input| input.trim().parse::<u8>().map_or(true, |p| p > 128))
+                || count_input.with(|input| input.trim().parse::<u32>().map_or(true, |n| n < 1))
+                || pool_count_input.with(|input| input.trim().parse::<u32>().map_or(true, |n| n < 1))
+        }
         SubnetMode::ByHierarchy => {
             //let levels = hierarchy_levels.read();
             /* 
@@ -87,6 +116,7 @@ pub fn InputPanel(
             
 
         }
+        SubnetMode::NextFree => size_input.read().trim().is_empty(),
         _ => false,
     };
 
@@ -109,6 +139,7 @@ pub fn InputPanel(
                     value: "{addr_input}",
                     oninput: move |e| addr_input.set(e.value())
                 }
+                UlaGenerator { addr_input, prefix_input }
             }
 
             // Prefix length (using the provided code snippet, adapted)
@@ -148,6 +179,8 @@ pub fn InputPanel(
                             "subnets" => SubnetMode::BySubnets,
                             "prefix" => SubnetMode::ByPrefix,
                             "hierarchy" => SubnetMode::ByHierarchy,
+                            "pd" => SubnetMode::PrefixDelegation,
+                            "next-free" => SubnetMode::NextFree,
                             _ => SubnetMode::Inspect,
                         });
                     },
@@ -155,9 +188,13 @@ pub fn InputPanel(
                     option { value: "subnets", selected: is_by_subnets, "Number of subnets" }
                     option { value: "prefix", selected: is_by_prefix, "By prefix" }
                     option { value: "hierarchy", selected: is_by_hierarchy, "By hierarchy" }
+                    option { value: "pd", selected: is_pd, "Prefix delegation (DHCPv6-PD)" }
+                    option { value: "next-free", selected: is_next_free, "Next free subnet" }
                 }
             }
 
+            HostBitsToggle { policy: host_bits_policy }
+
             // Dynamic Fields based on Mode
             if is_by_subnets {
                 div { class: "mb-6",
@@ -186,6 +223,52 @@ pub fn InputPanel(
                     }
                 }
             }
+            if is_pd {
+                div { class: "mb-6",
+                    label { class: "block text-sm font-medium mb-2", "Delegated Prefix Length" }
+                    input {
+                        r#type: "number",
+                        min: "1",
+                        max: "64",
+                        class: "w-full text-base px-4 py-3 border rounded-lg bg-gray-700",
+                        placeholder: "e.g. 56",
+                        value: "{child_prefix_input}",
+                        oninput: move |e| child_prefix_input.set(e.value())
+                    }
+                }
+                div { class: "mb-6",
+                    label { class: "block text-sm font-medium mb-2", "Number of Customers" }
+                    input {
+                        r#type: "number",
+                        min: "1",
+                        class: "w-full text-base px-4 py-3 border rounded-lg bg-gray-700",
+                        placeholder: "e.g. 20000",
+                        value: "{count_input}",
+                        oninput: move |e| count_input.set(e.value())
+                    }
+                }
+                div { class: "mb-6",
+                    label { class: "block text-sm font-medium mb-2", "Number of Pools (BNGs / Routers)" }
+                    input {
+                        r#type: "number",
+                        min: "1",
+                        class: "w-full text-base px-4 py-3 border rounded-lg bg-gray-700",
+                        placeholder: "e.g. 4",
+                        value: "{pool_count_input}",
+                        oninput: move |e| pool_count_input.set(e.value())
+                    }
+                }
+            }
+            if is_next_free {
+                FreeSearchFields {
+                    used_input,
+                    size_input,
+                    count_input,
+                    strategy,
+                    used_placeholder: "2001:db8:0:1::/64\n2001:db8:0:10::/60",
+                    size_placeholder: "e.g. /64",
+                }
+            }
             if is_by_hierarchy {
                          // NEW: Remaining bits indicator
                         div { class: "mb-2 text-xs text-left text-gray-400",
@@ -228,6 +311,22 @@ pub fn InputPanel(
                             current_bits,
                             max_available_bits: max_available_bits as u8
                         }
+                        div { class: "mb-2",
+                            label { class: "block text-xs text-left font-medium mb-2", "Subnets Used (optional)" }
+                            input {
+                                r#type: "number",
+                                min: "1",
+                                class: "flex px-4 py-2 text-sm text-left border rounded-lg bg-gray-700 hide-number-spinner",
+                                placeholder: "all",
+                                value: "{current_used}",
+                                oninput: move |e| current_used.set(e.value())
+                            }
+                            if *current_bits.read() > 0 && current_num.is_none() {
+                                p { class: "mt-1 text-xs text-left text-red-400",
+                                    "Must be between 1 and {1u32 << *current_bits.read()}"
+                                }
+                            }
+                        }
 
                         // New: Buttons for add/remove
                         div { class: "mb-4",
@@ -244,7 +343,8 @@ pub fn InputPanel(
 
                                     let is_disabled = max_available_bits == 0 
                                         || *current_bits.read() == 0 
-                                        || current_label.read().is_empty();
+                                        || current_label.read().is_empty()
+                                        || current_num.is_none();
 
                                     if is_disabled {
                                         classes.push("opacity-50");
@@ -253,16 +353,19 @@ pub fn InputPanel(
 
                                     classes.join(" ")
                                 },
-                                disabled: max_available_bits == 0 || *current_bits.read() == 0 || current_label.read().is_empty(),
+                                disabled: max_available_bits == 0 || *current_bits.read() == 0 || current_label.read().is_empty() || current_num.is_none(),
                                 onclick: move |_| {
                                     if *current_bits.read() > 0 && !current_label.read().is_empty() {
-                                        hierarchy_levels.write().push(HierarchyLevel {
-                                            name: current_label.read().clone(),
-                                            num: 1u32 << *current_bits.read(),
-                                            bits: *current_bits.read(),
-                                        });
-                                        current_label.set("".to_string());
-                                        current_bits.set(0);
+                                        if let Some(num) = current_num {
+                                            hierarchy_levels.write().push(HierarchyLevel {
+                                                name: current_label.read().clone(),
+                                                num,
+                                                bits: *current_bits.read(),
+                                            });
+                                            current_label.set("".to_string());
+                                            current_bits.set(0);
+                                            current_used.set("".to_string());
+                                        }
                                     }
                                 },
                                 "Add Level"
@@ -323,6 +426,15 @@ pub fn InputPanel(
                                 }
                             }
                         }
+                        label { class: "flex items-center mb-2 text-sm text-left",
+                            input {
+                                r#type: "checkbox",
+                                class: "mr-2",
+                                checked: *placement.read() == HierarchyPlacement::Sparse,
+                                onchange: move |e| placement.set(if e.checked() { HierarchyPlacement::Sparse } else { HierarchyPlacement::Contiguous })
+                            }
+                            "Spread used subnets sparsely (room for per-branch growth)"
+                        }
                         // NEW: Display total usable subnets from hierarchy
                         if total_usable_subnets > 0 {
                             p { class: "mt-4 text-center text-sm text-gray-500",
@@ -346,10 +458,15 @@ pub fn InputPanel(
                     class: "{button_classes}",
                     disabled: is_disabled,
                     onclick: move |_| if !is_disabled {
-                        let needed_subnets = if is_by_subnets { count_input.read().parse().ok() } else { None };
-                        let child_prefix = if is_by_prefix { child_prefix_input.read().parse().ok() } else { None };
+                        if is_next_free {
+                            result.set(Some(find_free_subnets(&addr_input(), &prefix_input(), &used_input(), &size_input(), &count_input(), strategy(), host_bits_policy())));
+                            return;
+                        }
+                        let needed_subnets = if is_by_subnets || is_pd { count_input.read().parse().ok() } else { None };
+                        let child_prefix = if is_by_prefix || is_pd { child_prefix_input.read().parse().ok() } else { None };
+                        let pd_pools = if is_pd { pool_count_input.read().parse().ok() } else { None };
                         let levels = hierarchy_levels.read().clone();
-                        let res = calculate(&addr_input(), &prefix_input(), current_mode.clone(), needed_subnets, child_prefix, levels);
+                        let res = calculate(&addr_input(), &prefix_input(), current_mode.clone(), needed_subnets, child_prefix, levels, placement(), pd_pools, host_bits_policy());
                         result.set(Some(res));
                     },
                     "Calculate"
@@ -362,7 +479,13 @@ pub fn InputPanel(
                         mode.set(SubnetMode::Inspect);
                         count_input.set("".to_string());
                         child_prefix_input.set("".to_string());
+                        pool_count_input.set("1".to_string());
                         hierarchy_levels.set(vec![]);
+                        placement.set(HierarchyPlacement::Contiguous);
+                        used_input.set("".to_string());
+                        size_input.set("".to_string());
+                        strategy.set(FitStrategy::First);
+                        host_bits_policy.set(HostBitsPolicy::Normalize);
                         result.set(None);
                     },
                     "Clear"
@@ -450,4 +573,60 @@ pub fn SubnetBitsDropdown(
             }
         }
     }
-}
\ No newline at end of file
+}
+
+/// RFC 4193 generator that fills the address and prefix fields with a fresh ULA /48
+#[component]
+pub fn UlaGenerator(addr_input: Signal<String>, prefix_input: Signal<String>) -> Element {
+    let mut is_open = use_signal(|| false);
+    let mut seed = use_signal(String::new);
+    let mut mac = use_signal(String::new);
+    let mut message = use_signal(|| None::<Result<String, String>>);
+
+    rsx! {
+        div { class: "mt-2 text-left text-sm",
+            button {
+                class: "text-blue-400 hover:underline",
+                onclick: move |_| is_open.toggle(),
+                if is_open() { "Hide ULA generator" } else { "Generate a Unique Local (ULA) /48…" }
+            }
+            if is_open() {
+                div { class: "mt-2 p-3 border border-gray-600 rounded-lg space-y-2",
+                    input {
+                        r#type: "text",
+                        class: "w-full px-3 py-2 text-sm border rounded-lg bg-gray-700",
+                        placeholder: "Seed (optional, makes the result reproducible)",
+                        value: "{seed}",
+                        oninput: move |e| seed.set(e.value())
+                    }
+                    input {
+                        r#type: "text",
+                        class: "w-full px-3 py-2 text-sm border rounded-lg bg-gray-700",
+                        placeholder: "MAC address for EUI-64 (optional)",
+                        value: "{mac}",
+                        oninput: move |e| mac.set(e.value())
+                    }
+                    button {
+                        class: "text-sm bg-green-500 px-2 py-1 rounded",
+                        onclick: move |_| {
+                            match generate_ula(Some(&seed()), Some(&mac())) {
+                                Ok(ula) => {
+                                    addr_input.set(ula.prefix.network().to_string());
+                                    prefix_input.set("/48".to_string());
+                                    message.set(Some(Ok(format!("Global ID {:010x} ({})", ula.global_id, ula.source))));
+                                }
+                                Err(err) => message.set(Some(Err(err.to_string()))),
+                            }
+                        },
+                        "Generate"
+                    }
+                    match message() {
+                        Some(Ok(msg)) => rsx! { p { class: "text-xs text-gray-400 break-all", "{msg}" } },
+                        Some(Err(msg)) => rsx! { p { class: "text-xs text-red-400", "{msg}" } },
+                        None => rsx! {},
+                    }
+                }
+            }
+        }
+    }
+}
@@ -2,12 +2,23 @@ pub mod calculator;
 pub mod types;
 pub mod input_panel;
 pub mod results_panel;
+pub mod export;
+pub mod ula;
+pub mod registry;
+pub mod multicast;
 
 use dioxus::prelude::*;
-use crate::ipv6::types::{CalculationResult, Ipv6InputError, HierarchyLevel, SubnetMode};
+use ipnet::Ipv6Net;
+use crate::address_math::HostBitsPolicy;
+use crate::free_space::FitStrategy;
+use crate::ipv6::types::{CalculationResult, Ipv6InputError, HierarchyLevel, HierarchyPlacement, SubnetMode};
 use crate::ipv6::input_panel::InputPanel;
 use crate::ipv6::results_panel::ResultsPanel;
 
+/// Base network of the latest IPv6 calculation, shared so other tabs can pair IPv4 plans with it
+#[derive(Clone, Copy)]
+pub struct SharedIpv6Network(pub Signal<Option<Ipv6Net>>);
+
 #[component]
 pub fn Ipv6Tab() -> Element {
     // Signals for shared state between input and results
@@ -16,9 +27,22 @@ pub fn Ipv6Tab() -> Element {
     let mode = use_signal(|| SubnetMode::Inspect);
     let count_input = use_signal(|| "".to_string()); // For BySubnets mode
     let child_prefix_input = use_signal(|| "".to_string()); // For ByPrefix mode
+    let pool_count_input = use_signal(|| "1".to_string()); // For PrefixDelegation mode
     let hierarchy_levels = use_signal(|| vec![] as Vec<HierarchyLevel>); // For ByHierarchy
+    let placement = use_signal(HierarchyPlacement::default); // For ByHierarchy
+    let used_input = use_signal(|| "".to_string()); // For NextFree mode
+    let size_input = use_signal(|| "".to_string()); // For NextFree mode
+    let strategy = use_signal(|| FitStrategy::First); // For NextFree mode
+    let host_bits_policy = use_signal(HostBitsPolicy::default);
     let result = use_signal(|| None::<Result<CalculationResult, Ipv6InputError>>);
 
+    let SharedIpv6Network(mut shared) = use_context::<SharedIpv6Network>();
+    use_effect(move || {
+        if let Some(Ok(calc)) = &*result.read() {
+            shared.set(Some(calc.base_network));
+        }
+    });
+
     rsx! {
         div { class: "grid grid-cols-3 gap-4",
             InputPanel {
@@ -27,7 +51,13 @@ pub fn Ipv6Tab() -> Element {
                 mode,
                 count_input,
                 child_prefix_input,
+                pool_count_input,
                 hierarchy_levels,
+                placement,
+                used_input,
+                size_input,
+                strategy,
+                host_bits_policy,
                 result
             }
             ResultsPanel { result: result.read().clone(), hierarchy_levels}
@@ -0,0 +1,153 @@
+// src/ipv6/multicast.rs
+use ipnet::Ipv6Net;
+use std::net::Ipv6Addr;
+use crate::ipv6::types::{MulticastInfo, PrefixBasedGroup};
+
+// Well-known groups with a fixed scope (RFC 4291 and the IANA IPv6 multicast registry)
+const FIXED_GROUPS: &[(Ipv6Addr, &str)] = &[
+    (Ipv6Addr::new(0xff01, 0, 0, 0, 0, 0, 0, 1), "All Nodes (interface-local)"),
+    (Ipv6Addr::new(0xff01, 0, 0, 0, 0, 0, 0, 2), "All Routers (interface-local)"),
+    (Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 0, 1), "All Nodes"),
+    (Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 0, 2), "All Routers"),
+    (Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 0, 4), "DVMRP Routers"),
+    (Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 0, 5), "OSPFv3 AllSPFRouters"),
+    (Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 0, 6), "OSPFv3 AllDRouters"),
+    (Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 0, 9), "RIPng Routers"),
+    (Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 0, 0xa), "EIGRP Routers"),
+    (Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 0, 0xd), "All PIM Routers"),
+    (Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 0, 0x12), "VRRP"),
+    (Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 0, 0x16), "All MLDv2-capable Routers"),
+    (Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 0, 0x1a), "All RPL Nodes"),
+    (Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 1, 2), "All DHCP Relay Agents and Servers"),
+    (Ipv6Addr::new(0xff02, 0, 0, 0, 0, 0, 1, 3), "LLMNR"),
+    (Ipv6Addr::new(0xff05, 0, 0, 0, 0, 0, 0, 2), "All Routers (site-local)"),
+    (Ipv6Addr::new(0xff05, 0, 0, 0, 0, 0, 1, 3), "All DHCP Servers"),
+];
+
+// Variable-scope groups (ff0X::group), identified by the low 112 bits
+const VARIABLE_GROUPS: &[(u128, &str)] = &[
+    (0xfb, "mDNSv6"),
+    (0x101, "Network Time Protocol (NTP)"),
+    (0x181, "PTP Primary"),
+    (0x18c, "All ACs (CAPWAP)"),
+];
+
+pub fn scope_name(scope: u8) -> &'static str {
+    match scope {
+        0x1 => "Interface-Local",
+        0x2 => "Link-Local",
+        0x3 => "Realm-Local",
+        0x4 => "Admin-Local",
+        0x5 => "Site-Local",
+        0x8 => "Organization-Local",
+        0xe => "Global",
+        0x0 | 0xf => "Reserved",
+        _ => "Unassigned",
+    }
+}
+
+fn well_known_group(addr: Ipv6Addr, flag_t: bool) -> Option<&'static str> {
+    if let Some((_, name)) = FIXED_GROUPS.iter().find(|(group, _)| *group == addr) {
+        return Some(name);
+    }
+    let segments = addr.segments();
+    // Solicited-node: ff02::1:ff00:0/104
+    if segments[..6] == [0xff02, 0, 0, 0, 0, 1] && segments[6] >> 8 == 0xff {
+        return Some("Solicited-Node");
+    }
+    if flag_t {
+        return None;
+    }
+    let group_id = u128::from(addr) & ((1u128 << 112) - 1);
+    VARIABLE_GROUPS.iter().find(|(id, _)| *id == group_id).map(|(_, name)| *name)
+}
+
+/// Decode flags, scope, RFC 3306/3956 fields, well-known name and Ethernet MAC; `None` if not multicast
+pub fn decode_multicast(addr: Ipv6Addr) -> Option<MulticastInfo> {
+    if !addr.is_multicast() {
+        return None;
+    }
+    let octets = addr.octets();
+    let flags = octets[1] >> 4;
+    let scope = octets[1] & 0x0f;
+    let (flag_r, flag_p, flag_t) = (flags & 0x4 != 0, flags & 0x2 != 0, flags & 0x1 != 0);
+
+    let prefix_based = flag_p.then(|| {
+        let plen = octets[3].min(64);
+        let mut prefix_octets = [0u8; 16];
+        prefix_octets[..8].copy_from_slice(&octets[4..12]);
+        PrefixBasedGroup {
+            riid: octets[2] & 0x0f,
+            plen,
+            network_prefix: Ipv6Net::new(Ipv6Addr::from(prefix_octets), plen).unwrap().trunc(),
+            group_id: u32::from_be_bytes([octets[12], octets[13], octets[14], octets[15]]),
+            // ff3X::/32 is the Source-Specific Multicast range (RFC 4607)
+            is_ssm: !flag_r && octets[2..12].iter().all(|&b| b == 0),
+        }
+    });
+
+    // RFC 3956: RP address is the network prefix with the RIID in the last nibble
+    let embedded_rp = prefix_based
+        .as_ref()
+        .filter(|_| flag_r)
+        .map(|group| Ipv6Addr::from(u128::from(group.network_prefix.network()) | group.riid as u128));
+
+    Some(MulticastInfo {
+        flag_r,
+        flag_p,
+        flag_t,
+        scope,
+        scope_name: scope_name(scope),
+        prefix_based,
+        embedded_rp,
+        well_known: well_known_group(addr, flag_t),
+        // RFC 2464: 33:33 followed by the low 32 bits of the group
+        mac: format!("33:33:{:02x}:{:02x}:{:02x}:{:02x}", octets[12], octets[13], octets[14], octets[15]),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(s: &str) -> MulticastInfo {
+        decode_multicast(s.parse().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn ignores_unicast() {
+        assert_eq!(decode_multicast("2001:db8::1".parse().unwrap()), None);
+    }
+
+    #[test]
+    fn names_well_known_groups() {
+        let all_nodes = decode("ff02::1");
+        assert_eq!(all_nodes.scope_name, "Link-Local");
+        assert_eq!(all_nodes.well_known, Some("All Nodes"));
+        assert_eq!(all_nodes.mac, "33:33:00:00:00:01");
+        assert_eq!(decode("ff02::1:ff12:3456").well_known, Some("Solicited-Node"));
+        assert_eq!(decode("ff05::101").well_known, Some("Network Time Protocol (NTP)"));
+        // Transient groups are never the permanently assigned ones
+        assert_eq!(decode("ff15::101").well_known, None);
+        assert_eq!(decode("ff0e::1").scope_name, "Global");
+    }
+
+    #[test]
+    fn decodes_prefix_based_and_ssm_groups() {
+        let group = decode("ff3e:30:2001:db8::1234");
+        let prefix = group.prefix_based.unwrap();
+        assert_eq!(prefix.plen, 48);
+        assert_eq!(prefix.network_prefix, "2001:db8::/48".parse().unwrap());
+        assert_eq!(prefix.group_id, 0x1234);
+        assert!(!prefix.is_ssm);
+
+        assert!(decode("ff3e::8000:1").prefix_based.unwrap().is_ssm);
+    }
+
+    #[test]
+    fn extracts_the_embedded_rp() {
+        let group = decode("ff7e:140:2001:db8:beef::1234");
+        assert!(group.flag_r && group.flag_p && group.flag_t);
+        assert_eq!(group.embedded_rp, Some("2001:db8:beef::1".parse().unwrap()));
+    }
+}
@@ -0,0 +1,137 @@
+// src/ipv6/registry.rs
+use std::net::Ipv6Addr;
+
+/// One block of the IANA IPv6 Special-Purpose Address Registry, plus the
+/// architectural ranges from RFC 4291 so every address classifies to something.
+/// `None` flags are "N/A" in the registry (or depend on scope, for multicast).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AddressBlock {
+    pub address: Ipv6Addr,
+    pub prefix_len: u8,
+    pub name: &'static str,
+    pub rfc: &'static str,
+    pub source: Option<bool>,
+    pub destination: Option<bool>,
+    pub forwardable: Option<bool>,
+    pub global: Option<bool>,
+    pub reserved_by_protocol: Option<bool>,
+}
+
+impl AddressBlock {
+    pub fn contains(&self, addr: Ipv6Addr) -> bool {
+        let mask = u128::MAX.checked_shl(128 - self.prefix_len as u32).unwrap_or(0);
+        u128::from(addr) & mask == u128::from(self.address)
+    }
+}
+
+const fn block(
+    address: Ipv6Addr,
+    prefix_len: u8,
+    name: &'static str,
+    rfc: &'static str,
+    flags: [Option<bool>; 5],
+) -> AddressBlock {
+    AddressBlock {
+        address,
+        prefix_len,
+        name,
+        rfc,
+        source: flags[0],
+        destination: flags[1],
+        forwardable: flags[2],
+        global: flags[3],
+        reserved_by_protocol: flags[4],
+    }
+}
+
+const T: Option<bool> = Some(true);
+const F: Option<bool> = Some(false);
+const NA: Option<bool> = None;
+
+// Flags are [source, destination, forwardable, globally reachable, reserved-by-protocol]
+pub const SPECIAL_PURPOSE: &[AddressBlock] = &[
+    // Registry entries
+    block(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1), 128, "Loopback Address", "RFC 4291", [F, F, F, F, T]),
+    block(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 0), 128, "Unspecified Address", "RFC 4291", [T, F, F, F, T]),
+    block(Ipv6Addr::new(0, 0, 0, 0, 0, 0xffff, 0, 0), 96, "IPv4-mapped Address", "RFC 4291", [F, F, F, F, T]),
+    block(Ipv6Addr::new(0x64, 0xff9b, 0, 0, 0, 0, 0, 0), 96, "IPv4-IPv6 Translation (Well-Known Prefix)", "RFC 6052", [T, T, T, T, F]),
+    block(Ipv6Addr::new(0x64, 0xff9b, 1, 0, 0, 0, 0, 0), 48, "IPv4-IPv6 Translation (Local-Use)", "RFC 8215", [T, T, T, F, F]),
+    block(Ipv6Addr::new(0x100, 0, 0, 0, 0, 0, 0, 0), 64, "Discard-Only Address Block", "RFC 6666", [T, T, T, F, F]),
+    block(Ipv6Addr::new(0x100, 0, 0, 1, 0, 0, 0, 0), 64, "Dummy IPv6 Prefix", "RFC 9780", [T, T, F, F, F]),
+    block(Ipv6Addr::new(0x2001, 0, 0, 0, 0, 0, 0, 0), 23, "IETF Protocol Assignments", "RFC 2928", [F, F, F, F, F]),
+    block(Ipv6Addr::new(0x2001, 0, 0, 0, 0, 0, 0, 0), 32, "TEREDO", "RFC 4380, RFC 8190", [T, T, T, NA, NA]),
+    block(Ipv6Addr::new(0x2001, 1, 0, 0, 0, 0, 0, 1), 128, "Port Control Protocol Anycast", "RFC 7723", [T, T, T, T, F]),
+    block(Ipv6Addr::new(0x2001, 1, 0, 0, 0, 0, 0, 2), 128, "TURN Anycast", "RFC 8155", [T, T, T, T, F]),
+    block(Ipv6Addr::new(0x2001, 1, 0, 0, 0, 0, 0, 3), 128, "DNS-SD Service Registration Protocol Anycast", "RFC 9665", [T, T, T, T, F]),
+    block(Ipv6Addr::new(0x2001, 2, 0, 0, 0, 0, 0, 0), 48, "Benchmarking", "RFC 5180", [T, T, T, F, F]),
+    block(Ipv6Addr::new(0x2001, 3, 0, 0, 0, 0, 0, 0), 32, "AMT", "RFC 7450", [T, T, T, T, F]),
+    block(Ipv6Addr::new(0x2001, 4, 0x112, 0, 0, 0, 0, 0), 48, "AS112-v6", "RFC 7535", [T, T, T, T, F]),
+    block(Ipv6Addr::new(0x2001, 0x10, 0, 0, 0, 0, 0, 0), 28, "Deprecated (previously ORCHID)", "RFC 4843", [NA, NA, NA, NA, NA]),
+    block(Ipv6Addr::new(0x2001, 0x20, 0, 0, 0, 0, 0, 0), 28, "ORCHIDv2", "RFC 7343", [T, T, T, T, F]),
+    block(Ipv6Addr::new(0x2001, 0x30, 0, 0, 0, 0, 0, 0), 28, "Drone Remote ID Protocol Entity Tags (DETs)", "RFC 9374", [T, T, T, T, F]),
+    block(Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0), 32, "Documentation", "RFC 3849", [F, F, F, F, F]),
+    block(Ipv6Addr::new(0x2002, 0, 0, 0, 0, 0, 0, 0), 16, "6to4", "RFC 3056", [T, T, T, NA, F]),
+    block(Ipv6Addr::new(0x2620, 0x4f, 0x8000, 0, 0, 0, 0, 0), 48, "Direct Delegation AS112 Service", "RFC 7534", [T, T, T, T, F]),
+    block(Ipv6Addr::new(0x3fff, 0, 0, 0, 0, 0, 0, 0), 20, "Documentation", "RFC 9637", [F, F, F, F, F]),
+    block(Ipv6Addr::new(0x5f00, 0, 0, 0, 0, 0, 0, 0), 16, "Segment Routing (SRv6) SIDs", "RFC 9602", [T, T, T, F, F]),
+    block(Ipv6Addr::new(0xfc00, 0, 0, 0, 0, 0, 0, 0), 7, "Unique-Local", "RFC 4193, RFC 8190", [T, T, T, F, F]),
+    block(Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 0), 10, "Link-Local Unicast", "RFC 4291", [T, T, F, F, T]),
+    // Architectural ranges outside the special-purpose registry
+    block(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 0), 96, "IPv4-compatible Address (deprecated)", "RFC 4291", [NA, NA, NA, F, F]),
+    block(Ipv6Addr::new(0xfec0, 0, 0, 0, 0, 0, 0, 0), 10, "Site-Local Unicast (deprecated)", "RFC 3879", [NA, NA, NA, F, F]),
+    block(Ipv6Addr::new(0xff00, 0, 0, 0, 0, 0, 0, 0), 8, "Multicast", "RFC 4291", [F, T, NA, NA, F]),
+    block(Ipv6Addr::new(0x2000, 0, 0, 0, 0, 0, 0, 0), 3, "Global Unicast", "RFC 4291", [T, T, T, T, F]),
+    block(Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 0), 0, "Reserved by IETF", "RFC 4291", [NA, NA, NA, NA, NA]),
+];
+
+/// Most specific block containing `addr`; the ::/0 entry guarantees a match
+pub fn classify(addr: Ipv6Addr) -> &'static AddressBlock {
+    SPECIAL_PURPOSE
+        .iter()
+        .filter(|b| b.contains(addr))
+        .max_by_key(|b| b.prefix_len)
+        .expect("::/0 matches every address")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name(s: &str) -> &'static str {
+        classify(s.parse().unwrap()).name
+    }
+
+    #[test]
+    fn most_specific_block_wins() {
+        assert_eq!(name("::1"), "Loopback Address");
+        assert_eq!(name("::"), "Unspecified Address");
+        assert_eq!(name("::ffff:192.0.2.1"), "IPv4-mapped Address");
+        assert_eq!(name("2001::1"), "TEREDO");
+        assert_eq!(name("2001:1::1"), "Port Control Protocol Anycast");
+        assert_eq!(name("2001:5::1"), "IETF Protocol Assignments");
+        assert_eq!(name("2001:db8::1"), "Documentation");
+        assert_eq!(name("3fff:123::1"), "Documentation");
+        assert_eq!(name("2a00:1450::1"), "Global Unicast");
+        assert_eq!(name("fd12:3456::1"), "Unique-Local");
+        assert_eq!(name("fe80::1"), "Link-Local Unicast");
+        assert_eq!(name("ff02::1"), "Multicast");
+        assert_eq!(name("4000::1"), "Reserved by IETF");
+    }
+
+    #[test]
+    fn flags_follow_the_registry() {
+        let documentation = classify("2001:db8::".parse().unwrap());
+        assert_eq!(documentation.global, Some(false));
+        assert_eq!(documentation.rfc, "RFC 3849");
+        let link_local = classify("fe80::1".parse().unwrap());
+        assert_eq!(link_local.forwardable, Some(false));
+        assert_eq!(link_local.reserved_by_protocol, Some(true));
+    }
+
+    #[test]
+    fn every_block_is_aligned() {
+        for block in SPECIAL_PURPOSE {
+            assert!(block.contains(block.address), "{} /{} has host bits set", block.address, block.prefix_len);
+        }
+    }
+}
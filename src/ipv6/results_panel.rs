@@ -1,7 +1,14 @@
 // src/ipv6/results_panel.rs
 use dioxus::prelude::*;
-use crate::ipv6::types::{CalculationResult, HierarchyLevel, HierarchyNode, Ipv6InputError, SubnetResult};
-use crate::ipv6::calculator::{LAST_N,LIMIT};
+use crate::util::get_tab_grid_class;
+use crate::components::{AddressArithmetic, ExportButtons, FreeSearchSummary, HierarchyMap, HostBitsWarning, SplitMap};
+use crate::ipv6::types::{CalculationResult, HierarchyExportFormat, HierarchyLevel, HierarchyNode, HierarchyPlacement, HierarchyResult, Ipv6InputError, LevelUsage, MulticastInfo, PdPlan, PdPoolFormat, SubnetResult};
+use crate::ipv6::calculator::{flatten_hierarchy, LAST_N, LIMIT};
+use crate::ipv6::export::{export_hierarchy, pd_pool_definitions};
+use crate::ipv6::multicast::decode_multicast;
+use crate::dhcp::DhcpPanel;
+use crate::iac::{types::IacSubnet, IacPanel};
+use crate::router_config::{types::ConfigSubnet, RouterConfigPanel};
 
 fn get_tab_class(is_active: bool) -> &'static str {
     if is_active {
@@ -10,6 +17,7 @@ fn get_tab_class(is_active: bool) -> &'static str {
         "px-6 py-3 font-medium border-b-2 border-transparent text-white-600 hover:text-gray-400"
     }
 }
+
 #[component]
 pub fn ResultsPanel(result: Option<Result<CalculationResult, Ipv6InputError>>, hierarchy_levels: Signal<Vec<HierarchyLevel>>) -> Element {
     let mut active_tab = use_signal(|| 0); 
@@ -30,11 +38,24 @@ pub fn ResultsPanel(result: Option<Result<CalculationResult, Ipv6InputError>>, h
                 Some(Ok(calc)) => {
                     let is_subnetted = calc.new_prefix.is_some();  // ← Fixed: only when new_prefix exists
                     let has_subnets = !calc.subnets.is_empty() || calc.hierarchy.is_some();
-                    let summary_tab_class = get_tab_class(*active_tab.read() == 0);
-                    let subnets_tab_class = get_tab_class(*active_tab.read() == 1);
+                    let has_map = (is_subnetted && calc.free_search.is_none()) || calc.hierarchy.is_some();
+                    let has_config = !calc.subnets.is_empty();
+                    let has_iac = has_config || calc.hierarchy.is_some();
+                    let available = [true, has_subnets, has_map, has_config, has_config, has_iac];
+                    let tab = if available.get(*active_tab.read()).copied().unwrap_or(false) { *active_tab.read() } else { 0 };
+                    let summary_tab_class = get_tab_class(tab == 0);
+                    let subnets_tab_class = get_tab_class(tab == 1);
+                    let map_tab_class = get_tab_class(tab == 2);
+                    let config_tab_class = get_tab_class(tab == 3);
+                    let dhcp_tab_class = get_tab_class(tab == 4);
+                    let iac_tab_class = get_tab_class(tab == 5);
+                    let tab_cols = get_tab_grid_class(available.iter().filter(|&&a| a).count());
 
                     rsx! {
-                        div { class: "flex grid grid-cols-2 border-b border-gray-600 mb-6",
+                        if let Some(found) = calc.host_bits {
+                            HostBitsWarning { found }
+                        }
+                        div { class: "flex grid {tab_cols} border-b border-gray-600 mb-6",
                             button {
                                 class: "{summary_tab_class}",
                                 onclick: move |_| active_tab.set(0),
@@ -44,26 +65,98 @@ pub fn ResultsPanel(result: Option<Result<CalculationResult, Ipv6InputError>>, h
                                 button {
                                     class: "{subnets_tab_class}",
                                     onclick: move |_| active_tab.set(1),
-                                    if calc.hierarchy.is_some() { "Hierarchy Tree ({total_usable_subnets.to_string()})" } else { "Subnets ({calc.total_subnets})" }
+                                    if calc.hierarchy.is_some() {
+                                        "Hierarchy Tree ({total_usable_subnets.to_string()})"
+                                    } else if let Some(plan) = &calc.pd_plan {
+                                        "Delegation Pools ({plan.pools.len()})"
+                                    } else {
+                                        "Subnets ({calc.total_subnets})"
+                                    }
+                                }
+                            }
+                            if has_map {
+                                button {
+                                    class: "{map_tab_class}",
+                                    onclick: move |_| active_tab.set(2),
+                                    "Map"
+                                }
+                            }
+                            if has_config {
+                                button {
+                                    class: "{config_tab_class}",
+                                    onclick: move |_| active_tab.set(3),
+                                    "Config"
+                                }
+                            }
+                            if has_config {
+                                button {
+                                    class: "{dhcp_tab_class}",
+                                    onclick: move |_| active_tab.set(4),
+                                    "DHCP"
+                                }
+                            }
+                            if has_iac {
+                                button {
+                                    class: "{iac_tab_class}",
+                                    onclick: move |_| active_tab.set(5),
+                                    "IaC"
                                 }
                             }
                         }
 
-                        if *active_tab.read() == 0 || !has_subnets {
-                            SummaryTable {
-                                summary: calc.summary.clone(),
-                                new_prefix: calc.new_prefix,
-                                base_prefix: calc.base_network.prefix_len(),
-                                is_subnetted: is_subnetted
-                            } 
+                        if tab == 0 {
+                            if let Some(search) = calc.free_search.clone() {
+                                FreeSearchSummary { search, found: calc.subnets.len() }
+                                SummaryTable {
+                                    summary: calc.summary.clone(),
+                                    new_prefix: None,
+                                    base_prefix: calc.base_network.prefix_len(),
+                                    is_subnetted: false
+                                }
+                            } else {
+                                SummaryTable {
+                                    summary: calc.summary.clone(),
+                                    new_prefix: calc.new_prefix,
+                                    base_prefix: calc.base_network.prefix_len(),
+                                    is_subnetted: is_subnetted
+                                } 
+                            }
+                            AddressArithmetic { network: calc.base_network.into() }
                         }
-                        if *active_tab.read() == 1 && has_subnets {
+                        if tab == 1 {
                             if let Some(hier) = &calc.hierarchy {
+                                HierarchyUsage { usage: hier.usage.clone(), placement: hier.placement }
                                 HierarchyTree { nodes: hier.tree.clone() }
+                                HierarchyExport { hierarchy: hier.clone() }
+                            } else if let Some(plan) = &calc.pd_plan {
+                                PdPlanView { plan: plan.clone() }
                             } else {
                                 SubnetTable { subnets: calc.subnets.clone(), total_subnets: calc.total_subnets }
                             }
                         }
+                        if tab == 2 {
+                            if let Some(hier) = &calc.hierarchy {
+                                HierarchyMap { nodes: hier.tree.clone() }
+                            } else {
+                                SplitMap {
+                                    key: "{calc.base_network}-{calc.new_prefix:?}",
+                                    base: u128::from(calc.base_network.network()),
+                                    new_prefix: calc.new_prefix.unwrap_or(128),
+                                    total: calc.total_subnets,
+                                    used: calc.requested_subnets.map_or(calc.total_subnets, |n| n as u128),
+                                    is_v4: false,
+                                }
+                            }
+                        }
+                        if tab == 3 {
+                            RouterConfigPanel { subnets: calc.subnets.iter().map(ConfigSubnet::from).collect::<Vec<_>>() }
+                        }
+                        if tab == 4 {
+                            DhcpPanel { subnets: calc.subnets.iter().map(|sub| sub.network.into()).collect::<Vec<_>>() }
+                        }
+                        if tab == 5 {
+                            IacPanel { subnets: iac_subnets(&calc) }
+                        }
                     }
                 }
             }
@@ -73,6 +166,8 @@ pub fn ResultsPanel(result: Option<Result<CalculationResult, Ipv6InputError>>, h
 
 #[component]
 fn SummaryTable(summary: SubnetResult, new_prefix: Option<u8>, base_prefix: u8, is_subnetted: bool) -> Element {
+    let multicast = decode_multicast(summary.network.network());
+
     rsx! {
         div { class: "overflow-y-auto pr-2",
             table { class: "w-full text-sm text-left border-collapse",
@@ -81,6 +176,21 @@ fn SummaryTable(summary: SubnetResult, new_prefix: Option<u8>, base_prefix: u8,
                     SummaryRow { label: "Compressed", value: summary.compressed }
                     SummaryRow { label: "Expanded", value: summary.expanded }
                     SummaryRow { label: "Address Type", value: summary.addr_type }
+                    SummaryRow {
+                        label: "Registry Block",
+                        value: format!("{}/{} ({})", summary.classification.address, summary.classification.prefix_len, summary.classification.rfc)
+                    }
+                    SummaryRow {
+                        label: "Properties",
+                        value: format!(
+                            "Source: {} · Destination: {} · Forwardable: {} · Globally Reachable: {} · Reserved-by-Protocol: {}",
+                            flag_text(summary.classification.source),
+                            flag_text(summary.classification.destination),
+                            flag_text(summary.classification.forwardable),
+                            flag_text(summary.classification.global),
+                            flag_text(summary.classification.reserved_by_protocol),
+                        )
+                    }
                     SummaryRow { label: "First Host", value: summary.first_host }
                     SummaryRow { label: "Last Host", value: summary.last_host }
                     if is_subnetted {
@@ -95,10 +205,60 @@ fn SummaryTable(summary: SubnetResult, new_prefix: Option<u8>, base_prefix: u8,
                     }
                 }
             }
+            if let Some(info) = multicast {
+                MulticastDetails { info }
+            }
+        }
+    }
+}
+
+#[component]
+fn MulticastDetails(info: MulticastInfo) -> Element {
+    let flags = format!(
+        "R={} P={} T={}{}",
+        info.flag_r as u8,
+        info.flag_p as u8,
+        info.flag_t as u8,
+        if info.flag_t { " (transient)" } else { " (well-known)" }
+    );
+
+    rsx! {
+        h3 { class: "mt-6 mb-2 font-bold text-left", "Multicast Details" }
+        table { class: "w-full text-sm text-left border-collapse",
+            tbody {
+                SummaryRow { label: "Flags", value: flags }
+                SummaryRow { label: "Scope", value: format!("{:x} – {}", info.scope, info.scope_name) }
+                if let Some(name) = info.well_known {
+                    SummaryRow { label: "Well-Known Group", value: name.to_string() }
+                }
+                if let Some(group) = &info.prefix_based {
+                    if group.is_ssm {
+                        SummaryRow { label: "Group Type", value: "Source-Specific Multicast (RFC 4607)".to_string() }
+                    } else if info.flag_r {
+                        SummaryRow { label: "Group Type", value: "Embedded-RP (RFC 3956)".to_string() }
+                    } else {
+                        SummaryRow { label: "Group Type", value: "Unicast-Prefix-based (RFC 3306)".to_string() }
+                    }
+                    SummaryRow { label: "Network Prefix", value: format!("{} (plen {})", group.network_prefix, group.plen) }
+                    SummaryRow { label: "Group ID", value: format!("{:#010x}", group.group_id) }
+                }
+                if let Some(rp) = info.embedded_rp {
+                    SummaryRow { label: "RP Address", value: format!("{} (RIID {:x})", rp, info.prefix_based.as_ref().map_or(0, |g| g.riid)) }
+                }
+                SummaryRow { label: "Ethernet MAC", value: info.mac.clone() }
+            }
         }
     }
 }
 
+fn flag_text(flag: Option<bool>) -> &'static str {
+    match flag {
+        Some(true) => "Yes",
+        Some(false) => "No",
+        None => "N/A",
+    }
+}
+
 #[component]
 fn SummaryRow(label: &'static str, value: String) -> Element {
     rsx! {
@@ -112,6 +272,33 @@ fn SummaryRow(label: &'static str, value: String) -> Element {
 // ... PlaceholderMessage, ErrorMessage, SubnetTable, and HierarchyTree remain largely same 
 // but ensure font-sizes use text-sm for consistency with IPv4.
 
+/// Hierarchy leaves become named allocations (named after their path); plain splits are numbered
+fn iac_subnets(calc: &CalculationResult) -> Vec<IacSubnet> {
+    match &calc.hierarchy {
+        Some(hier) => flatten_hierarchy(hier)
+            .into_iter()
+            .filter(|row| row.children == 0)
+            .map(|row| IacSubnet {
+                name: row
+                    .path
+                    .split(|c: char| !c.is_ascii_alphanumeric())
+                    .filter(|w| !w.is_empty())
+                    .collect::<Vec<_>>()
+                    .join("-")
+                    .to_lowercase(),
+                ipv4: None,
+                ipv6: Some(row.prefix),
+            })
+            .collect(),
+        None => calc
+            .subnets
+            .iter()
+            .enumerate()
+            .map(|(i, sub)| IacSubnet { name: format!("subnet-{}", i + 1), ipv4: None, ipv6: Some(sub.network) })
+            .collect(),
+    }
+}
+
 #[component]
 fn PlaceholderMessage() -> Element {
     rsx! { p { class: "text-center text-gray-500 py-20 text-base", "Enter details and calculate" } }
@@ -119,12 +306,14 @@ fn PlaceholderMessage() -> Element {
 
 #[component]
 fn ErrorMessage(err: Ipv6InputError) -> Element {
-    let msg = match err {
-        Ipv6InputError::ParseError(s) => s,
-        Ipv6InputError::InvalidPrefix => "Invalid prefix. Child prefix must be bigger than the original prefix".to_string(),
-        Ipv6InputError::InsufficientBits => "Insufficient bits for hierarchy".to_string(),
-    };
-    rsx! { div { class: "bg-red-900/40 p-4 rounded text-sm text-red-300", strong { "Error: " } "{msg}" } }
+    rsx! {
+        div { class: "bg-red-900/40 p-4 rounded text-sm text-red-300",
+            strong { "Error: " } "{err}"
+            if let Some(hint) = err.suggestion() {
+                p { class: "mt-1 text-red-200", "{hint}" }
+            }
+        }
+    }
 }
 
 #[component]
@@ -188,6 +377,74 @@ fn SubnetTable(subnets: Vec<SubnetResult>, total_subnets: u128) -> Element {
     }
 }
 
+#[component]
+fn PdPlanView(plan: PdPlan) -> Element {
+    let mut format = use_signal(|| PdPoolFormat::Kea);
+    let definitions = pd_pool_definitions(&plan, format());
+    let utilization = plan.customers as f64 / plan.capacity as f64 * 100.0;
+
+    rsx! {
+        div { class: "text-left",
+            table { class: "w-full text-sm text-left border-collapse mb-6",
+                tbody {
+                    SummaryRow { label: "Delegated Length", value: format!("/{}", plan.delegated_prefix) }
+                    SummaryRow { label: "Customers", value: format!("{} of {} ({:.1}% used)", plan.customers, plan.capacity, utilization) }
+                    SummaryRow { label: "LANs per Customer", value: format!("{} × /64", plan.lans_per_customer) }
+                }
+            }
+            table { class: "w-full text-sm text-left mb-6",
+                thead { class: "bg-gray-700",
+                    tr {
+                        th { class: "px-4 py-2", "Pool" }
+                        th { class: "px-4 py-2", "Prefix" }
+                        th { class: "px-4 py-2", "Delegated Range" }
+                        th { class: "px-4 py-2", "Customers" }
+                    }
+                }
+                tbody {
+                    for pool in plan.pools.iter() {
+                        tr { class: "border-t border-gray-700 hover:bg-gray-700/50",
+                            td { class: "px-4 py-2", "{pool.name}" }
+                            td { class: "px-4 py-2 font-roboto", "{pool.prefix}" }
+                            td { class: "px-4 py-2 font-roboto", "{pool.first_delegated} → {pool.last_delegated}" }
+                            td { class: "px-4 py-2 font-roboto", "{pool.customers} / {pool.capacity}" }
+                        }
+                    }
+                }
+            }
+            h3 { class: "font-bold mb-2", "Customer LAN Layout" }
+            p { class: "text-sm text-gray-400 mb-2",
+                "First customer ({plan.pools[0].first_delegated}), first {plan.sample_lans.len()} of {plan.lans_per_customer} LANs"
+            }
+            ul { class: "grid grid-cols-2 gap-1 text-sm font-roboto mb-6",
+                for (i, lan) in plan.sample_lans.iter().enumerate() {
+                    li { "LAN {i + 1}: {lan}" }
+                }
+            }
+            div { class: "flex items-center justify-between mb-2",
+                div { class: "flex items-center space-x-2",
+                    label { class: "text-sm font-medium", "Pool definitions for" }
+                    select {
+                        class: "px-2 py-1 text-sm border border-gray-600 rounded bg-gray-700",
+                        onchange: move |e| {
+                            format.set(match e.value().as_str() {
+                                "ios" => PdPoolFormat::CiscoIos,
+                                "dhcpd" => PdPoolFormat::IscDhcpd,
+                                _ => PdPoolFormat::Kea,
+                            });
+                        },
+                        option { value: "kea", selected: format() == PdPoolFormat::Kea, "Kea DHCPv6" }
+                        option { value: "ios", selected: format() == PdPoolFormat::CiscoIos, "Cisco IOS" }
+                        option { value: "dhcpd", selected: format() == PdPoolFormat::IscDhcpd, "ISC dhcpd" }
+                    }
+                }
+                ExportButtons { content: definitions.clone(), filename: format().file_name(), mime: format().mime() }
+            }
+            pre { class: "max-h-60 overflow-auto bg-gray-900 p-3 rounded text-xs font-roboto", "{definitions}" }
+        }
+    }
+}
+
 #[component]
 fn HierarchyNodeComponent(node: HierarchyNode) -> Element {
     let mut expanded = use_signal(|| false);  // Local state for this node's expansion
@@ -201,6 +458,26 @@ fn HierarchyNodeComponent(node: HierarchyNode) -> Element {
                     if expanded() { "- " } else { "+ " }
                 }
                 "{node.label}   →   {node.prefix}"
+                if !node.children.is_empty() || !node.reserved.is_empty() {
+                    {
+                        let used = node.children.len();
+                        let child_len = node.children.first().map_or(node.prefix.prefix_len(), |c| c.prefix.prefix_len());
+                        let spare: u128 = node.reserved.iter().map(|r| 1u128 << (child_len - r.prefix_len()) as u32).sum();
+                        let utilization = used as f64 / (used as u128 + spare) as f64 * 100.0;
+                        rsx! {
+                            span { class: "ml-3 text-xs text-gray-400",
+                                "{used} used, {spare} spare ({utilization:.1}%)"
+                            }
+                        }
+                    }
+                }
+            }
+            if expanded() && !node.reserved.is_empty() {
+                ul { class: "pl-12 ml-2 text-sm text-gray-500",
+                    for reserved in node.reserved.iter() {
+                        li { class: "py-1", "Reserved   →   {reserved}" }
+                    }
+                }
             }
             /*
             if expanded() && !node.children.is_empty() {
@@ -251,6 +528,42 @@ fn HierarchyNodeComponent(node: HierarchyNode) -> Element {
     }
 }
 
+#[component]
+fn HierarchyUsage(usage: Vec<LevelUsage>, placement: HierarchyPlacement) -> Element {
+    let placement_label = match placement {
+        HierarchyPlacement::Contiguous => "contiguously",
+        HierarchyPlacement::Sparse => "sparsely",
+    };
+
+    rsx! {
+        div { class: "mb-6 text-left",
+            table { class: "w-full text-sm text-left",
+                thead { class: "bg-gray-700",
+                    tr {
+                        th { class: "px-4 py-2", "Level" }
+                        th { class: "px-4 py-2", "Used" }
+                        th { class: "px-4 py-2", "Capacity" }
+                        th { class: "px-4 py-2", "Spare" }
+                        th { class: "px-4 py-2", "Utilization" }
+                    }
+                }
+                tbody {
+                    for level in usage.iter() {
+                        tr { class: "border-t border-gray-700",
+                            td { class: "px-4 py-2", "{level.name}" }
+                            td { class: "px-4 py-2 font-roboto", "{level.used}" }
+                            td { class: "px-4 py-2 font-roboto", "{level.capacity}" }
+                            td { class: "px-4 py-2 font-roboto", "{level.spare()}" }
+                            td { class: "px-4 py-2 font-roboto", "{level.utilization():.1}%" }
+                        }
+                    }
+                }
+            }
+            p { class: "mt-2 text-xs text-gray-500", "Used subnets placed {placement_label} within each parent" }
+        }
+    }
+}
+
 // Updated: Now renders the tree using the recursive HierarchyNodeComponent
 #[component]
 fn HierarchyTree(nodes: Vec<HierarchyNode>) -> Element {
@@ -261,4 +574,38 @@ fn HierarchyTree(nodes: Vec<HierarchyNode>) -> Element {
             }
         }
     }
-}
\ No newline at end of file
+}
+
+#[component]
+fn HierarchyExport(hierarchy: HierarchyResult) -> Element {
+    let mut format = use_signal(|| HierarchyExportFormat::Csv);
+    let rows = flatten_hierarchy(&hierarchy);
+    let content = export_hierarchy(&rows, format());
+
+    rsx! {
+        div { class: "mt-8 text-left",
+            div { class: "flex items-center justify-between mb-2",
+                div { class: "flex items-center space-x-2",
+                    label { class: "text-sm font-medium", "Export {rows.len()} rows as" }
+                    select {
+                        class: "px-2 py-1 text-sm border border-gray-600 rounded bg-gray-700",
+                        onchange: move |e| {
+                            format.set(match e.value().as_str() {
+                                "json" => HierarchyExportFormat::Json,
+                                "netbox" => HierarchyExportFormat::NetBox,
+                                _ => HierarchyExportFormat::Csv,
+                            });
+                        },
+                        option { value: "csv", selected: format() == HierarchyExportFormat::Csv, "CSV" }
+                        option { value: "json", selected: format() == HierarchyExportFormat::Json, "JSON" }
+                        option { value: "netbox", selected: format() == HierarchyExportFormat::NetBox, "NetBox import (CSV)" }
+                    }
+                }
+                ExportButtons { content: content.clone(), filename: format().file_name(), mime: format().mime() }
+            }
+            pre { class: "max-h-60 overflow-auto bg-gray-900 p-3 rounded text-xs font-roboto",
+                "{content}"
+            }
+        }
+    }
+}
@@ -1,10 +1,94 @@
-use ipnet::Ipv6Net;
+use ipnet::{IpNet, Ipv6Net};
+use serde::Serialize;
+use std::net::Ipv6Addr;
+use crate::address_math::HostBits;
+use crate::free_space::{FieldError, FreeSearch};
+use crate::ipv6::registry::AddressBlock;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Ipv6InputError {
-    ParseError(String),
-    InvalidPrefix,
-    InsufficientBits,
+    InvalidAddress(String),                       // Text that isn't an IPv6 address
+    HostBitsSet(Box<HostBits>),                   // Strict mode only
+    PrefixOutOfRange { min: u8, max: u8 },
+    MissingInput(&'static str),                   // Field the mode needs but the caller didn't pass
+    NoSubnets,
+    TooManySubnets { requested: u128, max: u128 },
+    NotEnoughBits { needed: u32, available: u32 }, // Split needs more bits than the parent has left
+    InvalidHierarchyLevel { index: usize },       // 1-based; no subnets, or no usable bit width
+    LevelTooSmall { index: usize, needed: u8, bits: u8 },
+    TooManyCustomers { requested: u128, capacity: u128 },
+    WrongFamily(IpNet),                           // IPv4 prefix in an IPv6 used list
+    InvalidMac(String),
+    Field(FieldError),
+    UnsupportedMode, // `calculate` was given a mode with its own entry point (NextFree: `find_free_subnets`)
+}
+
+impl std::fmt::Display for Ipv6InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Ipv6InputError::InvalidAddress(text) => write!(f, "'{}' is not a valid IPv6 address", text),
+            Ipv6InputError::HostBitsSet(found) => write!(f, "{}", found),
+            Ipv6InputError::PrefixOutOfRange { min, max } => write!(f, "Prefix length must be between /{} and /{}", min, max),
+            Ipv6InputError::MissingInput(field) => write!(f, "{} is required", field),
+            Ipv6InputError::NoSubnets => write!(f, "Count must be at least 1"),
+            Ipv6InputError::TooManySubnets { requested, max } => write!(f, "{} subnets requested; the maximum is {}", requested, max),
+            Ipv6InputError::NotEnoughBits { needed, available } => write!(f, "Needs {} more bits but the parent only has {} left", needed, available),
+            Ipv6InputError::InvalidHierarchyLevel { index } => write!(f, "Level {} needs at least one subnet and between 1 and 127 bits", index),
+            Ipv6InputError::LevelTooSmall { index, needed, bits } => write!(f, "Level {} needs {} bits for its subnets but only has {}", index, needed, bits),
+            Ipv6InputError::TooManyCustomers { requested, capacity } => write!(f, "{} customers requested but only {} delegated prefixes fit", requested, capacity),
+            Ipv6InputError::WrongFamily(net) => write!(f, "{} is not an IPv6 prefix", net),
+            Ipv6InputError::InvalidMac(text) => write!(f, "Invalid MAC address: {}", text),
+            Ipv6InputError::Field(err) => write!(f, "{}", err),
+            Ipv6InputError::UnsupportedMode => write!(f, "This mode can't be calculated here"),
+        }
+    }
+}
+
+impl std::error::Error for Ipv6InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Ipv6InputError::Field(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<FieldError> for Ipv6InputError {
+    fn from(err: FieldError) -> Self {
+        Ipv6InputError::Field(err)
+    }
+}
+
+impl Ipv6InputError {
+    /// What to change in the input, when there's something concrete to offer
+    pub fn suggestion(&self) -> Option<String> {
+        match self {
+            Ipv6InputError::InvalidAddress(text) => {
+                if text.parse::<std::net::Ipv4Addr>().is_ok() {
+                    return Some("That's an IPv4 address; use the IPv4 tab".to_string());
+                }
+                if let Some((addr, prefix)) = text.split_once('/') {
+                    return Some(format!("Put the prefix in the prefix field: {} and /{}", addr.trim(), prefix.trim()));
+                }
+                if text.matches("::").count() > 1 {
+                    return Some("'::' can only appear once".to_string());
+                }
+                Some("Addresses look like 2001:db8::1".to_string())
+            }
+            Ipv6InputError::HostBitsSet(found) => Some(format!("Did you mean {}?", found.network)),
+            Ipv6InputError::TooManySubnets { max, .. } => Some(format!("Ask for {} or fewer", max)),
+            Ipv6InputError::NotEnoughBits { needed, available } => {
+                let short = needed.saturating_sub(*available);
+                Some(format!("Shorten the parent prefix by {} bit{}", short, if short == 1 { "" } else { "s" }))
+            }
+            Ipv6InputError::LevelTooSmall { index, needed, .. } => Some(format!("Give level {} at least {} bits", index, needed)),
+            Ipv6InputError::TooManyCustomers { .. } => Some("Use a longer delegated prefix or a larger aggregate".to_string()),
+            Ipv6InputError::WrongFamily(_) => Some("Remove it from the list, or check it on the IPv4 tab".to_string()),
+            Ipv6InputError::InvalidMac(_) => Some("MAC addresses look like 00:11:22:33:44:55 or 0011.2233.4455".to_string()),
+            Ipv6InputError::Field(FieldError::InvalidEntry { .. }) => Some("List one prefix or address per line, e.g. 2001:db8::/64".to_string()),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -13,6 +97,7 @@ pub struct SubnetResult {
     pub compressed: String,
     pub expanded: String,
     pub addr_type: String,
+    pub classification: &'static AddressBlock,
     pub first_host: String,
     pub last_host: String,
 }
@@ -24,7 +109,11 @@ pub struct CalculationResult {
     pub subnets: Vec<SubnetResult>,
     pub new_prefix: Option<u8>,
     pub total_subnets: u128,
+    pub requested_subnets: Option<u32>,
     pub hierarchy: Option<HierarchyResult>,
+    pub pd_plan: Option<PdPlan>,
+    pub free_search: Option<FreeSearch>, // Only in NextFree mode
+    pub host_bits: Option<HostBits>,     // Parent typed as a host address, e.g. 2001:db8::1/64
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -33,6 +122,8 @@ pub enum SubnetMode {
     BySubnets,
     ByPrefix,
     ByHierarchy,
+    PrefixDelegation,
+    NextFree,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -42,10 +133,22 @@ pub struct HierarchyLevel {
     pub bits: u8,
 }
 
+/// How the used children of a level are laid out inside their parent
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HierarchyPlacement {
+    /// First `num` blocks, leaving the spare ones together at the end
+    #[default]
+    Contiguous,
+    /// Spread out so each child starts an aligned free block it can grow into
+    Sparse,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct HierarchyResult {
     pub levels: Vec<HierarchyLevel>,
     pub tree: Vec<HierarchyNode>,
+    pub usage: Vec<LevelUsage>,
+    pub placement: HierarchyPlacement,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -53,17 +156,126 @@ pub struct HierarchyNode {
     pub prefix: Ipv6Net,
     pub label: String,
     pub children: Vec<HierarchyNode>,
+    pub reserved: Vec<Ipv6Net>, // Unused child space, aggregated into the fewest prefixes
+}
+
+/// Allocation totals for one hierarchy level across all of its parents
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LevelUsage {
+    pub name: String,
+    pub used: u128,
+    pub capacity: u128,
+}
+
+impl LevelUsage {
+    pub fn spare(&self) -> u128 {
+        self.capacity - self.used
+    }
+
+    pub fn utilization(&self) -> f64 {
+        if self.capacity == 0 { 0.0 } else { self.used as f64 / self.capacity as f64 * 100.0 }
+    }
+}
+
+/// One node of a hierarchy tree, flattened for IPAM import
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct HierarchyRow {
+    pub path: String,
+    pub depth: usize,
+    pub prefix: Ipv6Net,
+    pub first_address: Ipv6Addr,
+    pub last_address: Ipv6Addr,
+    pub parent: Option<Ipv6Net>,
+    pub children: usize,
+    pub spare_capacity: u128,
+    pub reserved: Vec<Ipv6Net>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum HierarchyExportFormat {
+    Csv,
+    Json,
+    NetBox,
+}
+
+/// One DHCPv6-PD pool, served by a single BNG/router
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PdPool {
+    pub name: String,
+    pub prefix: Ipv6Net,
+    pub first_delegated: Ipv6Net,
+    pub last_delegated: Ipv6Net,
+    pub capacity: u128,
+    pub customers: u128,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PdPlan {
+    pub delegated_prefix: u8,
+    pub customers: u128,
+    pub capacity: u128,
+    pub pools: Vec<PdPool>,
+    pub lans_per_customer: u128,
+    pub sample_lans: Vec<Ipv6Net>, // First /64s of the first delegated prefix
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum PdPoolFormat {
+    Kea,
+    CiscoIos,
+    IscDhcpd,
+}
+
+/// A generated RFC 4193 Unique Local prefix
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UlaResult {
+    pub prefix: Ipv6Net,
+    pub global_id: u64,
+    pub source: String,
+}
+
+/// Decoded fields of an ff00::/8 address
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MulticastInfo {
+    pub flag_r: bool, // Embedded RP (RFC 3956)
+    pub flag_p: bool, // Unicast-prefix-based (RFC 3306)
+    pub flag_t: bool, // Transient (dynamically assigned)
+    pub scope: u8,
+    pub scope_name: &'static str,
+    pub prefix_based: Option<PrefixBasedGroup>,
+    pub embedded_rp: Option<Ipv6Addr>,
+    pub well_known: Option<&'static str>,
+    pub mac: String,
+}
+
+/// RFC 3306 / RFC 3956 fields: ffXX:<riid/rsvd><plen>:<network prefix>:<group id>
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PrefixBasedGroup {
+    pub riid: u8,
+    pub plen: u8,
+    pub network_prefix: Ipv6Net,
+    pub group_id: u32,
+    pub is_ssm: bool,
 }
 
 pub const PREFIX_OPTIONS: &[(u8, &'static str)] = &[
-    (1, "/1"), (2, "/2"), (3, "/3"), (4, "/4"), (5, "/5"), (6, "/6"), (7, "/7"), (8, "/8"),
-    (9, "/9"), (10, "/10"), (11, "/11"), (12, "/12"), (13, "/13"), (14, "/14"), (15, "/15"), (16, "/16"),
-    (17, "/17"), (18, "/18"), (19, "/19"), (20, "/20"), (21, "/21"), (22, "/22"), (23, "/23"), (24, "/24"),
-    (25, "/25"), (26, "/26"), (27, "/27"), (28, "/28"), (29, "/29"), (30, "/30"), (31, "/31"), (32, "/32"),
-    (33, "/33"), (34, "/34"), (35, "/35"), (36, "/36"), (37, "/37"), (38, "/38"), (39, "/39"), (40, "/40"),
-    (41, "/41"), (42, "/42"), (43, "/43"), (44, "/44"), (45, "/45"), (46, "/46"), (47, "/47"), (48, "/48"),
-    (49, "/49"), (50, "/50"), (51, "/51"), (52, "/52"), (53, "/53"), (54, "/54"), (55, "/55"), (56, "/56"),
-    (57, "/57"), (58, "/58"), (59, "/59"), (60, "/60"), (61, "/61"), (62, "/62"), (63, "/63"), (64, "/64"),
+    (0, "/0"), (1, "/1"), (2, "/2"), (3, "/3"), (4, "/4"), (5, "/5"), (6, "/6"), (7, "/7"),
+    (8, "/8"), (9, "/9"), (10, "/10"), (11, "/11"), (12, "/12"), (13, "/13"), (14, "/14"), (15, "/15"),
+    (16, "/16"), (17, "/17"), (18, "/18"), (19, "/19"), (20, "/20"), (21, "/21"), (22, "/22"), (23, "/23"),
+    (24, "/24"), (25, "/25"), (26, "/26"), (27, "/27"), (28, "/28"), (29, "/29"), (30, "/30"), (31, "/31"),
+    (32, "/32"), (33, "/33"), (34, "/34"), (35, "/35"), (36, "/36"), (37, "/37"), (38, "/38"), (39, "/39"),
+    (40, "/40"), (41, "/41"), (42, "/42"), (43, "/43"), (44, "/44"), (45, "/45"), (46, "/46"), (47, "/47"),
+    (48, "/48"), (49, "/49"), (50, "/50"), (51, "/51"), (52, "/52"), (53, "/53"), (54, "/54"), (55, "/55"),
+    (56, "/56"), (57, "/57"), (58, "/58"), (59, "/59"), (60, "/60"), (61, "/61"), (62, "/62"), (63, "/63"),
+    (64, "/64"), (65, "/65"), (66, "/66"), (67, "/67"), (68, "/68"), (69, "/69"), (70, "/70"), (71, "/71"),
+    (72, "/72"), (73, "/73"), (74, "/74"), (75, "/75"), (76, "/76"), (77, "/77"), (78, "/78"), (79, "/79"),
+    (80, "/80"), (81, "/81"), (82, "/82"), (83, "/83"), (84, "/84"), (85, "/85"), (86, "/86"), (87, "/87"),
+    (88, "/88"), (89, "/89"), (90, "/90"), (91, "/91"), (92, "/92"), (93, "/93"), (94, "/94"), (95, "/95"),
+    (96, "/96"), (97, "/97"), (98, "/98"), (99, "/99"), (100, "/100"), (101, "/101"), (102, "/102"), (103, "/103"),
+    (104, "/104"), (105, "/105"), (106, "/106"), (107, "/107"), (108, "/108"), (109, "/109"), (110, "/110"), (111, "/111"),
+    (112, "/112"), (113, "/113"), (114, "/114"), (115, "/115"), (116, "/116"), (117, "/117"), (118, "/118"), (119, "/119"),
+    (120, "/120"), (121, "/121"), (122, "/122"), (123, "/123"), (124, "/124"), (125, "/125"), (126, "/126"), (127, "/127"),
+    (128, "/128"),
 ];
 
 pub const MAX_USABLE_SUBNETS: u128 = 131072;
\ No newline at end of file
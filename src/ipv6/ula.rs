@@ -0,0 +1,94 @@
+// src/ipv6/ula.rs
+use ipnet::Ipv6Net;
+use sha1_smol::Sha1;
+use std::net::Ipv6Addr;
+use crate::ipv6::types::{Ipv6InputError, UlaResult};
+
+// Seconds between the NTP epoch (1900) and the Unix epoch (1970)
+const NTP_UNIX_OFFSET: u64 = 2_208_988_800;
+
+/// Current time of day in 64-bit NTP format (RFC 4193 section 3.2.2, step 1)
+fn ntp_now() -> u64 {
+    let millis = unix_time_millis();
+    let seconds = millis / 1000 + NTP_UNIX_OFFSET;
+    let fraction = ((millis % 1000) << 32) / 1000;
+    (seconds << 32) | fraction
+}
+
+fn unix_time_millis() -> u64 {
+    #[cfg(target_family = "wasm")]
+    {
+        js_sys::Date::now() as u64
+    }
+    #[cfg(not(target_family = "wasm"))]
+    {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0)
+    }
+}
+
+fn random_u64() -> u64 {
+    #[cfg(target_family = "wasm")]
+    {
+        let high = (js_sys::Math::random() * 4294967296.0) as u64;
+        let low = (js_sys::Math::random() * 4294967296.0) as u64;
+        (high << 32) | low
+    }
+    #[cfg(not(target_family = "wasm"))]
+    {
+        use std::hash::{BuildHasher, Hasher};
+        let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+        hasher.write_u64(unix_time_millis());
+        hasher.finish()
+    }
+}
+
+/// Modified EUI-64 from a 48-bit MAC address (ff:fe inserted, U/L bit flipped)
+pub fn eui64_from_mac(mac: &str) -> Result<[u8; 8], Ipv6InputError> {
+    let octets: Vec<u8> = mac
+        .trim()
+        .split([':', '-', '.'])
+        .flat_map(|part| {
+            // Accept both aa:bb:cc:dd:ee:ff and Cisco-style aabb.ccdd.eeff
+            part.as_bytes()
+                .chunks(2)
+                .map(|pair| std::str::from_utf8(pair).ok().and_then(|hex| u8::from_str_radix(hex, 16).ok()))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Option<Vec<u8>>>()
+        .filter(|octets| octets.len() == 6)
+        .ok_or_else(|| Ipv6InputError::InvalidMac(mac.trim().to_string()))?;
+
+    Ok([octets[0] ^ 0x02, octets[1], octets[2], 0xff, 0xfe, octets[3], octets[4], octets[5]])
+}
+
+/// Build the fd00::/8 /48 from the low 40 bits of SHA-1(key)
+pub fn ula_from_key(key: &[u8]) -> (Ipv6Net, u64) {
+    let digest = Sha1::from(key).digest().bytes();
+    let global_id = digest[15..20].iter().fold(0u64, |acc, &b| (acc << 8) | b as u64);
+    let high = (0xfd_u128 << 120) | ((global_id as u128) << 80);
+    (Ipv6Net::new(Ipv6Addr::from(high), 48).unwrap(), global_id)
+}
+
+/// Generate a Unique Local /48 per RFC 4193: SHA-1 over NTP time + EUI-64, or over `seed` when given
+pub fn generate_ula(seed: Option<&str>, mac: Option<&str>) -> Result<UlaResult, Ipv6InputError> {
+    let (key, source) = match seed.map(str::trim).filter(|s| !s.is_empty()) {
+        Some(seed) => (seed.as_bytes().to_vec(), format!("seed \"{}\"", seed)),
+        None => {
+            let (eui64, eui_source) = match mac.map(str::trim).filter(|m| !m.is_empty()) {
+                Some(mac) => (eui64_from_mac(mac)?, format!("EUI-64 from {}", mac)),
+                // No hardware address in a browser: use a random locally administered identifier
+                None => ((random_u64() & !(0x02 << 56)).to_be_bytes(), "random EUI-64".to_string()),
+            };
+            let ntp = ntp_now();
+            let mut key = ntp.to_be_bytes().to_vec();
+            key.extend_from_slice(&eui64);
+            (key, format!("NTP time {:#018x} + {}", ntp, eui_source))
+        }
+    };
+
+    let (prefix, global_id) = ula_from_key(&key);
+    Ok(UlaResult { prefix, global_id, source })
+}
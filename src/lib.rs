@@ -0,0 +1,17 @@
+// src/lib.rs
+pub mod address_math;
+pub mod app;
+pub mod components;
+pub mod dhcp;
+pub mod dual_stack;
+pub mod free_space;
+pub mod iac;
+pub mod theme;
+pub mod ipv4;
+pub mod ipv6;
+pub mod router_config;
+#[cfg(test)]
+mod test_support;
+pub mod tools;
+pub mod util;
+pub mod workspace;
@@ -1,13 +1,7 @@
 // src/main.rs
-mod app;
-mod components;
-mod theme;
-mod ipv4;
-mod ipv6;
-
 use dioxus::prelude::*;
-use app::App;
+use subnet_calculator::app::App;
 
 fn main() {
     launch(App);
-}
\ No newline at end of file
+}
@@ -0,0 +1,338 @@
+// src/router_config/generator.rs
+use ipnet::IpNet;
+use crate::router_config::types::{ConfigOptions, ConfigSubnet, Vendor};
+
+pub const MAX_CONFIG_SUBNETS: usize = 256;
+
+pub fn generate_config(subnets: &[ConfigSubnet], options: &ConfigOptions) -> String {
+    let subnets = &subnets[..subnets.len().min(MAX_CONFIG_SUBNETS)];
+    match options.vendor {
+        Vendor::CiscoIos => cisco_ios(subnets, options),
+        Vendor::Junos => junos(subnets, options),
+        Vendor::AristaEos => arista_eos(subnets, options),
+        Vendor::RouterOs => routeros(subnets, options),
+    }
+}
+
+fn is_v6(net: &IpNet) -> bool {
+    matches!(net, IpNet::V6(_))
+}
+
+fn description(i: usize, sub: &ConfigSubnet) -> String {
+    format!("Subnet {} - {}", i + 1, sub.network)
+}
+
+fn cisco_ios(subnets: &[ConfigSubnet], o: &ConfigOptions) -> String {
+    let mut out = String::new();
+
+    if o.interfaces {
+        for (i, sub) in subnets.iter().enumerate() {
+            out.push_str(&format!("interface {}.{}\n", o.interface, i + 1));
+            out.push_str(&format!(" description {}\n", description(i, sub)));
+            out.push_str(&format!(" encapsulation dot1Q {}\n", i + 1));
+            match sub.network {
+                IpNet::V4(net) => out.push_str(&format!(" ip address {} {}\n", sub.gateway, net.netmask())),
+                IpNet::V6(net) => {
+                    out.push_str(&format!(" ipv6 address {}/{}\n", sub.gateway, net.prefix_len()));
+                    if let Some(area) = &o.ospf_area {
+                        out.push_str(&format!(" ipv6 ospf 1 area {}\n", area));
+                    }
+                }
+            }
+            out.push_str("!\n");
+        }
+    }
+
+    if let Some(nh) = &o.next_hop {
+        for sub in subnets {
+            match sub.network {
+                IpNet::V4(net) => out.push_str(&format!("ip route {} {} {}\n", net.network(), net.netmask(), nh)),
+                IpNet::V6(net) => out.push_str(&format!("ipv6 route {} {}\n", net, nh)),
+            }
+        }
+        out.push_str("!\n");
+    }
+
+    if let Some(name) = &o.prefix_list {
+        for (i, sub) in subnets.iter().enumerate() {
+            let family = if is_v6(&sub.network) { "ipv6" } else { "ip" };
+            out.push_str(&format!("{} prefix-list {} seq {} permit {}\n", family, name, (i + 1) * 10, sub.network));
+        }
+        out.push_str("!\n");
+    }
+
+    if let Some(area) = &o.ospf_area {
+        let v4: Vec<_> = subnets.iter().filter(|s| !is_v6(&s.network)).collect();
+        if !v4.is_empty() {
+            out.push_str("router ospf 1\n");
+            for sub in v4 {
+                out.push_str(&format!(" network {} {} area {}\n", sub.network.network(), sub.network.hostmask(), area));
+            }
+            out.push_str("!\n");
+        } else {
+            out.push_str("ipv6 router ospf 1\n!\n");
+        }
+    }
+
+    if let Some(asn) = o.bgp_asn {
+        out.push_str(&format!("router bgp {}\n", asn));
+        let (v4, v6): (Vec<_>, Vec<_>) = subnets.iter().partition(|s| !is_v6(&s.network));
+        if !v4.is_empty() {
+            out.push_str(" address-family ipv4 unicast\n");
+            for sub in v4 {
+                if let IpNet::V4(net) = sub.network {
+                    out.push_str(&format!("  network {} mask {}\n", net.network(), net.netmask()));
+                }
+            }
+            out.push_str(" exit-address-family\n");
+        }
+        if !v6.is_empty() {
+            out.push_str(" address-family ipv6 unicast\n");
+            for sub in v6 {
+                out.push_str(&format!("  network {}\n", sub.network));
+            }
+            out.push_str(" exit-address-family\n");
+        }
+        out.push_str("!\n");
+    }
+    out
+}
+
+fn junos(subnets: &[ConfigSubnet], o: &ConfigOptions) -> String {
+    let mut out = String::new();
+
+    if o.interfaces {
+        // Units with a vlan-id need 802.1Q tagging on the physical interface
+        out.push_str(&format!("set interfaces {} vlan-tagging\n", o.interface));
+        for (i, sub) in subnets.iter().enumerate() {
+            let unit = format!("set interfaces {} unit {}", o.interface, i + 1);
+            let family = if is_v6(&sub.network) { "inet6" } else { "inet" };
+            out.push_str(&format!("{} description \"{}\"\n", unit, description(i, sub)));
+            out.push_str(&format!("{} vlan-id {}\n", unit, i + 1));
+            out.push_str(&format!("{} family {} address {}/{}\n", unit, family, sub.gateway, sub.network.prefix_len()));
+        }
+    }
+
+    if let Some(nh) = &o.next_hop {
+        for sub in subnets {
+            if is_v6(&sub.network) {
+                out.push_str(&format!("set routing-options rib inet6.0 static route {} next-hop {}\n", sub.network, nh));
+            } else {
+                out.push_str(&format!("set routing-options static route {} next-hop {}\n", sub.network, nh));
+            }
+        }
+    }
+
+    if let Some(name) = &o.prefix_list {
+        for sub in subnets {
+            out.push_str(&format!("set policy-options prefix-list {} {}\n", name, sub.network));
+        }
+    }
+
+    // OSPF runs on the units, so it is only emitted alongside them
+    if let Some(area) = o.ospf_area.as_ref().filter(|_| o.interfaces) {
+        for (i, sub) in subnets.iter().enumerate() {
+            let protocol = if is_v6(&sub.network) { "ospf3" } else { "ospf" };
+            out.push_str(&format!("set protocols {} area {} interface {}.{}\n", protocol, area, o.interface, i + 1));
+        }
+    }
+
+    // Junos has no network statements: advertise through an export policy instead
+    if let Some(asn) = o.bgp_asn {
+        out.push_str(&format!("set routing-options autonomous-system {}\n", asn));
+        for (i, sub) in subnets.iter().enumerate() {
+            out.push_str(&format!(
+                "set policy-options policy-statement EXPORT-SUBNETS term subnet-{} from route-filter {} exact\n",
+                i + 1,
+                sub.network
+            ));
+            out.push_str(&format!("set policy-options policy-statement EXPORT-SUBNETS term subnet-{} then accept\n", i + 1));
+        }
+        out.push_str("set protocols bgp export EXPORT-SUBNETS\n");
+    }
+    out
+}
+
+fn arista_eos(subnets: &[ConfigSubnet], o: &ConfigOptions) -> String {
+    let mut out = String::new();
+
+    if o.interfaces {
+        for (i, sub) in subnets.iter().enumerate() {
+            out.push_str(&format!("interface {}.{}\n", o.interface, i + 1));
+            out.push_str(&format!("   description {}\n", description(i, sub)));
+            out.push_str(&format!("   encapsulation dot1q vlan {}\n", i + 1));
+            if is_v6(&sub.network) {
+                out.push_str(&format!("   ipv6 address {}/{}\n", sub.gateway, sub.network.prefix_len()));
+                if let Some(area) = &o.ospf_area {
+                    out.push_str(&format!("   ipv6 ospf 1 area {}\n", area));
+                }
+            } else {
+                out.push_str(&format!("   ip address {}/{}\n", sub.gateway, sub.network.prefix_len()));
+            }
+            out.push_str("!\n");
+        }
+    }
+
+    if let Some(nh) = &o.next_hop {
+        for sub in subnets {
+            let family = if is_v6(&sub.network) { "ipv6" } else { "ip" };
+            out.push_str(&format!("{} route {} {}\n", family, sub.network, nh));
+        }
+        out.push_str("!\n");
+    }
+
+    if let Some(name) = &o.prefix_list {
+        let (v4, v6): (Vec<_>, Vec<_>) = subnets.iter().partition(|s| !is_v6(&s.network));
+        for (i, sub) in v4.iter().enumerate() {
+            out.push_str(&format!("ip prefix-list {} seq {} permit {}\n", name, (i + 1) * 10, sub.network));
+        }
+        if !v6.is_empty() {
+            out.push_str(&format!("ipv6 prefix-list {}\n", name));
+            for (i, sub) in v6.iter().enumerate() {
+                out.push_str(&format!("   seq {} permit {}\n", (i + 1) * 10, sub.network));
+            }
+        }
+        out.push_str("!\n");
+    }
+
+    if let Some(area) = &o.ospf_area {
+        let v4: Vec<_> = subnets.iter().filter(|s| !is_v6(&s.network)).collect();
+        if !v4.is_empty() {
+            out.push_str("router ospf 1\n");
+            for sub in v4 {
+                out.push_str(&format!("   network {} area {}\n", sub.network, area));
+            }
+        } else {
+            out.push_str("ipv6 router ospf 1\n");
+        }
+        out.push_str("!\n");
+    }
+
+    if let Some(asn) = o.bgp_asn {
+        out.push_str(&format!("router bgp {}\n", asn));
+        let (v4, v6): (Vec<_>, Vec<_>) = subnets.iter().partition(|s| !is_v6(&s.network));
+        for sub in v4 {
+            out.push_str(&format!("   network {}\n", sub.network));
+        }
+        if !v6.is_empty() {
+            out.push_str("   address-family ipv6\n");
+            for sub in v6 {
+                out.push_str(&format!("      network {}\n", sub.network));
+            }
+        }
+        out.push_str("!\n");
+    }
+    out
+}
+
+// RouterOS v7 syntax
+fn routeros(subnets: &[ConfigSubnet], o: &ConfigOptions) -> String {
+    let mut out = String::new();
+
+    if o.interfaces {
+        out.push_str("/interface vlan\n");
+        for (i, _) in subnets.iter().enumerate() {
+            out.push_str(&format!("add interface={} name=vlan{} vlan-id={}\n", o.interface, i + 1, i + 1));
+        }
+        for (i, sub) in subnets.iter().enumerate() {
+            let (menu, extra) = if is_v6(&sub.network) { ("/ipv6 address", " advertise=yes") } else { ("/ip address", "") };
+            out.push_str(&format!(
+                "{} add address={}/{} interface=vlan{} comment=\"{}\"{}\n",
+                menu,
+                sub.gateway,
+                sub.network.prefix_len(),
+                i + 1,
+                description(i, sub),
+                extra
+            ));
+        }
+    }
+
+    if let Some(nh) = &o.next_hop {
+        for sub in subnets {
+            let menu = if is_v6(&sub.network) { "/ipv6 route" } else { "/ip route" };
+            out.push_str(&format!("{} add dst-address={} gateway={}\n", menu, sub.network, nh));
+        }
+    }
+
+    if let Some(name) = &o.prefix_list {
+        out.push_str("/routing filter rule\n");
+        for sub in subnets {
+            out.push_str(&format!("add chain={} rule=\"if (dst in {} && dst-len == {}) {{ accept }}\"\n", name, sub.network, sub.network.prefix_len()));
+        }
+    }
+
+    if let Some(area) = &o.ospf_area {
+        let (v4, v6): (Vec<_>, Vec<_>) = subnets.iter().partition(|s| !is_v6(&s.network));
+        for (version, nets) in [(2, v4), (3, v6)] {
+            if nets.is_empty() {
+                continue;
+            }
+            out.push_str(&format!("/routing ospf instance add name=ospf-v{} version={}\n", version, version));
+            out.push_str(&format!("/routing ospf area add name=area-v{} area-id={} instance=ospf-v{}\n", version, area, version));
+            let networks: Vec<String> = nets.iter().map(|s| s.network.to_string()).collect();
+            out.push_str(&format!("/routing ospf interface-template add area=area-v{} networks={}\n", version, networks.join(",")));
+        }
+    }
+
+    // BGP advertises the contents of an address list
+    if let Some(asn) = o.bgp_asn {
+        let (v4, v6): (Vec<_>, Vec<_>) = subnets.iter().partition(|s| !is_v6(&s.network));
+        for sub in v4 {
+            out.push_str(&format!("/ip firewall address-list add list=bgp-networks address={}\n", sub.network));
+        }
+        for sub in v6 {
+            out.push_str(&format!("/ipv6 firewall address-list add list=bgp-networks address={}\n", sub.network));
+        }
+        out.push_str(&format!("/routing bgp template set default as={} output.network=bgp-networks\n", asn));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::assert_golden;
+
+    fn subnets() -> Vec<ConfigSubnet> {
+        [("10.0.0.0/24", "10.0.0.1"), ("10.0.1.0/26", "10.0.1.1"), ("2001:db8:0:1::/64", "2001:db8:0:1::1")]
+            .iter()
+            .map(|(net, gw)| ConfigSubnet { network: net.parse().unwrap(), gateway: gw.parse().unwrap() })
+            .collect()
+    }
+
+    fn full_options(vendor: Vendor) -> ConfigOptions {
+        ConfigOptions {
+            next_hop: None,
+            prefix_list: Some("LAN".to_string()),
+            ospf_area: Some("0".to_string()),
+            bgp_asn: Some(65001),
+            ..ConfigOptions::new(vendor)
+        }
+    }
+
+    #[test]
+    fn golden_configs() {
+        // The panel sends one family at a time, each with a next hop of that family
+        let (v4, v6): (Vec<ConfigSubnet>, Vec<ConfigSubnet>) = subnets().into_iter().partition(|s| !is_v6(&s.network));
+        for vendor in Vendor::ALL {
+            let v4_options = ConfigOptions { next_hop: Some("192.0.2.1".to_string()), ..full_options(vendor) };
+            let v6_options = ConfigOptions { next_hop: Some("2001:db8::ffff".to_string()), ..full_options(vendor) };
+            let config = format!("{}\n{}", generate_config(&v4, &v4_options), generate_config(&v6, &v6_options));
+            assert_golden(&format!("router_{}.txt", vendor.key()), &config);
+        }
+    }
+
+    #[test]
+    fn junos_enables_vlan_tagging_once() {
+        let config = generate_config(&subnets(), &ConfigOptions::new(Vendor::Junos));
+        assert_eq!(config.matches("vlan-tagging").count(), 1);
+    }
+
+    #[test]
+    fn junos_ospf_needs_interfaces() {
+        // The other vendors use network statements; Junos names the units directly
+        let options = ConfigOptions { interfaces: false, ..full_options(Vendor::Junos) };
+        assert!(!generate_config(&subnets(), &options).contains("ospf"));
+    }
+}
@@ -0,0 +1,6 @@
+// src/router_config/mod.rs
+pub mod generator;
+pub mod panel;
+pub mod types;
+
+pub use panel::RouterConfigPanel;
@@ -0,0 +1,91 @@
+// src/router_config/panel.rs
+use dioxus::prelude::*;
+use crate::components::ExportButtons;
+use crate::router_config::generator::{generate_config, MAX_CONFIG_SUBNETS};
+use crate::router_config::types::{ConfigOptions, ConfigSubnet, Vendor};
+
+fn non_empty(value: &str) -> Option<String> {
+    let trimmed = value.trim();
+    (!trimmed.is_empty()).then(|| trimmed.to_string())
+}
+
+#[component]
+pub fn RouterConfigPanel(subnets: Vec<ConfigSubnet>) -> Element {
+    let mut vendor = use_signal(|| Vendor::CiscoIos);
+    let mut interface = use_signal(|| Vendor::CiscoIos.default_interface().to_string());
+    let mut interfaces = use_signal(|| true);
+    let mut next_hop = use_signal(String::new);
+    let mut prefix_list = use_signal(String::new);
+    let mut ospf_area = use_signal(String::new);
+    let mut bgp_asn = use_signal(String::new);
+
+    let options = ConfigOptions {
+        interface: interface(),
+        interfaces: interfaces(),
+        next_hop: non_empty(&next_hop()),
+        prefix_list: non_empty(&prefix_list()),
+        ospf_area: non_empty(&ospf_area()),
+        bgp_asn: bgp_asn().trim().parse().ok(),
+        ..ConfigOptions::new(vendor())
+    };
+    let config = generate_config(&subnets, &options);
+    let filename = format!("subnets-{}.txt", vendor().key());
+    let field = "w-full px-3 py-2 text-sm border border-gray-600 rounded-lg bg-gray-700";
+
+    rsx! {
+        div { class: "text-left",
+            div { class: "grid grid-cols-2 gap-3 mb-4 text-sm",
+                div {
+                    label { class: "block mb-1 font-medium", "Vendor" }
+                    select {
+                        class: "{field}",
+                        onchange: move |e| {
+                            let v = Vendor::from_key(&e.value());
+                            vendor.set(v);
+                            interface.set(v.default_interface().to_string());
+                        },
+                        for v in Vendor::ALL {
+                            option { value: v.key(), selected: vendor() == v, "{v.label()}" }
+                        }
+                    }
+                }
+                div {
+                    label { class: "block mb-1 font-medium", "Parent Interface" }
+                    input { class: "{field}", value: "{interface}", oninput: move |e| interface.set(e.value()) }
+                }
+                div {
+                    label { class: "block mb-1 font-medium", "Static Route Next Hop" }
+                    input { class: "{field}", placeholder: "none", value: "{next_hop}", oninput: move |e| next_hop.set(e.value()) }
+                }
+                div {
+                    label { class: "block mb-1 font-medium", "Prefix-List Name" }
+                    input { class: "{field}", placeholder: "none", value: "{prefix_list}", oninput: move |e| prefix_list.set(e.value()) }
+                }
+                div {
+                    label { class: "block mb-1 font-medium", "OSPF Area" }
+                    input { class: "{field}", placeholder: "none, e.g. 0", value: "{ospf_area}", oninput: move |e| ospf_area.set(e.value()) }
+                }
+                div {
+                    label { class: "block mb-1 font-medium", "BGP AS Number" }
+                    input { class: "{field}", placeholder: "none, e.g. 65000", value: "{bgp_asn}", oninput: move |e| bgp_asn.set(e.value()) }
+                }
+            }
+            div { class: "flex items-center justify-between mb-2",
+                label { class: "flex items-center text-sm",
+                    input {
+                        r#type: "checkbox",
+                        class: "mr-2",
+                        checked: interfaces(),
+                        onchange: move |e| interfaces.set(e.checked())
+                    }
+                    "Interface addressing (first host as gateway)"
+                }
+                ExportButtons { content: config.clone(), filename, mime: "text/plain" }
+            }
+            if subnets.len() > MAX_CONFIG_SUBNETS {
+                p { class: "text-xs text-gray-500 mb-2", "Configuration generated for the first {MAX_CONFIG_SUBNETS} of {subnets.len()} subnets." }
+            }
+            pre { class: "max-h-96 overflow-auto bg-gray-900 p-3 rounded text-xs font-roboto", "{config}" }
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use ipnet::IpNet;
+use std::net::IpAddr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Vendor {
+    CiscoIos,
+    Junos,
+    AristaEos,
+    RouterOs,
+}
+
+impl Vendor {
+    pub const ALL: [Vendor; 4] = [Vendor::CiscoIos, Vendor::Junos, Vendor::AristaEos, Vendor::RouterOs];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Vendor::CiscoIos => "Cisco IOS / IOS-XE",
+            Vendor::Junos => "Juniper Junos (set)",
+            Vendor::AristaEos => "Arista EOS",
+            Vendor::RouterOs => "MikroTik RouterOS",
+        }
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            Vendor::CiscoIos => "ios",
+            Vendor::Junos => "junos",
+            Vendor::AristaEos => "eos",
+            Vendor::RouterOs => "routeros",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Vendor {
+        Vendor::ALL.into_iter().find(|v| v.key() == key).unwrap_or(Vendor::CiscoIos)
+    }
+
+    pub fn default_interface(&self) -> &'static str {
+        match self {
+            Vendor::CiscoIos => "GigabitEthernet0/0",
+            Vendor::Junos => "ge-0/0/0",
+            Vendor::AristaEos => "Ethernet1",
+            Vendor::RouterOs => "ether2",
+        }
+    }
+}
+
+/// A subnet from either calculator, reduced to what the config needs
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConfigSubnet {
+    pub network: IpNet,
+    pub gateway: IpAddr, // First usable host, assigned to the router interface
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConfigOptions {
+    pub vendor: Vendor,
+    pub interface: String, // Parent interface; each subnet gets sub-interface/unit/VLAN N
+    pub interfaces: bool,
+    pub next_hop: Option<String>, // Static routes are only emitted when set
+    pub prefix_list: Option<String>,
+    pub ospf_area: Option<String>,
+    pub bgp_asn: Option<u32>,
+}
+
+impl ConfigOptions {
+    pub fn new(vendor: Vendor) -> Self {
+        ConfigOptions {
+            vendor,
+            interface: vendor.default_interface().to_string(),
+            interfaces: true,
+            next_hop: None,
+            prefix_list: None,
+            ospf_area: None,
+            bgp_asn: None,
+        }
+    }
+}
+
+impl From<&crate::ipv4::types::SubnetResult> for ConfigSubnet {
+    fn from(sub: &crate::ipv4::types::SubnetResult) -> Self {
+        let gateway = sub
+            .first_host
+            .as_deref()
+            .and_then(|h| h.parse().ok())
+            .unwrap_or(sub.network.network());
+        ConfigSubnet { network: IpNet::V4(sub.network), gateway: IpAddr::V4(gateway) }
+    }
+}
+
+impl From<&crate::ipv6::types::SubnetResult> for ConfigSubnet {
+    fn from(sub: &crate::ipv6::types::SubnetResult) -> Self {
+        // Skip the Subnet-Router anycast address (the all-zeros host)
+        let network = u128::from(sub.network.network());
+        let gateway = if sub.network.prefix_len() < 128 { network + 1 } else { network };
+        ConfigSubnet { network: IpNet::V6(sub.network), gateway: IpAddr::V6(gateway.into()) }
+    }
+}
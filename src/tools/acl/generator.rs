@@ -0,0 +1,422 @@
+// src/tools/acl/generator.rs
+use ipnet::{IpNet, Ipv4Net};
+use serde_json::json;
+use crate::address_math::{check_host_bits, HostBits, HostBitsPolicy};
+use crate::ipv4::calculator::parse_network;
+use crate::tools::acl::types::{AclAction, AclFormat, AclInputError, AclProtocol, AclSpec, Endpoint, PortRange};
+
+/// Parse one prefix per line: CIDR ("10.0.0.0/24", "2001:db8::/32"), a bare address,
+/// or IPv4 address + netmask ("10.0.0.0 255.255.255.0"). Blank lines and # comments are skipped.
+/// Prefixes with host bits set are normalized and listed, or reported as errors under `policy`.
+pub fn parse_prefix_lines(text: &str, policy: HostBitsPolicy) -> (Vec<IpNet>, Vec<AclInputError>, Vec<HostBits>) {
+    let mut nets = vec![];
+    let mut errors = vec![];
+    let mut normalized = vec![];
+
+    for (i, raw) in text.lines().enumerate() {
+        let line = raw.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        let parsed = if let Ok(net) = line.parse::<IpNet>() {
+            Some(net)
+        } else if let Ok(addr) = line.parse::<std::net::IpAddr>() {
+            Some(IpNet::from(addr))
+        } else {
+            let mut parts = line.split_whitespace();
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some(ip), Some(mask), None) => parse_network(ip, mask).ok().map(|n: Ipv4Net| IpNet::V4(n)),
+                _ => None,
+            }
+        };
+        match parsed.map(|net| (net, check_host_bits(net, policy))) {
+            Some((net, Ok(found))) => {
+                normalized.extend(found);
+                nets.push(net.trunc());
+            }
+            Some((_, Err(found))) => errors.push(AclInputError::HostBitsSet { line: i + 1, found: Box::new(found) }),
+            None => errors.push(AclInputError::InvalidLine { line: i + 1, text: line.to_string() }),
+        }
+    }
+    (nets, errors, normalized)
+}
+
+/// "443" or "8000-8080"
+pub fn parse_ports(text: &str) -> Result<Option<PortRange>, AclInputError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(None);
+    }
+    let (start, end) = text.split_once('-').unwrap_or((text, text));
+    match (start.trim().parse::<u16>(), end.trim().parse::<u16>()) {
+        (Ok(start), Ok(end)) if start <= end => Ok(Some(PortRange { start, end })),
+        _ => Err(AclInputError::InvalidPort(text.to_string())),
+    }
+}
+
+/// Merge adjacent and overlapping prefixes so each side needs as few entries as possible
+pub fn merge_prefixes(nets: &[IpNet]) -> Vec<IpNet> {
+    IpNet::aggregate(&nets.to_vec())
+}
+
+fn is_v6(net: &IpNet) -> bool {
+    matches!(net, IpNet::V6(_))
+}
+
+/// Source/destination endpoints per address family, after merging
+fn family_pairs(spec: &AclSpec) -> Vec<(bool, Vec<Endpoint>, Vec<Endpoint>)> {
+    let sources = merge_prefixes(&spec.sources);
+    let destinations = merge_prefixes(&spec.destinations);
+    let side = |nets: &[IpNet], v6: bool| -> Option<Vec<Endpoint>> {
+        if nets.is_empty() {
+            return Some(vec![Endpoint::Any]);
+        }
+        let matching: Vec<_> = nets.iter().filter(|n| is_v6(n) == v6).map(|&n| Endpoint::Net(n)).collect();
+        (!matching.is_empty()).then_some(matching)
+    };
+
+    let has_v4 = sources.iter().chain(&destinations).any(|n| !is_v6(n));
+    let has_v6 = sources.iter().chain(&destinations).any(is_v6);
+    let families: Vec<bool> = match (has_v4, has_v6) {
+        (false, true) => vec![true],
+        (true, true) => vec![false, true],
+        _ => vec![false],
+    };
+
+    families
+        .into_iter()
+        .filter_map(|v6| Some((v6, side(&sources, v6)?, side(&destinations, v6)?)))
+        .collect()
+}
+
+/// Number of rules/lines the merged spec expands to
+pub fn rule_count(spec: &AclSpec) -> usize {
+    family_pairs(spec).iter().map(|(_, s, d)| s.len() * d.len()).sum()
+}
+
+pub fn generate_acl(spec: &AclSpec, format: AclFormat) -> Result<String, AclInputError> {
+    // e.g. IPv4-only sources with IPv6-only destinations
+    if family_pairs(spec).is_empty() {
+        return Err(AclInputError::FamilyMismatch);
+    }
+    match format {
+        AclFormat::CiscoExtended => Ok(cisco(spec)),
+        AclFormat::Iptables => Ok(iptables(spec)),
+        AclFormat::Nftables => Ok(nftables(spec)),
+        AclFormat::AwsSecurityGroup => aws_security_group(spec),
+        AclFormat::AzureNsg => Ok(azure_nsg(spec)),
+    }
+}
+
+fn protocol_name(protocol: AclProtocol) -> &'static str {
+    protocol.key()
+}
+
+fn has_ports(spec: &AclSpec) -> Option<PortRange> {
+    spec.ports.filter(|_| matches!(spec.protocol, AclProtocol::Tcp | AclProtocol::Udp))
+}
+
+fn cisco_endpoint(endpoint: &Endpoint) -> String {
+    match endpoint {
+        Endpoint::Any => "any".to_string(),
+        Endpoint::Net(IpNet::V4(net)) if net.prefix_len() == 32 => format!("host {}", net.addr()),
+        Endpoint::Net(IpNet::V4(net)) if net.prefix_len() == 0 => "any".to_string(),
+        // Extended ACLs match with the wildcard (inverse) mask
+        Endpoint::Net(IpNet::V4(net)) => format!("{} {}", net.network(), net.hostmask()),
+        Endpoint::Net(IpNet::V6(net)) if net.prefix_len() == 128 => format!("host {}", net.addr()),
+        Endpoint::Net(net) => net.to_string(),
+    }
+}
+
+fn cisco(spec: &AclSpec) -> String {
+    let action = match spec.action {
+        AclAction::Permit => "permit",
+        AclAction::Deny => "deny",
+    };
+    let ports = match has_ports(spec) {
+        Some(p) if p.start == p.end => format!(" eq {}", p.start),
+        Some(p) => format!(" range {} {}", p.start, p.end),
+        None => String::new(),
+    };
+    let mut out = String::new();
+
+    for (v6, sources, destinations) in family_pairs(spec) {
+        let protocol = match (v6, spec.protocol) {
+            (true, AclProtocol::Ip) => "ipv6",
+            (true, AclProtocol::Icmp) => "icmp",
+            (_, p) => protocol_name(p),
+        };
+        if v6 {
+            out.push_str(&format!("ipv6 access-list {}\n", spec.name));
+        } else {
+            out.push_str(&format!("ip access-list extended {}\n", spec.name));
+        }
+        for src in &sources {
+            for dst in &destinations {
+                out.push_str(&format!(" {} {} {} {}{}\n", action, protocol, cisco_endpoint(src), cisco_endpoint(dst), ports));
+            }
+        }
+        out.push_str("!\n");
+    }
+    out
+}
+
+fn iptables(spec: &AclSpec) -> String {
+    let target = match spec.action {
+        AclAction::Permit => "ACCEPT",
+        AclAction::Deny => "DROP",
+    };
+    let mut out = String::new();
+
+    for (v6, sources, destinations) in family_pairs(spec) {
+        let binary = if v6 { "ip6tables" } else { "iptables" };
+        let protocol = match (v6, spec.protocol) {
+            (_, AclProtocol::Ip) => String::new(),
+            (true, AclProtocol::Icmp) => " -p ipv6-icmp".to_string(),
+            (_, p) => format!(" -p {}", protocol_name(p)),
+        };
+        let ports = match has_ports(spec) {
+            Some(p) if p.start == p.end => format!(" --dport {}", p.start),
+            Some(p) => format!(" --dport {}:{}", p.start, p.end),
+            None => String::new(),
+        };
+        for src in &sources {
+            for dst in &destinations {
+                let src = match src { Endpoint::Any => String::new(), Endpoint::Net(n) => format!(" -s {}", n) };
+                let dst = match dst { Endpoint::Any => String::new(), Endpoint::Net(n) => format!(" -d {}", n) };
+                out.push_str(&format!("{} -A FORWARD{}{}{}{} -j {}\n", binary, src, dst, protocol, ports, target));
+            }
+        }
+    }
+    out
+}
+
+// nftables matches whole sets at once, so each family needs a single rule
+fn nftables(spec: &AclSpec) -> String {
+    let verdict = match spec.action {
+        AclAction::Permit => "accept",
+        AclAction::Deny => "drop",
+    };
+    let mut out = String::from("table inet filter {\n  chain forward {\n    type filter hook forward priority 0;\n");
+
+    for (v6, sources, destinations) in family_pairs(spec) {
+        let family = if v6 { "ip6" } else { "ip" };
+        let set = |endpoints: &[Endpoint]| -> Option<String> {
+            let nets: Vec<String> = endpoints
+                .iter()
+                .filter_map(|e| match e { Endpoint::Net(n) => Some(n.to_string()), Endpoint::Any => None })
+                .collect();
+            match nets.len() {
+                0 => None,
+                1 => Some(nets[0].clone()),
+                _ => Some(format!("{{ {} }}", nets.join(", "))),
+            }
+        };
+        let mut rule = String::from("    ");
+        if let Some(s) = set(&sources) {
+            rule.push_str(&format!("{} saddr {} ", family, s));
+        }
+        if let Some(d) = set(&destinations) {
+            rule.push_str(&format!("{} daddr {} ", family, d));
+        }
+        match (spec.protocol, has_ports(spec)) {
+            (AclProtocol::Tcp | AclProtocol::Udp, Some(p)) if p.start == p.end => {
+                rule.push_str(&format!("{} dport {} ", protocol_name(spec.protocol), p.start))
+            }
+            (AclProtocol::Tcp | AclProtocol::Udp, Some(p)) => {
+                rule.push_str(&format!("{} dport {}-{} ", protocol_name(spec.protocol), p.start, p.end))
+            }
+            (AclProtocol::Tcp | AclProtocol::Udp, None) => {
+                rule.push_str(&format!("meta l4proto {} ", protocol_name(spec.protocol)))
+            }
+            (AclProtocol::Icmp, _) => rule.push_str(if v6 { "meta l4proto ipv6-icmp " } else { "meta l4proto icmp " }),
+            (AclProtocol::Ip, _) => {
+                // Without an address match the rule would apply to both families
+                if set(&sources).is_none() && set(&destinations).is_none() {
+                    rule.push_str(&format!("meta nfproto {} ", if v6 { "ipv6" } else { "ipv4" }));
+                }
+            }
+        }
+        rule.push_str(verdict);
+        rule.push('\n');
+        out.push_str(&rule);
+    }
+    out.push_str("  }\n}\n");
+    out
+}
+
+// Ingress permissions for `aws ec2 authorize-security-group-ingress --ip-permissions`;
+// the destination is the security group itself
+fn aws_security_group(spec: &AclSpec) -> Result<String, AclInputError> {
+    if spec.action == AclAction::Deny {
+        return Err(AclInputError::DenyNotSupported);
+    }
+    if !spec.destinations.is_empty() {
+        return Err(AclInputError::DestinationsNotSupported);
+    }
+    let sources = merge_prefixes(&spec.sources);
+    let ip_ranges: Vec<_> = sources
+        .iter()
+        .filter(|n| !is_v6(n))
+        .map(|n| json!({ "CidrIp": n.to_string(), "Description": spec.name }))
+        .collect();
+    let ipv6_ranges: Vec<_> = sources
+        .iter()
+        .filter(|n| is_v6(n))
+        .map(|n| json!({ "CidrIpv6": n.to_string(), "Description": spec.name }))
+        .collect();
+    let (ip_ranges, ipv6_ranges) = if sources.is_empty() {
+        (vec![json!({ "CidrIp": "0.0.0.0/0", "Description": spec.name })], vec![json!({ "CidrIpv6": "::/0", "Description": spec.name })])
+    } else {
+        (ip_ranges, ipv6_ranges)
+    };
+
+    let mut permission = json!({
+        "IpProtocol": match spec.protocol { AclProtocol::Ip => "-1", p => protocol_name(p) },
+        "IpRanges": ip_ranges,
+        "Ipv6Ranges": ipv6_ranges,
+    });
+    match (spec.protocol, has_ports(spec)) {
+        (AclProtocol::Tcp | AclProtocol::Udp, Some(p)) => {
+            permission["FromPort"] = json!(p.start);
+            permission["ToPort"] = json!(p.end);
+        }
+        (AclProtocol::Tcp | AclProtocol::Udp, None) => {
+            permission["FromPort"] = json!(0);
+            permission["ToPort"] = json!(65535);
+        }
+        (AclProtocol::Icmp, _) => {
+            permission["FromPort"] = json!(-1);
+            permission["ToPort"] = json!(-1);
+        }
+        _ => {}
+    }
+    Ok(serde_json::to_string_pretty(&json!([permission])).unwrap_or_default())
+}
+
+// `securityRules` entries for an ARM template; a rule can't mix address families,
+// so each family gets its own rule and priority
+fn azure_nsg(spec: &AclSpec) -> String {
+    // "Any" must use the singular field: the plural arrays only accept concrete prefixes
+    let set_addresses = |properties: &mut serde_json::Value, side: &str, endpoints: &[Endpoint]| {
+        if endpoints.iter().any(|e| matches!(e, Endpoint::Any)) {
+            properties[format!("{}AddressPrefix", side)] = json!("*");
+        } else {
+            let prefixes: Vec<String> = endpoints.iter().filter_map(|e| match e { Endpoint::Any => None, Endpoint::Net(n) => Some(n.to_string()) }).collect();
+            properties[format!("{}AddressPrefixes", side)] = json!(prefixes);
+        }
+    };
+    let port = match has_ports(spec) {
+        Some(p) if p.start == p.end => p.start.to_string(),
+        Some(p) => format!("{}-{}", p.start, p.end),
+        None => "*".to_string(),
+    };
+    let pairs = family_pairs(spec);
+    let rules: Vec<_> = pairs
+        .iter()
+        .enumerate()
+        .map(|(i, (v6, sources, destinations))| {
+            let name = if pairs.len() > 1 { format!("{}-{}", spec.name, if *v6 { "v6" } else { "v4" }) } else { spec.name.clone() };
+            let mut properties = json!({
+                "priority": 100 + i * 10,
+                "direction": "Inbound",
+                "access": match spec.action { AclAction::Permit => "Allow", AclAction::Deny => "Deny" },
+                "protocol": match spec.protocol {
+                    AclProtocol::Ip => "*",
+                    AclProtocol::Tcp => "Tcp",
+                    AclProtocol::Udp => "Udp",
+                    AclProtocol::Icmp => "Icmp",
+                },
+                "sourcePortRange": "*",
+                "destinationPortRange": port,
+            });
+            set_addresses(&mut properties, "source", sources);
+            set_addresses(&mut properties, "destination", destinations);
+            json!({ "name": name, "properties": properties })
+        })
+        .collect();
+    serde_json::to_string_pretty(&json!({ "securityRules": rules })).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::assert_golden;
+
+    fn nets(list: &[&str]) -> Vec<IpNet> {
+        list.iter().map(|s| s.parse().unwrap()).collect()
+    }
+
+    fn spec() -> AclSpec {
+        AclSpec {
+            name: "WEB-IN".to_string(),
+            action: AclAction::Permit,
+            protocol: AclProtocol::Tcp,
+            ports: Some(PortRange { start: 443, end: 443 }),
+            sources: nets(&["10.0.0.0/25", "10.0.0.128/25", "2001:db8::/48"]),
+            destinations: nets(&["192.0.2.10/32", "2001:db8:ffff::10/128"]),
+        }
+    }
+
+    #[test]
+    fn parses_prefix_lines_and_ports() {
+        let text = "10.0.0.5/24\n192.0.2.1\n# skip\n10.1.0.0 255.255.0.0\nnot a prefix";
+        let (parsed, errors, normalized) = parse_prefix_lines(text, HostBitsPolicy::Normalize);
+        assert_eq!(parsed, nets(&["10.0.0.0/24", "192.0.2.1/32", "10.1.0.0/16"]));
+        assert_eq!(errors, vec![AclInputError::InvalidLine { line: 5, text: "not a prefix".into() }]);
+        assert_eq!(normalized.iter().map(|h| h.input).collect::<Vec<_>>(), nets(&["10.0.0.5/24"]));
+        let (strict, errors, _) = parse_prefix_lines(text, HostBitsPolicy::Reject);
+        assert_eq!(strict, nets(&["192.0.2.1/32", "10.1.0.0/16"]));
+        assert!(matches!(errors[0], AclInputError::HostBitsSet { line: 1, .. }));
+        assert_eq!(parse_ports(""), Ok(None));
+        assert_eq!(parse_ports("8000-8080"), Ok(Some(PortRange { start: 8000, end: 8080 })));
+        assert!(parse_ports("9000-8000").is_err());
+        assert!(parse_ports("70000").is_err());
+    }
+
+    #[test]
+    fn merges_and_splits_by_family() {
+        assert_eq!(merge_prefixes(&spec().sources), nets(&["10.0.0.0/24", "2001:db8::/48"]));
+        // One IPv4 and one IPv6 source/destination pair
+        assert_eq!(rule_count(&spec()), 2);
+        let any_source = AclSpec { sources: vec![], ..spec() };
+        assert_eq!(rule_count(&any_source), 2);
+    }
+
+    #[test]
+    fn security_groups_cannot_deny_or_filter_destinations() {
+        let deny = AclSpec { action: AclAction::Deny, destinations: vec![], ..spec() };
+        assert_eq!(generate_acl(&deny, AclFormat::AwsSecurityGroup), Err(AclInputError::DenyNotSupported));
+        assert_eq!(generate_acl(&spec(), AclFormat::AwsSecurityGroup), Err(AclInputError::DestinationsNotSupported));
+    }
+
+    #[test]
+    fn rejects_sources_and_destinations_of_different_families() {
+        let mismatch = AclSpec { sources: nets(&["10.0.0.0/24"]), destinations: nets(&["2001:db8::/64"]), ..spec() };
+        assert_eq!(rule_count(&mismatch), 0);
+        for format in AclFormat::ALL {
+            assert_eq!(generate_acl(&mismatch, format), Err(AclInputError::FamilyMismatch));
+        }
+        let reverse = AclSpec { sources: nets(&["2001:db8::/64"]), destinations: nets(&["10.0.0.0/24"]), ..spec() };
+        assert_eq!(generate_acl(&reverse, AclFormat::Iptables), Err(AclInputError::FamilyMismatch));
+    }
+
+    #[test]
+    fn azure_any_uses_the_singular_field() {
+        let any = AclSpec { sources: vec![], destinations: nets(&["10.0.0.0/24"]), ..spec() };
+        let output = generate_acl(&any, AclFormat::AzureNsg).unwrap();
+        assert!(output.contains(r#""sourceAddressPrefix": "*""#));
+        assert!(!output.contains("sourceAddressPrefixes"));
+    }
+
+    #[test]
+    fn golden_rules() {
+        for format in AclFormat::ALL {
+            let spec = match format {
+                AclFormat::AwsSecurityGroup => AclSpec { destinations: vec![], ..spec() },
+                _ => spec(),
+            };
+            assert_golden(&format!("acl_{}.txt", format.key()), &generate_acl(&spec, format).unwrap());
+        }
+    }
+}
@@ -0,0 +1,132 @@
+// src/tools/acl/panel.rs
+use dioxus::prelude::*;
+use crate::address_math::HostBitsPolicy;
+use crate::components::{ExportButtons, HostBitsToggle};
+use crate::tools::acl::generator::{generate_acl, merge_prefixes, parse_ports, parse_prefix_lines, rule_count};
+use crate::tools::acl::types::{AclAction, AclFormat, AclProtocol, AclSpec};
+
+#[component]
+pub fn AclTool() -> Element {
+    let mut sources_input = use_signal(|| "10.0.0.0/25\n10.0.0.128/25\n10.0.1.0/24".to_string());
+    let mut destinations_input = use_signal(|| "192.168.10.0/24".to_string());
+    let mut action = use_signal(|| AclAction::Permit);
+    let mut protocol = use_signal(|| AclProtocol::Tcp);
+    let mut ports_input = use_signal(|| "443".to_string());
+    let mut name = use_signal(|| "ALLOW-APP".to_string());
+    let mut format = use_signal(|| AclFormat::CiscoExtended);
+    let host_bits_policy = use_signal(HostBitsPolicy::default);
+
+    let (sources, source_errors, source_normalized) = parse_prefix_lines(&sources_input(), host_bits_policy());
+    let (destinations, destination_errors, destination_normalized) = parse_prefix_lines(&destinations_input(), host_bits_policy());
+    let notes: Vec<String> = source_normalized
+        .iter()
+        .map(|h| format!("Sources: {}", h))
+        .chain(destination_normalized.iter().map(|h| format!("Destinations: {}", h)))
+        .collect();
+    let ports = parse_ports(&ports_input());
+    let mut errors: Vec<String> = source_errors.iter().map(|e| format!("Sources: {}", e)).collect();
+    errors.extend(destination_errors.iter().map(|e| format!("Destinations: {}", e)));
+
+    let merged_sources = merge_prefixes(&sources).len();
+    let merged_destinations = merge_prefixes(&destinations).len();
+    let output = ports.clone().and_then(|ports| {
+        let spec = AclSpec {
+            name: name().trim().to_string(),
+            action: action(),
+            protocol: protocol(),
+            ports,
+            sources: sources.clone(),
+            destinations: destinations.clone(),
+        };
+        generate_acl(&spec, format()).map(|text| (text, rule_count(&spec)))
+    });
+    if let Err(err) = &output {
+        errors.push(err.to_string());
+    }
+
+    let field = "w-full px-3 py-2 text-sm border border-gray-600 rounded-lg bg-gray-700";
+    let area = "w-full h-32 px-3 py-2 text-sm font-roboto border border-gray-600 rounded-lg bg-gray-700";
+
+    rsx! {
+        div { class: "grid grid-cols-3 gap-4 text-left",
+            div { class: "w-full bg-gray-800 rounded-lg shadow-lg p-6 flex flex-col",
+                h2 { class: "text-2xl font-bold mb-6 text-center", "Rule Definition" }
+                div { class: "mb-4",
+                    label { class: "block text-sm font-medium mb-2", "Source Prefixes (one per line, empty = any)" }
+                    textarea { class: "{area}", value: "{sources_input}", oninput: move |e| sources_input.set(e.value()) }
+                }
+                div { class: "mb-4",
+                    label { class: "block text-sm font-medium mb-2", "Destination Prefixes (one per line, empty = any)" }
+                    textarea { class: "{area}", value: "{destinations_input}", oninput: move |e| destinations_input.set(e.value()) }
+                }
+                HostBitsToggle { policy: host_bits_policy }
+                div { class: "grid grid-cols-2 gap-3 text-sm",
+                    div {
+                        label { class: "block mb-1 font-medium", "Action" }
+                        select {
+                            class: "{field}",
+                            onchange: move |e| action.set(if e.value() == "deny" { AclAction::Deny } else { AclAction::Permit }),
+                            option { value: "permit", selected: action() == AclAction::Permit, "Permit" }
+                            option { value: "deny", selected: action() == AclAction::Deny, "Deny" }
+                        }
+                    }
+                    div {
+                        label { class: "block mb-1 font-medium", "Protocol" }
+                        select {
+                            class: "{field}",
+                            onchange: move |e| protocol.set(AclProtocol::from_key(&e.value())),
+                            for p in AclProtocol::ALL {
+                                option { value: p.key(), selected: protocol() == p, "{p.key()}" }
+                            }
+                        }
+                    }
+                    div {
+                        label { class: "block mb-1 font-medium", "Destination Port(s)" }
+                        input {
+                            class: "{field}",
+                            placeholder: "e.g. 443 or 8000-8080",
+                            disabled: !matches!(protocol(), AclProtocol::Tcp | AclProtocol::Udp),
+                            value: "{ports_input}",
+                            oninput: move |e| ports_input.set(e.value())
+                        }
+                    }
+                    div {
+                        label { class: "block mb-1 font-medium", "ACL / Rule Name" }
+                        input { class: "{field}", value: "{name}", oninput: move |e| name.set(e.value()) }
+                    }
+                }
+            }
+
+            div { class: "col-span-2 bg-gray-800 rounded-lg shadow-lg p-6 overflow-auto",
+                h2 { class: "text-xl font-bold mb-6 text-center", "Generated Rules" }
+                div { class: "flex items-center justify-between mb-3 text-sm",
+                    select {
+                        class: "px-3 py-2 border border-gray-600 rounded-lg bg-gray-700",
+                        onchange: move |e| format.set(AclFormat::from_key(&e.value())),
+                        for f in AclFormat::ALL {
+                            option { value: f.key(), selected: format() == f, "{f.label()}" }
+                        }
+                    }
+                    if let Ok((text, _)) = &output {
+                        ExportButtons { content: text.clone(), filename: format().file_name().to_string(), mime: format().mime() }
+                    }
+                }
+                p { class: "text-xs text-gray-400 mb-3",
+                    "Sources: {sources.len()} → {merged_sources} after merging · Destinations: {destinations.len()} → {merged_destinations} after merging"
+                    if let Ok((_, count)) = &output {
+                        " · {count} rule(s)"
+                    }
+                }
+                for err in errors.iter() {
+                    p { class: "text-sm text-red-400 mb-1", "{err}" }
+                }
+                for note in notes.iter() {
+                    p { class: "text-sm text-yellow-400 mb-1", "Normalized: {note}" }
+                }
+                if let Ok((text, _)) = &output {
+                    pre { class: "max-h-96 overflow-auto bg-gray-900 p-3 rounded text-xs font-roboto", "{text}" }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use ipnet::IpNet;
+use crate::address_math::HostBits;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AclAction {
+    Permit,
+    Deny,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AclProtocol {
+    Ip,
+    Tcp,
+    Udp,
+    Icmp,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AclFormat {
+    CiscoExtended,
+    Iptables,
+    Nftables,
+    AwsSecurityGroup,
+    AzureNsg,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PortRange {
+    pub start: u16,
+    pub end: u16,
+}
+
+/// One side of a rule; an empty prefix list means "any"
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Endpoint {
+    Any,
+    Net(IpNet),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AclSpec {
+    pub name: String,
+    pub action: AclAction,
+    pub protocol: AclProtocol,
+    pub ports: Option<PortRange>, // Destination ports, TCP/UDP only
+    pub sources: Vec<IpNet>,
+    pub destinations: Vec<IpNet>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AclInputError {
+    InvalidLine { line: usize, text: String },
+    HostBitsSet { line: usize, found: Box<HostBits> }, // Strict mode only
+    InvalidPort(String),
+    DenyNotSupported,         // Security groups only have allow rules
+    FamilyMismatch,           // Sources and destinations share no address family, so no rule can match
+    DestinationsNotSupported, // Security groups only filter sources
+}
+
+impl AclFormat {
+    pub const ALL: [AclFormat; 5] = [
+        AclFormat::CiscoExtended,
+        AclFormat::Iptables,
+        AclFormat::Nftables,
+        AclFormat::AwsSecurityGroup,
+        AclFormat::AzureNsg,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AclFormat::CiscoExtended => "Cisco Extended ACL",
+            AclFormat::Iptables => "iptables / ip6tables",
+            AclFormat::Nftables => "nftables",
+            AclFormat::AwsSecurityGroup => "AWS Security Group (JSON)",
+            AclFormat::AzureNsg => "Azure NSG (JSON)",
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            AclFormat::CiscoExtended => "cisco",
+            AclFormat::Iptables => "iptables",
+            AclFormat::Nftables => "nftables",
+            AclFormat::AwsSecurityGroup => "aws",
+            AclFormat::AzureNsg => "azure",
+        }
+    }
+
+    pub fn from_key(key: &str) -> AclFormat {
+        AclFormat::ALL.into_iter().find(|f| f.key() == key).unwrap_or(AclFormat::CiscoExtended)
+    }
+
+    pub fn file_name(self) -> &'static str {
+        match self {
+            AclFormat::CiscoExtended => "acl-cisco.txt",
+            AclFormat::Iptables => "acl-iptables.sh",
+            AclFormat::Nftables => "acl.nft",
+            AclFormat::AwsSecurityGroup => "acl-aws-sg.json",
+            AclFormat::AzureNsg => "acl-azure-nsg.json",
+        }
+    }
+
+    pub fn mime(self) -> &'static str {
+        match self {
+            AclFormat::AwsSecurityGroup | AclFormat::AzureNsg => "application/json",
+            _ => "text/plain",
+        }
+    }
+}
+
+impl AclProtocol {
+    pub const ALL: [AclProtocol; 4] = [AclProtocol::Ip, AclProtocol::Tcp, AclProtocol::Udp, AclProtocol::Icmp];
+
+    pub fn key(self) -> &'static str {
+        match self {
+            AclProtocol::Ip => "ip",
+            AclProtocol::Tcp => "tcp",
+            AclProtocol::Udp => "udp",
+            AclProtocol::Icmp => "icmp",
+        }
+    }
+
+    pub fn from_key(key: &str) -> AclProtocol {
+        AclProtocol::ALL.into_iter().find(|p| p.key() == key).unwrap_or(AclProtocol::Ip)
+    }
+}
+
+impl std::fmt::Display for AclInputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AclInputError::InvalidLine { line, text } => write!(f, "Line {}: '{}' is not a valid prefix", line, text),
+            AclInputError::HostBitsSet { line, found } => write!(f, "Line {}: {}", line, found),
+            AclInputError::InvalidPort(text) => write!(f, "Invalid port or port range '{}'", text),
+            AclInputError::DenyNotSupported => write!(f, "Security groups only support allow rules"),
+            AclInputError::FamilyMismatch => write!(f, "Sources and destinations have no address family in common, so no rules can be generated"),
+            AclInputError::DestinationsNotSupported => {
+                write!(f, "Security groups only filter sources; the group itself is the destination, so leave destinations empty")
+            }
+        }
+    }
+}
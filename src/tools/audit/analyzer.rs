@@ -0,0 +1,240 @@
+// src/tools/audit/analyzer.rs
+use ipnet::{IpNet, Ipv6Net};
+use std::net::Ipv4Addr;
+use crate::address_math::{check_host_bits, HostBitsPolicy};
+use crate::ipv4::calculator::parse_network;
+use crate::tools::audit::types::{ImportEntry, ImportIssue, ImportReport, ParentUsage};
+
+fn size_of(net: &IpNet) -> u128 {
+    let bits = match net {
+        IpNet::V4(_) => 32,
+        IpNet::V6(_) => 128,
+    };
+    1u128.checked_shl(bits - net.prefix_len() as u32).unwrap_or(u128::MAX)
+}
+
+/// Digits with dots or any colon: something meant to be an IPv4 or IPv6 address
+fn looks_like_address(field: &str) -> bool {
+    field.contains(':') || (field.contains('.') && field.chars().any(|c| c.is_ascii_digit()))
+}
+
+/// Find the prefix among a line's columns. IPv4 goes through `parse_network`, so
+/// "10.0.0.0/24", "10.0.0.0 /24" and "10.0.0.0, 255.255.255.0" are all accepted.
+/// Columns that fail to parse are skipped; the first failure's reason is kept in case none parse.
+/// The prefix comes back as typed, host bits included.
+fn parse_fields(fields: &[&str]) -> Result<(IpNet, Vec<usize>), Option<String>> {
+    let mut reason = None;
+    for (i, field) in fields.iter().enumerate() {
+        let field = field.trim();
+        let parsed = if let Some((ip, prefix)) = field.split_once('/') {
+            if ip.contains(':') {
+                field.parse::<Ipv6Net>().map(|net| (IpNet::V6(net), vec![i])).map_err(|e| e.to_string())
+            } else {
+                parse_network(ip, prefix).map(|net| (IpNet::V4(net), vec![i])).map_err(|e| e.to_string())
+            }
+        } else if field.contains(':') {
+            field.parse::<std::net::Ipv6Addr>().map(|addr| (IpNet::V6(Ipv6Net::from(addr)), vec![i])).map_err(|e| e.to_string())
+        } else if field.parse::<Ipv4Addr>().is_ok() {
+            // Dotted mask or "/len" in the next column; a bare number could just as well be a VLAN
+            match fields.get(i + 1).filter(|m| m.contains('.') || m.starts_with('/')) {
+                Some(mask) => parse_network(field, mask).map(|net| (IpNet::V4(net), vec![i, i + 1])).map_err(|e| e.to_string()),
+                None => parse_network(field, "32").map(|net| (IpNet::V4(net), vec![i])).map_err(|e| e.to_string()),
+            }
+        } else if looks_like_address(field) {
+            parse_network(field, "32").map(|net| (IpNet::V4(net), vec![i])).map_err(|e| e.to_string())
+        } else {
+            continue;
+        };
+        match parsed {
+            Ok(found) => return Ok(found),
+            Err(e) if reason.is_none() && looks_like_address(field) => reason = Some(format!("'{}': {}", field, e)),
+            Err(_) => {}
+        }
+    }
+    Err(reason)
+}
+
+/// Parse a CSV/TSV/plain list of prefixes, then check it for duplicates and nesting.
+/// Prefixes with host bits set are kept as their network, or rejected as invalid under `policy`.
+pub fn analyze_import(text: &str, policy: HostBitsPolicy) -> ImportReport {
+    let mut report = ImportReport::default();
+
+    for (i, raw) in text.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line
+            .split([',', ';', '\t'])
+            // Plain lists may separate address, mask and name with spaces
+            .flat_map(|f| f.trim().trim_matches('"').split_whitespace())
+            .collect();
+
+        match parse_fields(&fields) {
+            Ok((typed, used)) => {
+                match check_host_bits(typed, policy) {
+                    Ok(None) => {}
+                    Ok(Some(found)) => report.normalized.push(ImportIssue::HostBitsSet { line: i + 1, found }),
+                    Err(found) => {
+                        report.invalid.push(ImportIssue::InvalidLine { line: i + 1, text: line.to_string(), reason: found.to_string() });
+                        continue;
+                    }
+                }
+                let prefix = typed.trunc();
+                let name = fields
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, f)| !used.contains(j) && !f.is_empty())
+                    .map(|(_, f)| *f)
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                report.entries.push(ImportEntry { line: i + 1, prefix, name });
+            }
+            // A first line where nothing even looks like an address is taken to be a header row
+            Err(None) if report.entries.is_empty() && report.invalid.is_empty() && !report.skipped_header => {
+                report.skipped_header = true;
+            }
+            Err(reason) => report.invalid.push(ImportIssue::InvalidLine {
+                line: i + 1,
+                text: line.to_string(),
+                reason: reason.unwrap_or_else(|| "No address or prefix found".to_string()),
+            }),
+        }
+    }
+
+    find_overlaps(&mut report);
+    report
+}
+
+/// CIDR blocks either nest or are disjoint, so after sorting by (network, length) a stack of
+/// open containers finds every duplicate and each entry's closest enclosing entry
+fn find_overlaps(report: &mut ImportReport) {
+    let mut sorted: Vec<&ImportEntry> = report.entries.iter().collect();
+    sorted.sort_by_key(|e| (e.prefix.network(), e.prefix.prefix_len(), e.line));
+
+    let mut stack: Vec<&ImportEntry> = vec![];
+    let mut children: Vec<(usize, u128, usize)> = vec![]; // (parent's line number, addresses used, child count)
+
+    for entry in sorted {
+        while stack.last().is_some_and(|top| !top.prefix.contains(&entry.prefix)) {
+            stack.pop();
+        }
+        match stack.last() {
+            Some(top) if top.prefix == entry.prefix => {
+                report.overlaps.push(ImportIssue::Duplicate { first_line: top.line, line: entry.line, prefix: entry.prefix });
+                continue;
+            }
+            Some(top) => {
+                report.overlaps.push(ImportIssue::Contained {
+                    parent_line: top.line,
+                    line: entry.line,
+                    parent: top.prefix,
+                    prefix: entry.prefix,
+                });
+                match children.iter_mut().find(|(line, _, _)| *line == top.line) {
+                    Some(usage) => {
+                        usage.1 += size_of(&entry.prefix);
+                        usage.2 += 1;
+                    }
+                    None => children.push((top.line, size_of(&entry.prefix), 1)),
+                }
+            }
+            None => {}
+        }
+        stack.push(entry);
+    }
+
+    report.parents = children
+        .into_iter()
+        .filter_map(|(line, used, count)| {
+            let parent = report.entries.iter().find(|e| e.line == line)?;
+            Some(ParentUsage {
+                line,
+                prefix: parent.prefix,
+                name: parent.name.clone(),
+                children: count,
+                used,
+                size: size_of(&parent.prefix),
+            })
+        })
+        .collect();
+    report.parents.sort_by_key(|p| (p.prefix.network(), p.prefix.prefix_len()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn net(s: &str) -> IpNet {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn reads_csv_with_header_masks_and_names() {
+        let report = analyze_import("name,prefix\ncorp,10.0.0.0/16\nusers,10.0.0.0/22\nservers,10.0.4.0 255.255.255.0\nservers-dup,10.0.4.0/24", HostBitsPolicy::Normalize);
+        assert!(report.skipped_header);
+        let entries: Vec<(usize, IpNet, &str)> = report.entries.iter().map(|e| (e.line, e.prefix, e.name.as_str())).collect();
+        assert_eq!(entries, [
+            (2, net("10.0.0.0/16"), "corp"),
+            (3, net("10.0.0.0/22"), "users"),
+            (4, net("10.0.4.0/24"), "servers"),
+            (5, net("10.0.4.0/24"), "servers-dup"),
+        ]);
+        assert!(report.overlaps.contains(&ImportIssue::Duplicate { first_line: 4, line: 5, prefix: net("10.0.4.0/24") }));
+        assert!(report.overlaps.contains(&ImportIssue::Contained { parent_line: 2, line: 3, parent: net("10.0.0.0/16"), prefix: net("10.0.0.0/22") }));
+
+        let corp = &report.parents[0];
+        assert_eq!((corp.line, corp.children, corp.used, corp.size), (2, 2, 1024 + 256, 65536));
+    }
+
+    #[test]
+    fn reports_why_a_line_is_invalid() {
+        let report = analyze_import("10.0.0.0/8\nlab,10.0.300.0/24\nvlan 12 only\n10.0.0.0/33", HostBitsPolicy::Normalize);
+        assert!(!report.skipped_header);
+        assert_eq!(report.entries.len(), 1);
+        let reasons: Vec<(usize, &str)> = report
+            .invalid
+            .iter()
+            .filter_map(|issue| match issue {
+                ImportIssue::InvalidLine { line, reason, .. } => Some((*line, reason.as_str())),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(reasons.len(), 3);
+        assert!(reasons[0].1.starts_with("'10.0.300.0/24'"));
+        assert_eq!(reasons[1], (3, "No address or prefix found"));
+        assert_eq!(reasons[2].0, 4);
+    }
+
+    #[test]
+    fn keeps_scanning_after_a_bad_column() {
+        // The description looks like an address but isn't; the real prefix comes later
+        let report = analyze_import("v1.2 upgrade;10.1.0.0/24;vlan 20", HostBitsPolicy::Normalize);
+        assert_eq!(report.entries[0].prefix, net("10.1.0.0/24"));
+        assert_eq!(report.entries[0].name, "v1.2 upgrade vlan 20");
+    }
+
+    #[test]
+    fn handles_ipv6_and_bare_addresses() {
+        let report = analyze_import("2001:db8::/32\n2001:db8:1::/48\n2001:db8:1::1\n192.0.2.1", HostBitsPolicy::Normalize);
+        let prefixes: Vec<IpNet> = report.entries.iter().map(|e| e.prefix).collect();
+        assert_eq!(prefixes, [net("2001:db8::/32"), net("2001:db8:1::/48"), net("2001:db8:1::1/128"), net("192.0.2.1/32")]);
+        assert_eq!(report.overlaps.len(), 2);
+        assert_eq!(report.parents.len(), 2);
+    }
+
+    #[test]
+    fn normalizes_or_rejects_host_bits() {
+        let report = analyze_import("10.0.0.5/24\n10.1.0.0/24", HostBitsPolicy::Normalize);
+        assert_eq!(report.entries[0].prefix, net("10.0.0.0/24"));
+        match &report.normalized[..] {
+            [ImportIssue::HostBitsSet { line: 1, found }] => assert_eq!((found.offset, found.host_index), (5, Some(5))),
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let strict = analyze_import("10.0.0.5/24\n10.1.0.0/24", HostBitsPolicy::Reject);
+        assert_eq!(strict.entries.len(), 1);
+        assert!(strict.normalized.is_empty());
+        assert!(matches!(&strict.invalid[..], [ImportIssue::InvalidLine { line: 1, reason, .. }] if reason.contains("10.0.0.0/24")));
+    }
+}
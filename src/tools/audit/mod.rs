@@ -0,0 +1,6 @@
+// src/tools/audit/mod.rs
+pub mod analyzer;
+pub mod panel;
+pub mod types;
+
+pub use panel::ImportAudit;
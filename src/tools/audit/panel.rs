@@ -0,0 +1,115 @@
+// src/tools/audit/panel.rs
+use dioxus::prelude::*;
+use crate::address_math::HostBitsPolicy;
+use crate::components::HostBitsToggle;
+use crate::tools::audit::analyzer::analyze_import;
+use crate::tools::audit::types::ImportIssue;
+
+#[component]
+pub fn ImportAudit() -> Element {
+    let mut text = use_signal(|| "name,prefix\ncorp,10.0.0.0/16\nusers,10.0.0.0/22\nservers,10.0.4.0 255.255.255.0\nservers-dup,10.0.4.0/24\nlab,10.0.300.0/24".to_string());
+    let mut load_error = use_signal(|| None::<String>);
+    let policy = use_signal(HostBitsPolicy::default);
+
+    let report = analyze_import(&text(), policy());
+    let area = "w-full h-96 px-3 py-2 text-sm font-roboto border border-gray-600 rounded-lg bg-gray-700";
+
+    rsx! {
+        div { class: "grid grid-cols-3 gap-4 text-left",
+            div { class: "w-full bg-gray-800 rounded-lg shadow-lg p-6 flex flex-col",
+                h2 { class: "text-2xl font-bold mb-6 text-center", "Prefix List" }
+                label { class: "block text-sm font-medium mb-2", "Paste CSV or one prefix per line" }
+                textarea { class: "{area}", value: "{text}", oninput: move |e| text.set(e.value()) }
+                div { class: "mt-4", HostBitsToggle { policy } }
+                label { class: "block text-sm font-medium mt-4 mb-2", "Or load a file" }
+                input {
+                    r#type: "file",
+                    accept: ".csv,.txt,.tsv",
+                    class: "text-sm",
+                    onchange: move |e| {
+                        let files = e.files();
+                        spawn(async move {
+                            if let Some(file) = files.first() {
+                                match file.read_string().await {
+                                    Ok(contents) => {
+                                        text.set(contents);
+                                        load_error.set(None);
+                                    }
+                                    Err(err) => load_error.set(Some(err.to_string())),
+                                }
+                            }
+                        });
+                    }
+                }
+                if let Some(err) = load_error() {
+                    p { class: "mt-1 text-xs text-red-400", "{err}" }
+                }
+            }
+
+            div { class: "col-span-2 bg-gray-800 rounded-lg shadow-lg p-6 overflow-auto",
+                h2 { class: "text-xl font-bold mb-6 text-center", "Audit" }
+                p { class: "text-sm text-gray-400 mb-4",
+                    "{report.entries.len()} prefixes · {report.invalid.len()} invalid line(s) · {report.overlaps.len()} overlap(s)"
+                    if report.skipped_header { " · header row skipped" }
+                }
+
+                if !report.invalid.is_empty() {
+                    h3 { class: "mb-2 font-bold", "Invalid Lines" }
+                    for issue in report.invalid.iter() {
+                        if let ImportIssue::InvalidLine { line, text, reason } = issue {
+                            p { class: "text-sm text-red-400 font-roboto", "Line {line}: {text} — {reason}" }
+                        }
+                    }
+                }
+
+                if !report.normalized.is_empty() {
+                    h3 { class: "mt-4 mb-2 font-bold", "Normalized" }
+                    for issue in report.normalized.iter() {
+                        if let ImportIssue::HostBitsSet { line, found } = issue {
+                            p { class: "text-sm text-yellow-400 font-roboto", "Line {line}: {found}" }
+                        }
+                    }
+                }
+
+                if !report.overlaps.is_empty() {
+                    h3 { class: "mt-4 mb-2 font-bold", "Overlaps" }
+                    for issue in report.overlaps.iter() {
+                        match issue {
+                            ImportIssue::Duplicate { first_line, line, prefix } => rsx! {
+                                p { class: "text-sm text-yellow-400 font-roboto", "Line {line}: {prefix} duplicates line {first_line}" }
+                            },
+                            ImportIssue::Contained { parent_line, line, parent, prefix } => rsx! {
+                                p { class: "text-sm text-gray-300 font-roboto", "Line {line}: {prefix} is inside {parent} (line {parent_line})" }
+                            },
+                            ImportIssue::InvalidLine { .. } | ImportIssue::HostBitsSet { .. } => rsx! {},
+                        }
+                    }
+                }
+
+                if !report.parents.is_empty() {
+                    h3 { class: "mt-6 mb-2 font-bold", "Parent Utilization" }
+                    table { class: "w-full text-sm font-roboto text-left",
+                        thead { class: "bg-gray-700",
+                            tr {
+                                th { class: "px-4 py-2", "Parent" }
+                                th { class: "px-4 py-2", "Name" }
+                                th { class: "px-4 py-2", "Children" }
+                                th { class: "px-4 py-2", "Used" }
+                            }
+                        }
+                        tbody {
+                            for parent in report.parents.iter() {
+                                tr { class: "border-t border-gray-700",
+                                    td { class: "px-4 py-2", "{parent.prefix}" }
+                                    td { class: "px-4 py-2", "{parent.name}" }
+                                    td { class: "px-4 py-2", "{parent.children}" }
+                                    td { class: "px-4 py-2", "{parent.used} / {parent.size} ({parent.utilization():.1}%)" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
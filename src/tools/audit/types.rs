@@ -0,0 +1,45 @@
+use ipnet::IpNet;
+use crate::address_math::HostBits;
+
+/// One prefix read from the pasted list
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImportEntry {
+    pub line: usize,
+    pub prefix: IpNet,
+    pub name: String, // Remaining columns, joined
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ImportIssue {
+    InvalidLine { line: usize, text: String, reason: String },
+    Duplicate { first_line: usize, line: usize, prefix: IpNet },
+    Contained { parent_line: usize, line: usize, parent: IpNet, prefix: IpNet },
+    HostBitsSet { line: usize, found: HostBits }, // Kept as its network
+}
+
+/// An entry that encloses other entries, and how much of it they cover
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParentUsage {
+    pub line: usize,
+    pub prefix: IpNet,
+    pub name: String,
+    pub children: usize, // Direct children only
+    pub used: u128,      // Addresses covered by direct children
+    pub size: u128,
+}
+
+impl ParentUsage {
+    pub fn utilization(&self) -> f64 {
+        self.used as f64 / self.size as f64 * 100.0
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    pub entries: Vec<ImportEntry>,
+    pub invalid: Vec<ImportIssue>,
+    pub overlaps: Vec<ImportIssue>,
+    pub normalized: Vec<ImportIssue>,
+    pub parents: Vec<ParentUsage>,
+    pub skipped_header: bool,
+}
@@ -0,0 +1,58 @@
+// src/tools/mod.rs
+pub mod acl;
+pub mod audit;
+pub mod plan_diff;
+
+use dioxus::prelude::*;
+use crate::tools::acl::AclTool;
+use crate::tools::audit::ImportAudit;
+use crate::tools::plan_diff::PlanDiffTool;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Tool {
+    Acl,
+    Audit,
+    Diff,
+}
+
+impl Tool {
+    pub const ALL: [Tool; 3] = [Tool::Acl, Tool::Audit, Tool::Diff];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Tool::Acl => "ACL / Firewall Rules",
+            Tool::Audit => "Import Audit",
+            Tool::Diff => "Plan Diff",
+        }
+    }
+}
+
+fn get_tool_class(is_active: bool) -> &'static str {
+    if is_active {
+        "px-4 py-2 text-sm font-medium rounded-lg bg-blue-600 text-white"
+    } else {
+        "px-4 py-2 text-sm font-medium rounded-lg bg-gray-800 text-gray-300 hover:bg-gray-700"
+    }
+}
+
+#[component]
+pub fn ToolsTab() -> Element {
+    let mut active_tool = use_signal(|| Tool::Acl);
+
+    rsx! {
+        div { class: "flex space-x-2 mb-4",
+            for tool in Tool::ALL {
+                button {
+                    class: "{get_tool_class(active_tool() == tool)} transition-colors",
+                    onclick: move |_| active_tool.set(tool),
+                    "{tool.label()}"
+                }
+            }
+        }
+        match active_tool() {
+            Tool::Acl => rsx! { AclTool {} },
+            Tool::Audit => rsx! { ImportAudit {} },
+            Tool::Diff => rsx! { PlanDiffTool {} },
+        }
+    }
+}
@@ -0,0 +1,198 @@
+// src/tools/plan_diff/comparer.rs
+use ipnet::IpNet;
+use crate::address_math::{check_host_bits, HostBits, HostBitsPolicy};
+use crate::tools::plan_diff::types::{ChangeKind, PlanChange, PlanDiff};
+
+/// Every CIDR prefix in the text, wherever it appears: plain lists, CSV rows, JSON exports
+/// or config snippets all work. Bare addresses are skipped since exports also carry gateways.
+/// Prefixes with host bits set are normalized and listed alongside, or rejected under `policy`.
+pub fn extract_prefixes(text: &str, policy: HostBitsPolicy) -> Result<(Vec<IpNet>, Vec<HostBits>), HostBits> {
+    let mut prefixes = vec![];
+    let mut normalized = vec![];
+    let tokens = text
+        .split(|c: char| !(c.is_ascii_hexdigit() || matches!(c, '.' | ':' | '/')))
+        .map(|token| token.trim_matches(|c| c == '.' || c == ':'))
+        .filter(|token| token.contains('/'))
+        .filter_map(|token| token.parse::<IpNet>().ok());
+    for net in tokens {
+        normalized.extend(check_host_bits(net, policy)?);
+        prefixes.push(net.trunc());
+    }
+    prefixes.sort_by_key(|n| (n.network(), n.prefix_len()));
+    prefixes.dedup();
+    Ok((prefixes, normalized))
+}
+
+fn overlaps(a: &IpNet, b: &IpNet) -> bool {
+    a.contains(b) || b.contains(a)
+}
+
+/// Addresses covered by a set of prefixes, with nested or adjacent prefixes counted once
+fn addresses(prefixes: &[IpNet]) -> u128 {
+    IpNet::aggregate(&prefixes.to_vec())
+        .iter()
+        .map(|net| {
+            let bits = if matches!(net, IpNet::V4(_)) { 32 } else { 128 };
+            1u128.checked_shl(bits - net.prefix_len() as u32).unwrap_or(u128::MAX)
+        })
+        .fold(0u128, |acc, n| acc.saturating_add(n))
+}
+
+fn classify(old: &[IpNet], new: &[IpNet]) -> ChangeKind {
+    match (old, new) {
+        ([], _) => ChangeKind::Added,
+        (_, []) => ChangeKind::Removed,
+        ([_], [_]) => ChangeKind::Resized,
+        ([o], _) if new.iter().all(|n| o.contains(n)) => ChangeKind::Split,
+        (_, [n]) if old.iter().all(|o| n.contains(o)) => ChangeKind::Merged,
+        _ => ChangeKind::Restructured,
+    }
+}
+
+/// Compare two plans. Prefixes in both are unchanged; the rest are grouped by overlap
+/// (old-only and new-only prefixes that share addresses end up in one change).
+pub fn diff_plans(old: &[IpNet], new: &[IpNet]) -> PlanDiff {
+    let removed: Vec<IpNet> = old.iter().filter(|o| !new.contains(o)).copied().collect();
+    let added: Vec<IpNet> = new.iter().filter(|n| !old.contains(n)).copied().collect();
+
+    // Union-find over removed (0..r) followed by added (r..r+a)
+    let r = removed.len();
+    let all: Vec<IpNet> = removed.iter().chain(added.iter()).copied().collect();
+    let mut parent: Vec<usize> = (0..all.len()).collect();
+    fn find(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+    for i in 0..all.len() {
+        for j in i + 1..all.len() {
+            if overlaps(&all[i], &all[j]) {
+                let (a, b) = (find(&mut parent, i), find(&mut parent, j));
+                parent[a] = b;
+            }
+        }
+    }
+
+    let mut groups: Vec<(usize, Vec<IpNet>, Vec<IpNet>)> = vec![];
+    for (i, net) in all.iter().enumerate() {
+        let root = find(&mut parent, i);
+        let index = match groups.iter().position(|(g, _, _)| *g == root) {
+            Some(index) => index,
+            None => {
+                groups.push((root, vec![], vec![]));
+                groups.len() - 1
+            }
+        };
+        if i < r {
+            groups[index].1.push(*net);
+        } else {
+            groups[index].2.push(*net);
+        }
+    }
+
+    let mut changes: Vec<PlanChange> = groups
+        .into_iter()
+        .map(|(_, mut old, mut new)| {
+            old.sort_by_key(|n| (n.network(), n.prefix_len()));
+            new.sort_by_key(|n| (n.network(), n.prefix_len()));
+            (old, new)
+        })
+        .map(|(old, new)| PlanChange {
+            kind: classify(&old, &new),
+            old_addresses: addresses(&old),
+            new_addresses: addresses(&new),
+            old,
+            new,
+        })
+        .chain(old.iter().filter(|o| new.contains(o)).map(|&net| PlanChange {
+            kind: ChangeKind::Unchanged,
+            old: vec![net],
+            new: vec![net],
+            old_addresses: addresses(&[net]),
+            new_addresses: addresses(&[net]),
+        }))
+        .collect();
+    changes.sort_by_key(|c| {
+        let first = c.old.first().or(c.new.first()).copied();
+        first.map(|n| (n.network(), n.prefix_len()))
+    });
+
+    PlanDiff {
+        changes,
+        old_count: old.len(),
+        new_count: new.len(),
+        old_addresses: addresses(old),
+        new_addresses: addresses(new),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::assert_golden;
+    use crate::tools::plan_diff::types::format_delta;
+
+    fn nets(list: &[&str]) -> Vec<IpNet> {
+        list.iter().map(|s| s.parse().unwrap()).collect()
+    }
+
+    fn kinds(diff: &PlanDiff) -> Vec<ChangeKind> {
+        diff.changes.iter().map(|c| c.kind).collect()
+    }
+
+    #[test]
+    fn extracts_prefixes_from_any_format() {
+        let text = r#"{"prefix": "10.0.1.0/24", "gateway": "10.0.1.1"}
+            name,prefix
+            users,10.0.0.5/24
+            ip route 2001:db8::/32 Null0
+            10.0.0.0/24."#;
+        let (prefixes, normalized) = extract_prefixes(text, HostBitsPolicy::Normalize).unwrap();
+        assert_eq!(prefixes, nets(&["10.0.0.0/24", "10.0.1.0/24", "2001:db8::/32"]));
+        assert_eq!(normalized.iter().map(|h| h.input).collect::<Vec<_>>(), nets(&["10.0.0.5/24"]));
+        assert_eq!(extract_prefixes(text, HostBitsPolicy::Reject).unwrap_err().network, nets(&["10.0.0.0/24"])[0]);
+        assert_eq!(extract_prefixes("1/2 and 3/4 are not prefixes", HostBitsPolicy::Normalize), Ok((vec![], vec![])));
+    }
+
+    #[test]
+    fn classifies_each_kind_of_change() {
+        let old = nets(&["10.0.0.0/24", "10.0.1.0/24", "10.0.2.0/24", "10.0.3.0/24", "10.0.4.0/24", "10.0.6.0/24", "10.0.7.0/24"]);
+        let new = nets(&["10.0.0.0/24", "10.0.1.0/25", "10.0.1.128/25", "10.0.2.0/23", "10.0.4.0/23", "10.0.8.0/24"]);
+        let diff = diff_plans(&old, &new);
+        assert_eq!(kinds(&diff), [
+            ChangeKind::Unchanged,
+            ChangeKind::Split,
+            ChangeKind::Merged,
+            ChangeKind::Resized,
+            ChangeKind::Removed,
+            ChangeKind::Removed,
+            ChangeKind::Added,
+        ]);
+        assert_eq!(diff.old_addresses, 7 * 256);
+        assert_eq!(diff.new_addresses, 7 * 256);
+        assert_eq!(diff.changes[3].delta(), format_delta(256, 512));
+    }
+
+    #[test]
+    fn groups_many_to_many_overlaps() {
+        let diff = diff_plans(&nets(&["10.0.0.0/24", "10.0.1.0/24"]), &nets(&["10.0.0.0/23", "10.0.1.0/25"]));
+        assert_eq!(kinds(&diff), [ChangeKind::Restructured]);
+        assert_eq!(diff.changes[0].old.len(), 2);
+        assert_eq!(diff.changes[0].new.len(), 2);
+    }
+
+    #[test]
+    fn golden_report() {
+        let (old, _) = extract_prefixes("10.0.0.0/22\n10.0.4.0/24\n10.0.5.0/24\n10.1.0.0/16\n2001:db8::/48", HostBitsPolicy::Normalize).unwrap();
+        let (new, _) = extract_prefixes("10.0.0.0/23\n10.0.2.0/23\n10.0.4.0/23\n10.2.0.0/16\n2001:db8::/48", HostBitsPolicy::Normalize).unwrap();
+        let diff = diff_plans(&old, &new);
+        let mut out = format!("{} -> {} prefixes, {}\n", diff.old_count, diff.new_count, diff.delta());
+        for change in &diff.changes {
+            let list = |nets: &[IpNet]| nets.iter().map(|n| n.to_string()).collect::<Vec<_>>().join(" ");
+            out += &format!("{:<12} {} => {} ({})\n", change.kind.label(), list(&change.old), list(&change.new), change.delta());
+        }
+        assert_golden("plan_diff.txt", &out);
+    }
+}
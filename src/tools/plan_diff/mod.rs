@@ -0,0 +1,6 @@
+// src/tools/plan_diff/mod.rs
+pub mod comparer;
+pub mod panel;
+pub mod types;
+
+pub use panel::PlanDiffTool;
@@ -0,0 +1,112 @@
+// src/tools/plan_diff/panel.rs
+use dioxus::prelude::*;
+use ipnet::IpNet;
+use crate::address_math::{HostBits, HostBitsPolicy};
+use crate::components::HostBitsToggle;
+use crate::tools::plan_diff::comparer::{diff_plans, extract_prefixes};
+use crate::tools::plan_diff::types::ChangeKind;
+
+fn get_change_class(kind: ChangeKind) -> &'static str {
+    match kind {
+        ChangeKind::Added => "text-green-400",
+        ChangeKind::Removed => "text-red-400",
+        ChangeKind::Resized => "text-yellow-400",
+        ChangeKind::Split => "text-blue-400",
+        ChangeKind::Merged => "text-purple-400",
+        ChangeKind::Restructured => "text-orange-400",
+        ChangeKind::Unchanged => "text-gray-500",
+    }
+}
+
+fn join(prefixes: &[IpNet]) -> String {
+    prefixes.iter().map(|n| n.to_string()).collect::<Vec<_>>().join(", ")
+}
+
+/// Prefixes to compare, plus a note for each one that was normalized or the one that was rejected
+fn read_plan(text: &str, policy: HostBitsPolicy) -> (Vec<IpNet>, Vec<String>, Option<HostBits>) {
+    match extract_prefixes(text, policy) {
+        Ok((prefixes, normalized)) => (prefixes, normalized.iter().map(|h| h.to_string()).collect(), None),
+        Err(found) => (vec![], vec![], Some(found)),
+    }
+}
+
+#[component]
+pub fn PlanDiffTool() -> Element {
+    let mut old_text = use_signal(|| "10.0.0.0/24\n10.0.1.0/24\n10.0.2.0/25\n10.0.2.128/25\n10.0.4.0/24\n10.0.8.0/24".to_string());
+    let mut new_text = use_signal(|| "10.0.0.0/25\n10.0.0.128/25\n10.0.1.0/24\n10.0.2.0/24\n10.0.4.0/23\n10.0.9.0/24".to_string());
+    let mut show_unchanged = use_signal(|| false);
+    let policy = use_signal(HostBitsPolicy::default);
+
+    let (old, old_notes, old_error) = read_plan(&old_text(), policy());
+    let (new, new_notes, new_error) = read_plan(&new_text(), policy());
+    let diff = diff_plans(&old, &new);
+    let area = "w-full h-64 px-3 py-2 text-sm font-roboto border border-gray-600 rounded-lg bg-gray-700";
+
+    rsx! {
+        div { class: "grid grid-cols-3 gap-4 text-left",
+            div { class: "w-full bg-gray-800 rounded-lg shadow-lg p-6 flex flex-col",
+                h2 { class: "text-2xl font-bold mb-6 text-center", "Plans" }
+                p { class: "text-xs text-gray-400 mb-4", "Paste prefix lists, CSV or JSON exports; every CIDR prefix is compared." }
+                label { class: "block text-sm font-medium mb-2", "Old Plan ({diff.old_count} prefixes)" }
+                textarea { class: "{area}", value: "{old_text}", oninput: move |e| old_text.set(e.value()) }
+                if let Some(found) = old_error {
+                    p { class: "mt-1 text-xs text-red-400", "{found}" }
+                }
+                for note in old_notes {
+                    p { class: "mt-1 text-xs text-yellow-400", "Normalized: {note}" }
+                }
+                label { class: "block text-sm font-medium mt-4 mb-2", "New Plan ({diff.new_count} prefixes)" }
+                textarea { class: "{area}", value: "{new_text}", oninput: move |e| new_text.set(e.value()) }
+                if let Some(found) = new_error {
+                    p { class: "mt-1 text-xs text-red-400", "{found}" }
+                }
+                for note in new_notes {
+                    p { class: "mt-1 text-xs text-yellow-400", "Normalized: {note}" }
+                }
+                div { class: "mt-4", HostBitsToggle { policy } }
+            }
+
+            div { class: "col-span-2 bg-gray-800 rounded-lg shadow-lg p-6 overflow-auto",
+                h2 { class: "text-xl font-bold mb-6 text-center", "Differences" }
+                p { class: "text-sm text-gray-400 mb-2",
+                    "{diff.count(ChangeKind::Added)} added · {diff.count(ChangeKind::Removed)} removed · "
+                    "{diff.count(ChangeKind::Resized)} resized · {diff.count(ChangeKind::Split)} split · "
+                    "{diff.count(ChangeKind::Merged)} merged · {diff.count(ChangeKind::Restructured)} restructured · "
+                    "{diff.count(ChangeKind::Unchanged)} unchanged"
+                }
+                p { class: "text-sm text-gray-400 mb-4",
+                    "Addresses: {diff.old_addresses} → {diff.new_addresses} ({diff.delta()})"
+                }
+                label { class: "flex items-center mb-4 text-sm",
+                    input {
+                        r#type: "checkbox",
+                        class: "mr-2",
+                        checked: show_unchanged(),
+                        onchange: move |e| show_unchanged.set(e.checked())
+                    }
+                    "Show unchanged prefixes"
+                }
+                table { class: "w-full text-sm font-roboto text-left",
+                    thead { class: "bg-gray-700",
+                        tr {
+                            th { class: "px-4 py-2", "Change" }
+                            th { class: "px-4 py-2", "Old" }
+                            th { class: "px-4 py-2", "New" }
+                            th { class: "px-4 py-2", "Δ Addresses" }
+                        }
+                    }
+                    tbody {
+                        for change in diff.changes.iter().filter(|c| show_unchanged() || c.kind != ChangeKind::Unchanged) {
+                            tr { class: "border-t border-gray-700 {get_change_class(change.kind)}",
+                                td { class: "px-4 py-2 font-bold", "{change.kind.label()}" }
+                                td { class: "px-4 py-2", "{join(&change.old)}" }
+                                td { class: "px-4 py-2", "{join(&change.new)}" }
+                                td { class: "px-4 py-2", "{change.delta()}" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
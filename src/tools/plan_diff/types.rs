@@ -0,0 +1,70 @@
+use ipnet::IpNet;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Resized,      // One prefix replaced by one overlapping prefix of another size
+    Split,        // One old prefix replaced by several inside it
+    Merged,       // Several old prefixes replaced by one covering them
+    Restructured, // Any other many-to-many overlap
+    Unchanged,
+}
+
+impl ChangeKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChangeKind::Added => "Added",
+            ChangeKind::Removed => "Removed",
+            ChangeKind::Resized => "Resized",
+            ChangeKind::Split => "Split",
+            ChangeKind::Merged => "Merged",
+            ChangeKind::Restructured => "Restructured",
+            ChangeKind::Unchanged => "Unchanged",
+        }
+    }
+}
+
+/// One group of related prefixes: everything on either side that overlaps, transitively
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlanChange {
+    pub kind: ChangeKind,
+    pub old: Vec<IpNet>,
+    pub new: Vec<IpNet>,
+    pub old_addresses: u128, // Nested prefixes are only counted once
+    pub new_addresses: u128,
+}
+
+impl PlanChange {
+    pub fn delta(&self) -> String {
+        format_delta(self.old_addresses, self.new_addresses)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PlanDiff {
+    pub changes: Vec<PlanChange>, // In address order
+    pub old_count: usize,
+    pub new_count: usize,
+    pub old_addresses: u128,
+    pub new_addresses: u128,
+}
+
+impl PlanDiff {
+    pub fn count(&self, kind: ChangeKind) -> usize {
+        self.changes.iter().filter(|c| c.kind == kind).count()
+    }
+
+    pub fn delta(&self) -> String {
+        format_delta(self.old_addresses, self.new_addresses)
+    }
+}
+
+/// Signed difference of two address counts; counts can reach 2^128, so no i128
+pub fn format_delta(old: u128, new: u128) -> String {
+    match new.cmp(&old) {
+        std::cmp::Ordering::Greater => format!("+{}", new - old),
+        std::cmp::Ordering::Less => format!("-{}", old - new),
+        std::cmp::Ordering::Equal => "0".to_string(),
+    }
+}
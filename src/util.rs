@@ -0,0 +1,48 @@
+// src/util.rs
+use dioxus::prelude::*;
+
+/// Copy text to the system clipboard through the webview
+pub fn copy_to_clipboard(text: &str) {
+    let literal = serde_json::to_string(text).unwrap_or_default();
+    document::eval(&format!("navigator.clipboard.writeText({literal});"));
+}
+
+/// Offer text as a file download through the webview
+pub fn download_file(filename: &str, mime: &str, text: &str) {
+    let literal = serde_json::to_string(text).unwrap_or_default();
+    let filename = serde_json::to_string(filename).unwrap_or_default();
+    let mime = serde_json::to_string(mime).unwrap_or_default();
+    document::eval(&format!(
+        r#"
+        const blob = new Blob([{literal}], {{ type: {mime} }});
+        const a = document.createElement("a");
+        a.href = URL.createObjectURL(blob);
+        a.download = {filename};
+        document.body.appendChild(a);
+        a.click();
+        a.remove();
+        URL.revokeObjectURL(a.href);
+        "#
+    ));
+}
+
+/// Quote a CSV field when it contains a separator, quote or line break
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Tailwind grid class for a results tab bar with `tab_count` visible tabs
+pub fn get_tab_grid_class(tab_count: usize) -> &'static str {
+    match tab_count {
+        1 => "grid-cols-1",
+        2 => "grid-cols-2",
+        3 => "grid-cols-3",
+        4 => "grid-cols-4",
+        5 => "grid-cols-5",
+        _ => "grid-cols-6",
+    }
+}
@@ -0,0 +1,169 @@
+// src/workspace/allocations_panel.rs
+use dioxus::prelude::*;
+use ipnet::IpNet;
+use crate::address_math::{host_bits, HostBitsPolicy};
+use crate::components::HostBitsWarning;
+use crate::workspace::types::{AllocationDetails, AllocationRequest, Workspace};
+
+#[component]
+pub fn AllocationsPanel(
+    workspace: Signal<Workspace>,
+    selected_pool: Signal<Option<u32>>,
+    host_bits_policy: Signal<HostBitsPolicy>,
+) -> Element {
+    let mut by_size = use_signal(|| true);
+    let mut size_input = use_signal(|| "24".to_string());
+    let mut prefix_input = use_signal(String::new);
+    let mut name_input = use_signal(String::new);
+    let mut vlan_input = use_signal(String::new);
+    let mut owner_input = use_signal(String::new);
+    let mut description_input = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+    let mut normalized = use_signal(|| None);
+
+    let ws = workspace.read();
+    let conflicts = ws.conflicts();
+    let Some(pool) = selected_pool().and_then(|id| ws.pool(id)).cloned() else {
+        return rsx! {
+            div { class: "h-150 bg-gray-800 rounded-lg shadow-lg p-6",
+                p { class: "text-center text-gray-500 text-base py-20", "Add or select a pool" }
+            }
+        };
+    };
+    let allocations: Vec<_> = ws.pool_allocations(pool.id).cloned().collect();
+    let free = ws.free_space(pool.id);
+    let pct = ws.utilization(pool.id) * 100.0;
+    let prefix_of = |id: u32| ws.allocations.iter().find(|a| a.id == id).map(|a| a.prefix.to_string()).unwrap_or_default();
+    let conflict_text: Vec<String> = conflicts.iter().map(|c| format!("{} overlaps {}", prefix_of(c.first), prefix_of(c.second))).collect();
+    drop(ws);
+
+    let field = "w-full px-3 py-2 text-sm border border-gray-600 rounded-lg bg-gray-700";
+    let pool_id = pool.id;
+
+    rsx! {
+        div { class: "h-150 bg-gray-800 rounded-lg shadow-lg p-6 overflow-auto text-left",
+            h2 { class: "text-xl font-bold mb-1 text-center", "{pool.name}" }
+            p { class: "text-center text-sm text-gray-400 font-roboto mb-6", "{pool.prefix} · {allocations.len()} allocation(s) · {pct:.1}% used" }
+
+            for text in conflict_text {
+                p { class: "text-sm text-red-400 mb-1", "Conflict: {text}" }
+            }
+
+            // Allocation form
+            div { class: "grid grid-cols-3 gap-2 mb-2 text-sm",
+                select {
+                    class: "{field}",
+                    onchange: move |e| by_size.set(e.value() == "size"),
+                    option { value: "size", selected: by_size(), "Next free of size" }
+                    option { value: "prefix", selected: !by_size(), "Specific prefix" }
+                }
+                if by_size() {
+                    input { class: "{field}", placeholder: "/24", value: "{size_input}", oninput: move |e| size_input.set(e.value()) }
+                } else {
+                    input { class: "{field}", placeholder: "e.g. 10.1.2.0/24", value: "{prefix_input}", oninput: move |e| prefix_input.set(e.value()) }
+                }
+                input { class: "{field}", placeholder: "Name", value: "{name_input}", oninput: move |e| name_input.set(e.value()) }
+                input { class: "{field}", placeholder: "VLAN", value: "{vlan_input}", oninput: move |e| vlan_input.set(e.value()) }
+                input { class: "{field}", placeholder: "Owner", value: "{owner_input}", oninput: move |e| owner_input.set(e.value()) }
+                input { class: "{field}", placeholder: "Description", value: "{description_input}", oninput: move |e| description_input.set(e.value()) }
+            }
+            button {
+                class: "w-full bg-blue-600 hover:bg-blue-700 text-white font-bold py-2 rounded-lg mb-2",
+                onclick: move |_| {
+                    let request = if by_size() {
+                        match size_input().trim().trim_start_matches('/').parse::<u8>() {
+                            Ok(len) => AllocationRequest::NextFree(len),
+                            Err(_) => return error.set(Some("Enter a prefix length, e.g. 24".to_string())),
+                        }
+                    } else {
+                        match prefix_input().trim().parse::<IpNet>() {
+                            Ok(net) => AllocationRequest::Specific(net),
+                            Err(_) => return error.set(Some("Enter a prefix in CIDR notation".to_string())),
+                        }
+                    };
+                    let vlan = match vlan_input().trim() {
+                        "" => None,
+                        v => match v.parse::<u16>() {
+                            Ok(id) if (1..=4094).contains(&id) => Some(id),
+                            _ => return error.set(Some("VLAN must be 1–4094".to_string())),
+                        },
+                    };
+                    let details = AllocationDetails {
+                        name: name_input().trim().to_string(),
+                        vlan,
+                        owner: owner_input().trim().to_string(),
+                        description: description_input().trim().to_string(),
+                    };
+                    let allocated = workspace.write().allocate(pool_id, request, details, host_bits_policy());
+                    match allocated {
+                        Ok(_) => {
+                            normalized.set(match request {
+                                AllocationRequest::Specific(net) => host_bits(net),
+                                AllocationRequest::NextFree(_) => None,
+                            });
+                            name_input.set(String::new());
+                            vlan_input.set(String::new());
+                            description_input.set(String::new());
+                            prefix_input.set(String::new());
+                            error.set(None);
+                        }
+                        Err(e) => {
+                            error.set(Some(e.to_string()));
+                            normalized.set(None);
+                        }
+                    }
+                },
+                "Allocate"
+            }
+            if let Some(found) = normalized() {
+                HostBitsWarning { found }
+            }
+            if let Some(err) = error() {
+                p { class: "text-xs text-red-400 mb-2", "{err}" }
+            }
+
+            table { class: "w-full text-sm font-roboto text-left mt-4",
+                thead { class: "bg-gray-700",
+                    tr {
+                        th { class: "px-3 py-2", "Prefix" }
+                        th { class: "px-3 py-2", "Name" }
+                        th { class: "px-3 py-2", "VLAN" }
+                        th { class: "px-3 py-2", "Owner" }
+                        th { class: "px-3 py-2", "Description" }
+                        th { class: "px-3 py-2" }
+                    }
+                }
+                tbody {
+                    for a in allocations {
+                        tr { class: "border-t border-gray-700 hover:bg-gray-700/50",
+                            td { class: "px-3 py-2", "{a.prefix}" }
+                            td { class: "px-3 py-2", "{a.name}" }
+                            td { class: "px-3 py-2", "{a.vlan.map(|v| v.to_string()).unwrap_or_default()}" }
+                            td { class: "px-3 py-2", "{a.owner}" }
+                            td { class: "px-3 py-2", "{a.description}" }
+                            td { class: "px-3 py-2 text-right",
+                                button {
+                                    class: "text-xs text-red-400 hover:text-red-300",
+                                    onclick: move |_| {
+                                        let _ = workspace.write().free(a.id);
+                                    },
+                                    "Free"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            h3 { class: "mt-6 mb-2 font-bold", "Free Blocks ({free.len()})" }
+            div { class: "grid grid-cols-4 gap-1 text-xs font-roboto",
+                for block in free.iter().take(256) {
+                    span { "{block}" }
+                }
+            }
+            if free.len() > 256 {
+                p { class: "mt-2 text-xs text-gray-500", "Showing the first 256 free blocks." }
+            }
+        }
+    }
+}
@@ -0,0 +1,245 @@
+// src/workspace/allocator.rs
+use ipnet::IpNet;
+use crate::address_math::{check_host_bits, HostBitsPolicy};
+use crate::free_space::free_blocks;
+use crate::workspace::types::{
+    Allocation, AllocationDetails, AllocationRequest, Conflict, Pool, Workspace, WorkspaceError,
+};
+
+fn overlaps(a: &IpNet, b: &IpNet) -> bool {
+    a.contains(b) || b.contains(a)
+}
+
+fn same_family(a: &IpNet, b: &IpNet) -> bool {
+    matches!((a, b), (IpNet::V4(_), IpNet::V4(_)) | (IpNet::V6(_), IpNet::V6(_)))
+}
+
+fn max_prefix_len(net: &IpNet) -> u8 {
+    match net {
+        IpNet::V4(_) => 32,
+        IpNet::V6(_) => 128,
+    }
+}
+
+impl Workspace {
+    fn take_id(&mut self) -> u32 {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    pub fn pool(&self, pool_id: u32) -> Option<&Pool> {
+        self.pools.iter().find(|p| p.id == pool_id)
+    }
+
+    pub fn pool_allocations(&self, pool_id: u32) -> impl Iterator<Item = &Allocation> {
+        self.allocations.iter().filter(move |a| a.pool_id == pool_id)
+    }
+
+    /// Add a top-level block; a prefix with host bits set is normalized unless `policy` rejects it
+    pub fn add_pool(&mut self, name: &str, prefix: IpNet, policy: HostBitsPolicy) -> Result<u32, WorkspaceError> {
+        check_host_bits(prefix, policy).map_err(WorkspaceError::HostBitsSet)?;
+        let prefix = prefix.trunc();
+        if let Some(existing) = self.pools.iter().find(|p| overlaps(&p.prefix, &prefix)) {
+            return Err(WorkspaceError::PoolOverlap(existing.name.clone()));
+        }
+        let id = self.take_id();
+        self.pools.push(Pool { id, name: name.to_string(), prefix });
+        Ok(id)
+    }
+
+    pub fn remove_pool(&mut self, pool_id: u32) -> Result<(), WorkspaceError> {
+        if self.pool(pool_id).is_none() {
+            return Err(WorkspaceError::PoolNotFound);
+        }
+        if self.pool_allocations(pool_id).next().is_some() {
+            return Err(WorkspaceError::PoolInUse);
+        }
+        self.pools.retain(|p| p.id != pool_id);
+        Ok(())
+    }
+
+    /// Unallocated space in a pool, as the largest aligned blocks
+    pub fn free_space(&self, pool_id: u32) -> Vec<IpNet> {
+        let Some(pool) = self.pool(pool_id) else { return vec![] };
+        let used: Vec<IpNet> = self.pool_allocations(pool_id).map(|a| a.prefix).collect();
+        free_blocks(pool.prefix, &used)
+    }
+
+    /// Carve a block out of a pool, either the first free /N or a specific prefix.
+    /// A specific prefix with host bits set is normalized unless `policy` rejects it.
+    pub fn allocate(
+        &mut self,
+        pool_id: u32,
+        request: AllocationRequest,
+        details: AllocationDetails,
+        policy: HostBitsPolicy,
+    ) -> Result<Allocation, WorkspaceError> {
+        let pool = self.pool(pool_id).ok_or(WorkspaceError::PoolNotFound)?.clone();
+
+        let prefix = match request {
+            AllocationRequest::Specific(net) => {
+                check_host_bits(net, policy).map_err(WorkspaceError::HostBitsSet)?;
+                let net = net.trunc();
+                if !same_family(&net, &pool.prefix) {
+                    return Err(WorkspaceError::FamilyMismatch);
+                }
+                if !pool.prefix.contains(&net) {
+                    return Err(WorkspaceError::OutsidePool);
+                }
+                if let Some(existing) = self.pool_allocations(pool_id).find(|a| overlaps(&a.prefix, &net)) {
+                    return Err(WorkspaceError::Conflict(existing.prefix));
+                }
+                net
+            }
+            AllocationRequest::NextFree(len) => {
+                if len < pool.prefix.prefix_len() || len > max_prefix_len(&pool.prefix) {
+                    return Err(WorkspaceError::InvalidPrefix);
+                }
+                // First free block in address order that can hold the request
+                self.free_space(pool_id)
+                    .into_iter()
+                    .find(|b| b.prefix_len() <= len)
+                    .and_then(|b| b.subnets(len).ok()?.next())
+                    .ok_or(WorkspaceError::PoolFull)?
+            }
+        };
+
+        let allocation = Allocation {
+            id: self.take_id(),
+            pool_id,
+            prefix,
+            name: details.name,
+            vlan: details.vlan,
+            owner: details.owner,
+            description: details.description,
+        };
+        self.allocations.push(allocation.clone());
+        self.allocations.sort_by_key(|a| (a.pool_id, a.prefix.network(), a.prefix.prefix_len()));
+        Ok(allocation)
+    }
+
+    pub fn free(&mut self, allocation_id: u32) -> Result<Allocation, WorkspaceError> {
+        let index = self
+            .allocations
+            .iter()
+            .position(|a| a.id == allocation_id)
+            .ok_or(WorkspaceError::AllocationNotFound)?;
+        Ok(self.allocations.remove(index))
+    }
+
+    /// Overlapping allocation pairs; the allocator never creates them, but loaded files can contain them
+    pub fn conflicts(&self) -> Vec<Conflict> {
+        let mut found = vec![];
+        for (i, a) in self.allocations.iter().enumerate() {
+            for b in &self.allocations[i + 1..] {
+                if overlaps(&a.prefix, &b.prefix) {
+                    found.push(Conflict { first: a.id, second: b.id });
+                }
+            }
+        }
+        found
+    }
+
+    /// Fraction of the pool's addresses that are allocated
+    pub fn utilization(&self, pool_id: u32) -> f64 {
+        let Some(pool) = self.pool(pool_id) else { return 0.0 };
+        let bits = max_prefix_len(&pool.prefix);
+        let size = |net: &IpNet| 2f64.powi((bits - net.prefix_len()) as i32);
+        let used: f64 = self.pool_allocations(pool_id).map(|a| size(&a.prefix)).sum();
+        used / size(&pool.prefix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn net(s: &str) -> IpNet {
+        s.parse().unwrap()
+    }
+
+    fn named(name: &str) -> AllocationDetails {
+        AllocationDetails { name: name.to_string(), ..AllocationDetails::default() }
+    }
+
+    #[test]
+    fn pools_cannot_overlap() {
+        let mut ws = Workspace::default();
+        let id = ws.add_pool("corp", net("10.0.0.0/16"), HostBitsPolicy::Normalize).unwrap();
+        assert_eq!(ws.add_pool("lab", net("10.0.128.0/17"), HostBitsPolicy::Normalize), Err(WorkspaceError::PoolOverlap("corp".into())));
+        assert_eq!(ws.add_pool("v6", net("2001:db8::/32"), HostBitsPolicy::Normalize), Ok(id + 1));
+        assert_eq!(ws.pool(id).unwrap().prefix, net("10.0.0.0/16"));
+    }
+
+    #[test]
+    fn normalizes_or_rejects_host_bits() {
+        let mut ws = Workspace::default();
+        let strict = ws.add_pool("corp", net("10.0.0.1/16"), HostBitsPolicy::Reject);
+        assert!(matches!(strict, Err(WorkspaceError::HostBitsSet(found)) if found.network == net("10.0.0.0/16")));
+        let pool = ws.add_pool("corp", net("10.0.0.1/16"), HostBitsPolicy::Normalize).unwrap();
+        assert_eq!(ws.pool(pool).unwrap().prefix, net("10.0.0.0/16"));
+
+        let specific = AllocationRequest::Specific(net("10.0.1.9/24"));
+        assert!(matches!(ws.allocate(pool, specific, named("a"), HostBitsPolicy::Reject), Err(WorkspaceError::HostBitsSet(_))));
+        assert_eq!(ws.allocate(pool, specific, named("a"), HostBitsPolicy::Normalize).unwrap().prefix, net("10.0.1.0/24"));
+    }
+
+    #[test]
+    fn allocates_next_free_in_address_order() {
+        let mut ws = Workspace::default();
+        let pool = ws.add_pool("corp", net("10.0.0.0/24"), HostBitsPolicy::Normalize).unwrap();
+        let first = ws.allocate(pool, AllocationRequest::NextFree(26), named("a"), HostBitsPolicy::Normalize).unwrap();
+        let second = ws.allocate(pool, AllocationRequest::NextFree(25), named("b"), HostBitsPolicy::Normalize).unwrap();
+        let third = ws.allocate(pool, AllocationRequest::NextFree(26), named("c"), HostBitsPolicy::Normalize).unwrap();
+        assert_eq!((first.prefix, second.prefix, third.prefix), (net("10.0.0.0/26"), net("10.0.0.128/25"), net("10.0.0.64/26")));
+        assert_eq!(ws.allocate(pool, AllocationRequest::NextFree(30), named("d"), HostBitsPolicy::Normalize), Err(WorkspaceError::PoolFull));
+        assert_eq!(ws.utilization(pool), 1.0);
+
+        ws.free(first.id).unwrap();
+        assert_eq!(ws.free_space(pool), vec![net("10.0.0.0/26")]);
+        assert_eq!(ws.free(first.id), Err(WorkspaceError::AllocationNotFound));
+    }
+
+    #[test]
+    fn validates_specific_requests() {
+        let mut ws = Workspace::default();
+        let pool = ws.add_pool("corp", net("10.0.0.0/24"), HostBitsPolicy::Normalize).unwrap();
+        ws.allocate(pool, AllocationRequest::Specific(net("10.0.0.64/26")), named("a"), HostBitsPolicy::Normalize).unwrap();
+        let specific = |n: &str| AllocationRequest::Specific(net(n));
+        assert_eq!(ws.allocate(pool, specific("10.0.0.0/25"), named("b"), HostBitsPolicy::Normalize), Err(WorkspaceError::Conflict(net("10.0.0.64/26"))));
+        assert_eq!(ws.allocate(pool, specific("10.0.1.0/26"), named("b"), HostBitsPolicy::Normalize), Err(WorkspaceError::OutsidePool));
+        assert_eq!(ws.allocate(pool, specific("2001:db8::/64"), named("b"), HostBitsPolicy::Normalize), Err(WorkspaceError::FamilyMismatch));
+        assert_eq!(ws.allocate(pool, AllocationRequest::NextFree(23), named("b"), HostBitsPolicy::Normalize), Err(WorkspaceError::InvalidPrefix));
+        assert_eq!(ws.allocate(pool + 10, AllocationRequest::NextFree(26), named("b"), HostBitsPolicy::Normalize), Err(WorkspaceError::PoolNotFound));
+        assert_eq!(ws.remove_pool(pool), Err(WorkspaceError::PoolInUse));
+    }
+
+    #[test]
+    fn finds_conflicts_in_edited_files() {
+        let mut ws = Workspace::default();
+        let pool = ws.add_pool("corp", net("10.0.0.0/24"), HostBitsPolicy::Normalize).unwrap();
+        let a = ws.allocate(pool, AllocationRequest::NextFree(25), named("a"), HostBitsPolicy::Normalize).unwrap();
+        let mut overlapping = a.clone();
+        overlapping.id = 99;
+        overlapping.prefix = net("10.0.0.0/26");
+        ws.allocations.push(overlapping);
+        assert_eq!(ws.conflicts(), vec![Conflict { first: a.id, second: 99 }]);
+    }
+
+    proptest! {
+        #[test]
+        fn allocations_never_overlap(sizes in proptest::collection::vec(20u8..=28, 1..40)) {
+            let mut ws = Workspace::default();
+            let pool = ws.add_pool("corp", net("10.0.0.0/20"), HostBitsPolicy::Normalize).unwrap();
+            for len in sizes {
+                match ws.allocate(pool, AllocationRequest::NextFree(len), AllocationDetails::default(), HostBitsPolicy::Normalize) {
+                    Ok(allocation) => prop_assert!(net("10.0.0.0/20").contains(&allocation.prefix)),
+                    Err(e) => prop_assert_eq!(e, WorkspaceError::PoolFull),
+                }
+            }
+            prop_assert!(ws.conflicts().is_empty());
+            prop_assert!(ws.utilization(pool) <= 1.0);
+        }
+    }
+}
@@ -0,0 +1,61 @@
+// src/workspace/mod.rs
+pub mod allocator;
+pub mod types;
+pub mod storage;
+pub mod pools_panel;
+pub mod allocations_panel;
+
+use dioxus::prelude::*;
+use crate::address_math::HostBitsPolicy;
+use crate::components::HostBitsToggle;
+use crate::workspace::allocations_panel::AllocationsPanel;
+use crate::workspace::pools_panel::PoolsPanel;
+use crate::workspace::storage::{load_workspace, save_workspace, storage_location};
+use crate::workspace::types::Workspace;
+
+#[component]
+pub fn WorkspaceTab() -> Element {
+    let loaded = use_hook(load_workspace);
+    let mut load_error = use_signal(|| loaded.as_ref().err().map(|e| e.to_string()));
+    // A file that failed to load is never overwritten until the user chooses to start over
+    let mut persist = use_signal(|| loaded.is_ok());
+    let mut workspace = use_signal(|| loaded.clone().unwrap_or_default());
+    let mut save_error = use_signal(|| None::<String>);
+    let selected_pool = use_signal(|| workspace.peek().pools.first().map(|p| p.id));
+    let host_bits_policy = use_signal(HostBitsPolicy::default);
+
+    use_effect(move || {
+        let ws = workspace.read();
+        if persist() {
+            save_error.set(save_workspace(&ws).err().map(|e| e.to_string()));
+        }
+    });
+
+    rsx! {
+        if let Some(err) = load_error() {
+            div { class: "mb-4 bg-red-900/40 border border-red-500 text-red-300 p-4 rounded-lg text-sm text-left flex items-center justify-between",
+                span { "Saved workspace could not be loaded: {err}" }
+                button {
+                    class: "ml-4 bg-red-600 hover:bg-red-700 px-3 py-1 rounded",
+                    onclick: move |_| {
+                        workspace.set(Workspace { name: "Default".to_string(), ..Workspace::default() });
+                        load_error.set(None);
+                        persist.set(true);
+                    },
+                    "Start a new workspace"
+                }
+            }
+        }
+        if let Some(err) = save_error() {
+            p { class: "mb-2 text-sm text-red-400", "{err}" }
+        }
+        HostBitsToggle { policy: host_bits_policy }
+        div { class: "grid grid-cols-3 gap-4",
+            PoolsPanel { workspace, selected_pool, host_bits_policy }
+            div { class: "col-span-2",
+                AllocationsPanel { workspace, selected_pool, host_bits_policy }
+            }
+        }
+        p { class: "mt-2 text-xs text-gray-500 text-left", "Saved automatically to {storage_location()}" }
+    }
+}
@@ -0,0 +1,139 @@
+// src/workspace/pools_panel.rs
+use dioxus::prelude::*;
+use ipnet::IpNet;
+use crate::address_math::{host_bits, HostBitsPolicy};
+use crate::components::{ExportButtons, HostBitsWarning};
+use crate::workspace::storage::{from_json, to_json};
+use crate::workspace::types::Workspace;
+
+#[component]
+pub fn PoolsPanel(
+    workspace: Signal<Workspace>,
+    selected_pool: Signal<Option<u32>>,
+    host_bits_policy: Signal<HostBitsPolicy>,
+) -> Element {
+    let mut name_input = use_signal(String::new);
+    let mut prefix_input = use_signal(String::new);
+    let mut import_input = use_signal(String::new);
+    let mut show_import = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+    let mut normalized = use_signal(|| None);
+
+    let field = "w-full px-3 py-2 text-sm border border-gray-600 rounded-lg bg-gray-700";
+    let pools: Vec<_> = workspace
+        .read()
+        .pools
+        .iter()
+        .map(|p| (p.id, p.name.clone(), p.prefix, workspace.read().utilization(p.id) * 100.0))
+        .collect();
+    let json = to_json(&workspace.read());
+
+    rsx! {
+        div { class: "w-full h-150 bg-gray-800 rounded-lg shadow-lg p-6 flex flex-col text-left overflow-auto",
+            h2 { class: "text-2xl font-bold mb-6 text-center", "Pools" }
+
+            div { class: "flex-1 mb-4",
+                if pools.is_empty() {
+                    p { class: "text-sm text-gray-500", "No pools yet. Add a top-level IPv4 or IPv6 block below." }
+                }
+                for (id, name, prefix, pct) in pools {
+                    div {
+                        class: if selected_pool() == Some(id) { "flex items-center justify-between px-3 py-2 mb-1 rounded bg-gray-700 cursor-pointer" } else { "flex items-center justify-between px-3 py-2 mb-1 rounded hover:bg-gray-700/50 cursor-pointer" },
+                        onclick: move |_| selected_pool.set(Some(id)),
+                        div {
+                            p { class: "font-medium", "{name}" }
+                            p { class: "text-xs font-roboto text-gray-400", "{prefix} · {pct:.1}% used" }
+                        }
+                        button {
+                            class: "text-xs text-red-400 hover:text-red-300",
+                            onclick: move |e| {
+                                e.stop_propagation();
+                                let removed = workspace.write().remove_pool(id);
+                                match removed {
+                                    Ok(()) => {
+                                        if selected_pool() == Some(id) {
+                                            selected_pool.set(workspace.read().pools.first().map(|p| p.id));
+                                        }
+                                        error.set(None);
+                                    }
+                                    Err(e) => error.set(Some(e.to_string())),
+                                }
+                            },
+                            "Remove"
+                        }
+                    }
+                }
+            }
+
+            div { class: "grid grid-cols-2 gap-2 mb-2",
+                input { class: "{field}", placeholder: "Pool name", value: "{name_input}", oninput: move |e| name_input.set(e.value()) }
+                input { class: "{field}", placeholder: "10.0.0.0/8 or 2001:db8::/32", value: "{prefix_input}", oninput: move |e| prefix_input.set(e.value()) }
+            }
+            button {
+                class: "w-full bg-blue-600 hover:bg-blue-700 text-white font-bold py-2 rounded-lg mb-2",
+                onclick: move |_| {
+                    let Ok(prefix) = prefix_input().trim().parse::<IpNet>() else {
+                        error.set(Some("Enter the pool as CIDR, e.g. 10.0.0.0/8".to_string()));
+                        return;
+                    };
+                    let name = match name_input().trim() {
+                        "" => prefix.trunc().to_string(),
+                        n => n.to_string(),
+                    };
+                    let added = workspace.write().add_pool(&name, prefix, host_bits_policy());
+                    match added {
+                        Ok(id) => {
+                            selected_pool.set(Some(id));
+                            name_input.set(String::new());
+                            prefix_input.set(String::new());
+                            error.set(None);
+                            normalized.set(host_bits(prefix));
+                        }
+                        Err(e) => {
+                            error.set(Some(e.to_string()));
+                            normalized.set(None);
+                        }
+                    }
+                },
+                "Add Pool"
+            }
+            if let Some(found) = normalized() {
+                HostBitsWarning { found }
+            }
+            if let Some(err) = error() {
+                p { class: "text-xs text-red-400 mb-2", "{err}" }
+            }
+
+            div { class: "flex items-center justify-between mt-2",
+                button {
+                    class: "text-sm bg-gray-600 hover:bg-gray-500 px-3 py-1 rounded",
+                    onclick: move |_| show_import.set(!show_import()),
+                    "Import JSON"
+                }
+                ExportButtons { content: json, filename: "workspace.json".to_string(), mime: "application/json" }
+            }
+            if show_import() {
+                textarea {
+                    class: "{field} h-24 mt-2 font-roboto text-xs",
+                    placeholder: "Paste a workspace.json export; it replaces the current workspace",
+                    value: "{import_input}",
+                    oninput: move |e| import_input.set(e.value())
+                }
+                button {
+                    class: "mt-2 text-sm bg-blue-600 hover:bg-blue-700 px-3 py-1 rounded",
+                    onclick: move |_| match from_json(&import_input()) {
+                        Ok(imported) => {
+                            selected_pool.set(imported.pools.first().map(|p| p.id));
+                            workspace.set(imported);
+                            import_input.set(String::new());
+                            show_import.set(false);
+                            error.set(None);
+                        }
+                        Err(e) => error.set(Some(e.to_string())),
+                    },
+                    "Replace Workspace"
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,139 @@
+// src/workspace/storage.rs
+use crate::workspace::types::{Workspace, WorkspaceError};
+
+#[cfg(target_family = "wasm")]
+const STORAGE_KEY: &str = "ipam-workspace";
+
+/// Desktop: a JSON file in the user's home (or %APPDATA% on Windows)
+#[cfg(not(target_family = "wasm"))]
+fn workspace_path() -> Option<std::path::PathBuf> {
+    let base = std::env::var_os("APPDATA").or_else(|| std::env::var_os("HOME"))?;
+    Some(std::path::PathBuf::from(base).join(".subnet-calculator").join("workspace.json"))
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn read_raw() -> Result<Option<String>, WorkspaceError> {
+    let path = workspace_path().ok_or(WorkspaceError::Storage("No home directory".into()))?;
+    match std::fs::read_to_string(&path) {
+        Ok(text) => Ok(Some(text)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(WorkspaceError::Storage(e.to_string())),
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn write_raw(text: &str) -> Result<(), WorkspaceError> {
+    let path = workspace_path().ok_or(WorkspaceError::Storage("No home directory".into()))?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(|e| WorkspaceError::Storage(e.to_string()))?;
+    }
+    std::fs::write(&path, text).map_err(|e| WorkspaceError::Storage(e.to_string()))
+}
+
+/// Web: the browser's localStorage
+#[cfg(target_family = "wasm")]
+fn local_storage() -> Result<web_sys::Storage, WorkspaceError> {
+    web_sys::window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .ok_or(WorkspaceError::Storage("localStorage unavailable".into()))
+}
+
+#[cfg(target_family = "wasm")]
+fn read_raw() -> Result<Option<String>, WorkspaceError> {
+    local_storage()?.get_item(STORAGE_KEY).map_err(|_| WorkspaceError::Storage("Read failed".into()))
+}
+
+#[cfg(target_family = "wasm")]
+fn write_raw(text: &str) -> Result<(), WorkspaceError> {
+    local_storage()?.set_item(STORAGE_KEY, text).map_err(|_| WorkspaceError::Storage("Write failed (quota?)".into()))
+}
+
+pub fn to_json(workspace: &Workspace) -> String {
+    serde_json::to_string_pretty(workspace).unwrap_or_default()
+}
+
+/// Parse and validate a saved or imported workspace. Ids must be unique and every allocation must
+/// belong to a pool; `next_id` (the last id handed out) is raised to the largest id so new
+/// entries never reuse one.
+pub fn from_json(text: &str) -> Result<Workspace, WorkspaceError> {
+    let mut workspace: Workspace = serde_json::from_str(text).map_err(|e| WorkspaceError::Storage(e.to_string()))?;
+
+    let mut ids = std::collections::HashSet::new();
+    for id in workspace.pools.iter().map(|p| p.id).chain(workspace.allocations.iter().map(|a| a.id)) {
+        if !ids.insert(id) {
+            return Err(WorkspaceError::DuplicateId(id));
+        }
+    }
+    if let Some(orphan) = workspace.allocations.iter().find(|a| !workspace.pools.iter().any(|p| p.id == a.pool_id)) {
+        return Err(WorkspaceError::OrphanAllocation(orphan.id));
+    }
+    if let Some(max) = ids.into_iter().max() {
+        workspace.next_id = workspace.next_id.max(max);
+    }
+    Ok(workspace)
+}
+
+/// Saved workspace, or an empty one on first run
+pub fn load_workspace() -> Result<Workspace, WorkspaceError> {
+    match read_raw()? {
+        Some(text) => from_json(&text),
+        None => Ok(Workspace { name: "Default".to_string(), ..Workspace::default() }),
+    }
+}
+
+pub fn save_workspace(workspace: &Workspace) -> Result<(), WorkspaceError> {
+    write_raw(&to_json(workspace))
+}
+
+#[cfg(not(target_family = "wasm"))]
+pub fn storage_location() -> String {
+    workspace_path().map(|p| p.display().to_string()).unwrap_or_default()
+}
+
+#[cfg(target_family = "wasm")]
+pub fn storage_location() -> String {
+    format!("localStorage[\"{}\"]", STORAGE_KEY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::address_math::HostBitsPolicy;
+    use crate::workspace::types::{AllocationDetails, AllocationRequest};
+
+    fn sample() -> Workspace {
+        let mut ws = Workspace { name: "Test".to_string(), ..Workspace::default() };
+        let pool = ws.add_pool("corp", "10.0.0.0/16".parse().unwrap(), HostBitsPolicy::Normalize).unwrap();
+        ws.allocate(pool, AllocationRequest::NextFree(24), AllocationDetails { vlan: Some(10), ..AllocationDetails::default() }, HostBitsPolicy::Normalize).unwrap();
+        ws
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let ws = sample();
+        assert_eq!(from_json(&to_json(&ws)), Ok(ws));
+    }
+
+    #[test]
+    fn raises_next_id_past_imported_ids() {
+        let mut ws = sample();
+        ws.next_id = 0;
+        let mut loaded = from_json(&to_json(&ws)).unwrap();
+        assert_eq!(loaded.next_id, 2);
+        let pool = loaded.add_pool("lab", "10.1.0.0/16".parse().unwrap(), HostBitsPolicy::Normalize).unwrap();
+        assert_eq!(pool, 3);
+    }
+
+    #[test]
+    fn rejects_invalid_imports() {
+        let mut duplicate = sample();
+        duplicate.allocations[0].id = duplicate.pools[0].id;
+        assert_eq!(from_json(&to_json(&duplicate)), Err(WorkspaceError::DuplicateId(1)));
+
+        let mut orphan = sample();
+        orphan.allocations[0].pool_id = 42;
+        assert_eq!(from_json(&to_json(&orphan)), Err(WorkspaceError::OrphanAllocation(2)));
+
+        assert!(matches!(from_json("{not json"), Err(WorkspaceError::Storage(_))));
+    }
+}
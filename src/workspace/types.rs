@@ -0,0 +1,90 @@
+use ipnet::IpNet;
+use crate::address_math::HostBits;
+use serde::{Deserialize, Serialize};
+
+/// Top-level block that allocations are carved from
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Pool {
+    pub id: u32,
+    pub name: String,
+    pub prefix: IpNet,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Allocation {
+    pub id: u32,
+    pub pool_id: u32,
+    pub prefix: IpNet,
+    pub name: String,
+    pub vlan: Option<u16>,
+    pub owner: String,
+    pub description: String,
+}
+
+/// Everything that gets persisted between sessions
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Workspace {
+    pub name: String,
+    pub pools: Vec<Pool>,
+    pub allocations: Vec<Allocation>,
+    pub next_id: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AllocationRequest {
+    NextFree(u8),     // First free block of this prefix length
+    Specific(IpNet),
+}
+
+/// Name, VLAN, owner and description attached to an allocation
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AllocationDetails {
+    pub name: String,
+    pub vlan: Option<u16>,
+    pub owner: String,
+    pub description: String,
+}
+
+/// Two allocations that overlap, e.g. after editing the saved file by hand
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Conflict {
+    pub first: u32,
+    pub second: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WorkspaceError {
+    PoolOverlap(String),     // Name of the existing pool
+    PoolNotFound,
+    PoolInUse,               // Pool still has allocations
+    FamilyMismatch,
+    OutsidePool,
+    InvalidPrefix,           // Requested size doesn't fit the pool
+    Conflict(IpNet),         // Existing allocation the request overlaps
+    PoolFull,
+    AllocationNotFound,
+    DuplicateId(u32),        // Imported pools/allocations sharing an id
+    OrphanAllocation(u32),   // Imported allocation whose pool doesn't exist
+    Storage(String),
+    HostBitsSet(HostBits),   // Rejected in strict mode
+}
+
+impl std::fmt::Display for WorkspaceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WorkspaceError::PoolOverlap(name) => write!(f, "Overlaps existing pool '{}'", name),
+            WorkspaceError::PoolNotFound => write!(f, "Pool not found"),
+            WorkspaceError::PoolInUse => write!(f, "Free the pool's allocations before removing it"),
+            WorkspaceError::FamilyMismatch => write!(f, "Address family doesn't match the pool"),
+            WorkspaceError::OutsidePool => write!(f, "Prefix is outside the pool"),
+            WorkspaceError::InvalidPrefix => write!(f, "Prefix length doesn't fit in the pool"),
+            WorkspaceError::Conflict(existing) => write!(f, "Overlaps existing allocation {}", existing),
+            WorkspaceError::PoolFull => write!(f, "No free block of that size left in the pool"),
+            WorkspaceError::AllocationNotFound => write!(f, "Allocation not found"),
+            WorkspaceError::DuplicateId(id) => write!(f, "Id {} is used more than once", id),
+            WorkspaceError::OrphanAllocation(id) => write!(f, "Allocation {} refers to a pool that doesn't exist", id),
+            WorkspaceError::Storage(msg) => write!(f, "Storage error: {}", msg),
+            WorkspaceError::HostBitsSet(found) => write!(f, "{}", found),
+        }
+    }
+}